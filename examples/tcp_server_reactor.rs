@@ -0,0 +1,212 @@
+// You can run this example from the root of the mio repo:
+// cargo run --example tcp_server_reactor --features="reactor net"
+//
+// This is the same server as the `tcp_server` example, but using
+// `mio::reactor` instead of dispatching events manually.
+use mio::event::Source;
+use mio::net::{TcpListener, TcpStream};
+use mio::reactor::{Control, Handler, Reactor, Readiness};
+use mio::{Interest, Registry, Token};
+use std::io::{self, Read, Write};
+use std::str::from_utf8;
+
+// Some data we'll send over the connection.
+const DATA: &[u8] = b"Hello world!\n";
+
+/// All event sources in the reactor must be of the same type.
+enum Socket {
+    Listener(TcpListener),
+    Stream(TcpStream),
+}
+
+impl Source for Socket {
+    fn register(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        match self {
+            Socket::Listener(listener) => listener.register(registry, token, interests),
+            Socket::Stream(stream) => stream.register(registry, token, interests),
+        }
+    }
+
+    fn reregister(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        match self {
+            Socket::Listener(listener) => listener.reregister(registry, token, interests),
+            Socket::Stream(stream) => stream.reregister(registry, token, interests),
+        }
+    }
+
+    fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
+        match self {
+            Socket::Listener(listener) => listener.deregister(registry),
+            Socket::Stream(stream) => stream.deregister(registry),
+        }
+    }
+}
+
+/// The handler doesn't need any state, it determines what to do based on the
+/// type of socket.
+struct Echo;
+
+impl Handler for Echo {
+    type Source = Socket;
+
+    fn ready(
+        &mut self,
+        socket: &mut Socket,
+        readiness: Readiness,
+        control: &mut Control<'_, Self>,
+    ) {
+        let result = match socket {
+            Socket::Listener(listener) => accept(listener, control),
+            Socket::Stream(connection) => handle_connection_event(connection, readiness),
+        };
+
+        match result {
+            // After we've written something we'll reregister the connection
+            // to only respond to readable events.
+            Ok(false) if readiness.is_writable() => {
+                if let Err(err) = control.reregister(socket, Interest::READABLE) {
+                    println!("Error: {}", err);
+                    control.remove();
+                }
+            }
+            Ok(false) => {}
+            Ok(true) => control.remove(),
+            Err(err) => {
+                println!("Error: {}", err);
+                control.remove();
+            }
+        }
+    }
+}
+
+fn main() -> io::Result<()> {
+    env_logger::init();
+
+    // Create a reactor, which creates a `Poll` instance for us.
+    let mut reactor = Reactor::new()?;
+
+    // Setup the TCP server socket, the reactor will register it for us.
+    let addr = "127.0.0.1:9000".parse().unwrap();
+    let server = TcpListener::bind(addr)?;
+    reactor.insert(Socket::Listener(server), Interest::READABLE, Echo)?;
+
+    println!("You can connect to the server using `nc`:");
+    println!(" $ nc 127.0.0.1 9000");
+    println!("You'll see our welcome message and anything you type we'll be printed here.");
+
+    loop {
+        reactor.run_once(None)?;
+    }
+}
+
+/// Accept all queued connections, always returns `Ok(false)`.
+fn accept(listener: &mut TcpListener, control: &mut Control<'_, Echo>) -> io::Result<bool> {
+    loop {
+        // Received an event for the TCP server socket, which indicates we can
+        // accept an connection.
+        let (connection, address) = match listener.accept() {
+            Ok((connection, address)) => (connection, address),
+            // If we get a `WouldBlock` error we know our listener has no more
+            // incoming connections queued, so we can return to polling and
+            // wait for some more.
+            Err(ref err) if would_block(err) => return Ok(false),
+            // If it was any other kind of error, something went wrong and we
+            // terminate the listener.
+            Err(err) => return Err(err),
+        };
+
+        println!("Accepted connection from: {}", address);
+
+        control.insert(
+            Socket::Stream(connection),
+            Interest::READABLE.add(Interest::WRITABLE),
+            Echo,
+        )?;
+    }
+}
+
+/// Returns `true` if the connection is done.
+fn handle_connection_event(connection: &mut TcpStream, readiness: Readiness) -> io::Result<bool> {
+    if readiness.is_writable() {
+        // We can (maybe) write to the connection.
+        match connection.write(DATA) {
+            // We want to write the entire `DATA` buffer in a single go. If we
+            // write less we'll return a short write error (same as
+            // `io::Write::write_all` does).
+            Ok(n) if n < DATA.len() => return Err(io::ErrorKind::WriteZero.into()),
+            Ok(_) => {}
+            // Would block "errors" are the OS's way of saying that the
+            // connection is not actually ready to perform this I/O operation.
+            Err(ref err) if would_block(err) => {}
+            // Got interrupted (how rude!), we'll try again.
+            Err(ref err) if interrupted(err) => {
+                return handle_connection_event(connection, readiness)
+            }
+            // Other errors we'll consider fatal.
+            Err(err) => return Err(err),
+        }
+    }
+
+    if readiness.is_readable() {
+        let mut connection_closed = false;
+        let mut received_data = vec![0; 4096];
+        let mut bytes_read = 0;
+        // We can (maybe) read from the connection.
+        loop {
+            match connection.read(&mut received_data[bytes_read..]) {
+                Ok(0) => {
+                    // Reading 0 bytes means the other side has closed the
+                    // connection or is done writing, then so are we.
+                    connection_closed = true;
+                    break;
+                }
+                Ok(n) => {
+                    bytes_read += n;
+                    if bytes_read == received_data.len() {
+                        received_data.resize(received_data.len() + 1024, 0);
+                    }
+                }
+                // Would block "errors" are the OS's way of saying that the
+                // connection is not actually ready to perform this I/O operation.
+                Err(ref err) if would_block(err) => break,
+                Err(ref err) if interrupted(err) => continue,
+                // Other errors we'll consider fatal.
+                Err(err) => return Err(err),
+            }
+        }
+
+        if bytes_read != 0 {
+            let received_data = &received_data[..bytes_read];
+            if let Ok(str_buf) = from_utf8(received_data) {
+                println!("Received data: {}", str_buf.trim_end());
+            } else {
+                println!("Received (none UTF-8) data: {:?}", received_data);
+            }
+        }
+
+        if connection_closed {
+            println!("Connection closed");
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
+}
+
+fn would_block(err: &io::Error) -> bool {
+    err.kind() == io::ErrorKind::WouldBlock
+}
+
+fn interrupted(err: &io::Error) -> bool {
+    err.kind() == io::ErrorKind::Interrupted
+}
@@ -0,0 +1,46 @@
+/* C API for Mio, enabled by the `capi` feature. See `src/capi.rs` for the
+ * documentation of the functions. */
+
+#ifndef MIO_H
+#define MIO_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#define MIO_READABLE 1
+#define MIO_WRITABLE 2
+#define MIO_ERROR 4
+#define MIO_READ_CLOSED 8
+#define MIO_WRITE_CLOSED 16
+#define MIO_PRIORITY 32
+
+typedef struct MioPoll MioPoll;
+
+typedef struct MioEvent {
+  uint64_t token;
+  uint32_t readiness;
+} MioEvent;
+
+int mio_poll_new(MioPoll **poll_out);
+
+void mio_poll_free(MioPoll *poll);
+
+/* Returns -EINVAL if `token` doesn't fit in a `size_t`, only possible on 32 bit
+ * platforms. */
+int mio_poll_register(MioPoll *poll, int fd, uint64_t token, uint32_t interests);
+
+int mio_poll_deregister(MioPoll *poll, int fd);
+
+/* Events that don't fit in `capacity` are returned by the following calls,
+ * without polling again. */
+int mio_poll_poll(MioPoll *poll, MioEvent *events_out, size_t capacity, int timeout_ms);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* MIO_H */
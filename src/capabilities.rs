@@ -0,0 +1,58 @@
+/// Guarantees provided by the system selector used by [`Poll`].
+///
+/// The values are determined at compile time based on the target platform,
+/// see [`Poll::capabilities`]. This allows portable code to pick a strategy
+/// without having to check the target OS itself.
+///
+/// [`Poll`]: ../struct.Poll.html
+/// [`Poll::capabilities`]: ../struct.Poll.html#method.capabilities
+///
+/// # Examples
+///
+/// ```
+/// use mio::features::Triggering;
+/// use mio::Poll;
+///
+/// let capabilities = Poll::capabilities();
+/// if capabilities.triggering == Triggering::Edge {
+///     // Read until a `WouldBlock` error is returned, otherwise we might not
+///     // get another event.
+/// }
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Capabilities {
+    /// How readiness events are triggered.
+    pub triggering: Triggering,
+    /// Whether or not registrations can be made oneshot, i.e. be disabled
+    /// after the first event, see [`Interest::ONESHOT`].
+    ///
+    /// [`Interest::ONESHOT`]: ../struct.Interest.html
+    pub oneshot: bool,
+    /// Whether or not [`Waker`] is supported.
+    ///
+    /// [`Waker`]: ../struct.Waker.html
+    pub waker: bool,
+    /// Whether or not [`Interest::PRIORITY`] is supported.
+    ///
+    /// [`Interest::PRIORITY`]: ../struct.Interest.html
+    pub priority: bool,
+    /// Whether or not regular files can be registered.
+    pub regular_files: bool,
+}
+
+/// How readiness events are triggered, see [`Capabilities::triggering`].
+///
+/// See the [`Poll`] documentation for more on the difference between the two.
+///
+/// [`Poll`]: ../struct.Poll.html
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Triggering {
+    /// An event is only returned when the readiness changed. Once an event is
+    /// received the operation must be performed until it returns a
+    /// `WouldBlock` error, otherwise no new event may be returned.
+    Edge,
+    /// An event is returned as long as the event source is ready.
+    Level,
+}
@@ -0,0 +1,331 @@
+//! C API for `Poll`.
+//!
+//! A minimal `extern "C"` interface to register file descriptors and poll for
+//! events, for embedding Mio in C programs. The matching header is
+//! `include/mio.h` in the source distribution.
+//!
+//! All functions that can fail return a negative `errno` value on error, e.g.
+//! `-EINVAL` if a pointer argument is null. Panics never unwind into C, if one
+//! occurs the process is aborted.
+
+use crate::event::Iter;
+use crate::sys::SourceFd;
+use crate::{Events, Interest, Poll, Token};
+
+use std::convert::TryFrom;
+use std::os::raw::c_int;
+use std::os::unix::io::RawFd;
+use std::panic::{self, AssertUnwindSafe};
+use std::time::Duration;
+use std::{io, process, ptr};
+
+/// Readable interest or readiness.
+pub const MIO_READABLE: u32 = 0b00_0001;
+/// Writable interest or readiness.
+pub const MIO_WRITABLE: u32 = 0b00_0010;
+/// Error readiness, see [`Event::is_error`].
+///
+/// [`Event::is_error`]: crate::event::Event::is_error
+pub const MIO_ERROR: u32 = 0b00_0100;
+/// Read closed readiness, see [`Event::is_read_closed`].
+///
+/// [`Event::is_read_closed`]: crate::event::Event::is_read_closed
+pub const MIO_READ_CLOSED: u32 = 0b00_1000;
+/// Write closed readiness, see [`Event::is_write_closed`].
+///
+/// [`Event::is_write_closed`]: crate::event::Event::is_write_closed
+pub const MIO_WRITE_CLOSED: u32 = 0b01_0000;
+/// Priority interest or readiness, interest is only supported on epoll
+/// platforms.
+pub const MIO_PRIORITY: u32 = 0b10_0000;
+
+/// Maximum capacity of the events buffer of `MioPoll`, independent of the
+/// `capacity` passed to `mio_poll_poll`.
+const EVENTS_CAPACITY: usize = 1024;
+
+/// Opaque handle to a `Poll` instance and its events buffer.
+#[derive(Debug)]
+pub struct MioPoll {
+    poll: Poll,
+    /// Events of the last poll, the events not yet written to `events_out`
+    /// are returned by the following calls to `mio_poll_poll`.
+    events: Events,
+}
+
+/// A readiness event, see [`Event`].
+///
+/// [`Event`]: crate::event::Event
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct MioEvent {
+    /// Token passed to `mio_poll_register`.
+    pub token: u64,
+    /// Readiness, a combination of the `MIO_*` constants.
+    pub readiness: u32,
+}
+
+/// Creates a new `Poll` instance, writing it to `poll_out`.
+///
+/// Returns `0` on success. The instance must be freed using `mio_poll_free`.
+///
+/// # Safety
+///
+/// `poll_out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn mio_poll_new(poll_out: *mut *mut MioPoll) -> c_int {
+    guard(|| {
+        if poll_out.is_null() {
+            return -libc::EINVAL;
+        }
+        match Poll::new() {
+            Ok(poll) => {
+                let poll = Box::new(MioPoll {
+                    poll,
+                    events: Events::with_capacity(0),
+                });
+                *poll_out = Box::into_raw(poll);
+                0
+            }
+            Err(err) => errno(err),
+        }
+    })
+}
+
+/// Frees a `Poll` instance created by `mio_poll_new`. Does nothing if `poll`
+/// is null.
+///
+/// # Safety
+///
+/// `poll` must be null or created by `mio_poll_new` and not freed before.
+#[no_mangle]
+pub unsafe extern "C" fn mio_poll_free(poll: *mut MioPoll) {
+    guard(|| {
+        if !poll.is_null() {
+            drop(Box::from_raw(poll));
+        }
+        0
+    });
+}
+
+/// Registers `fd` with `interests`, a combination of `MIO_READABLE`,
+/// `MIO_WRITABLE` and `MIO_PRIORITY`, see [`Registry::register`].
+///
+/// Returns `0` on success. Returns `-EINVAL` if `interests` is empty or
+/// contains unsupported interests, or if `token` doesn't fit in a `usize`
+/// (only possible on 32 bit platforms).
+///
+/// [`Registry::register`]: crate::Registry::register
+///
+/// # Safety
+///
+/// `poll` must be created by `mio_poll_new`.
+#[no_mangle]
+pub unsafe extern "C" fn mio_poll_register(
+    poll: *mut MioPoll,
+    fd: RawFd,
+    token: u64,
+    interests: u32,
+) -> c_int {
+    guard(|| {
+        let (poll, token, interests) = match (
+            poll.as_ref(),
+            usize::try_from(token),
+            to_interest(interests),
+        ) {
+            (Some(poll), Ok(token), Some(interests)) => (poll, Token(token), interests),
+            _ => return -libc::EINVAL,
+        };
+        match poll
+            .poll
+            .registry()
+            .register(&mut SourceFd(&fd), token, interests)
+        {
+            Ok(()) => 0,
+            Err(err) => errno(err),
+        }
+    })
+}
+
+/// Deregisters `fd`, see [`Registry::deregister`].
+///
+/// Returns `0` on success.
+///
+/// [`Registry::deregister`]: crate::Registry::deregister
+///
+/// # Safety
+///
+/// `poll` must be created by `mio_poll_new`.
+#[no_mangle]
+pub unsafe extern "C" fn mio_poll_deregister(poll: *mut MioPoll, fd: RawFd) -> c_int {
+    guard(|| {
+        let poll = match poll.as_ref() {
+            Some(poll) => poll,
+            None => return -libc::EINVAL,
+        };
+        match poll.poll.registry().deregister(&mut SourceFd(&fd)) {
+            Ok(()) => 0,
+            Err(err) => errno(err),
+        }
+    })
+}
+
+/// Polls for at most `capacity` events, writing them to `events_out`, see
+/// [`Poll::poll`]. A negative `timeout_ms` blocks until an event is received.
+///
+/// The events are collected in a buffer owned by `poll`, holding at most 1024
+/// events. If more events were received than fit in `capacity` the remaining
+/// events are written by the following calls, without polling again (and
+/// thus without blocking), until all of them are returned.
+///
+/// Returns the number of events written. Like `Poll::poll` this doesn't
+/// retry if the call is interrupted, `-EINTR` is returned instead.
+///
+/// # Safety
+///
+/// `poll` must be created by `mio_poll_new` and `events_out` must be valid
+/// for writes of `capacity` events.
+#[no_mangle]
+pub unsafe extern "C" fn mio_poll_poll(
+    poll: *mut MioPoll,
+    events_out: *mut MioEvent,
+    capacity: usize,
+    timeout_ms: c_int,
+) -> c_int {
+    guard(|| {
+        let poll = match poll.as_mut() {
+            Some(poll) => poll,
+            None => return -libc::EINVAL,
+        };
+        if events_out.is_null() || capacity == 0 {
+            return -libc::EINVAL;
+        }
+        // Never return more events than the buffer holds, this also ensures
+        // the count fits in a `c_int`.
+        let capacity = capacity.min(EVENTS_CAPACITY);
+
+        // Return the events left over by the previous call first.
+        let n = write_events(poll.events.drain_bounded(capacity), events_out);
+        if n != 0 {
+            return n as c_int;
+        }
+
+        // The buffer is only reallocated when growing, all of its events are
+        // returned at this point.
+        if poll.events.capacity() < capacity {
+            poll.events = Events::with_capacity(capacity);
+        }
+        let timeout = if timeout_ms < 0 {
+            None
+        } else {
+            Some(Duration::from_millis(timeout_ms as u64))
+        };
+        if let Err(err) = poll.poll.poll(&mut poll.events, timeout) {
+            return errno(err);
+        }
+        write_events(poll.events.drain_bounded(capacity), events_out) as c_int
+    })
+}
+
+/// Writes `events` to `events_out`, returning the number of events written.
+///
+/// # Safety
+///
+/// `events_out` must be valid for writes of all `events`.
+unsafe fn write_events(events: Iter<'_>, events_out: *mut MioEvent) -> usize {
+    let mut n = 0;
+    for event in events {
+        let event = MioEvent {
+            token: usize::from(event.token()) as u64,
+            readiness: to_readiness(event),
+        };
+        ptr::write(events_out.add(n), event);
+        n += 1;
+    }
+    n
+}
+
+/// Calls `f`, aborting the process if it panics.
+fn guard<F>(f: F) -> c_int
+where
+    F: FnOnce() -> c_int,
+{
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(res) => res,
+        Err(_) => process::abort(),
+    }
+}
+
+/// Converts `err` into a negative `errno` value.
+fn errno(err: io::Error) -> c_int {
+    -err.raw_os_error().unwrap_or(libc::EIO)
+}
+
+fn to_interest(interests: u32) -> Option<Interest> {
+    #[cfg(any(
+        target_os = "android",
+        target_os = "illumos",
+        target_os = "linux",
+        target_os = "solaris"
+    ))]
+    const SUPPORTED: u32 = MIO_READABLE | MIO_WRITABLE | MIO_PRIORITY;
+    #[cfg(not(any(
+        target_os = "android",
+        target_os = "illumos",
+        target_os = "linux",
+        target_os = "solaris"
+    )))]
+    const SUPPORTED: u32 = MIO_READABLE | MIO_WRITABLE;
+
+    if interests & !SUPPORTED != 0 {
+        return None;
+    }
+
+    let mut interest = None;
+    let mut add = |other: Interest| {
+        interest = Some(match interest {
+            Some(interest) => interest | other,
+            None => other,
+        });
+    };
+    if interests & MIO_READABLE != 0 {
+        add(Interest::READABLE);
+    }
+    if interests & MIO_WRITABLE != 0 {
+        add(Interest::WRITABLE);
+    }
+    #[cfg(any(
+        target_os = "android",
+        target_os = "illumos",
+        target_os = "linux",
+        target_os = "solaris"
+    ))]
+    {
+        if interests & MIO_PRIORITY != 0 {
+            add(Interest::PRIORITY);
+        }
+    }
+    interest
+}
+
+fn to_readiness(event: &crate::event::Event) -> u32 {
+    let mut readiness = 0;
+    if event.is_readable() {
+        readiness |= MIO_READABLE;
+    }
+    if event.is_writable() {
+        readiness |= MIO_WRITABLE;
+    }
+    if event.is_error() {
+        readiness |= MIO_ERROR;
+    }
+    if event.is_read_closed() {
+        readiness |= MIO_READ_CLOSED;
+    }
+    if event.is_write_closed() {
+        readiness |= MIO_WRITE_CLOSED;
+    }
+    if event.is_priority() {
+        readiness |= MIO_PRIORITY;
+    }
+    readiness
+}
@@ -40,6 +40,15 @@ impl Event {
     /// the socket will also receive a readable or writable event. Reading or
     /// writing to the socket will result in an error.
     ///
+    /// If data was received before the error occurred the event is also
+    /// readable, the data can be read before the error is returned.
+    ///
+    /// The event source is not deregistered after receiving an error event. To
+    /// treat an error as terminal the event source must be [deregistered] (or
+    /// dropped) manually.
+    ///
+    /// [deregistered]: ../struct.Registry.html#method.deregister
+    ///
     /// # Notes
     ///
     /// Method is available on all platforms, but not all platforms trigger the
@@ -75,12 +84,22 @@ impl Event {
     /// return readiness when read half is closed, it is guaranteed that
     /// false-positives will not occur.
     ///
+    /// Like all readiness this is edge-triggered: it's returned once when the
+    /// read half is closed, not on every call to [`Poll::poll`] while it stays
+    /// closed.
+    ///
+    /// To receive this readiness without receiving events for incoming data
+    /// the event source can be registered with only [read closed] interest.
+    ///
+    /// [`Poll::poll`]: ../struct.Poll.html#method.poll
+    /// [read closed]: ../struct.Interest.html#associatedconstant.READ_CLOSED
+    ///
     /// The table below shows what flags are checked on what OS.
     ///
     /// | [OS selector] | Flag(s) checked |
     /// |---------------|-----------------|
     /// | [epoll]       | `EPOLLHUP`, or  |
-    /// |               | `EPOLLIN` and `EPOLLRDHUP` |
+    /// |               | `EPOLLRDHUP`    |
     /// | [kqueue]      | `EV_EOF`        |
     ///
     /// [OS selector]: ../struct.Poll.html#implementation-notes
@@ -142,6 +161,35 @@ impl Event {
         sys::event::is_priority(&self.inner)
     }
 
+    /// Returns true if the event contains band writable readiness, i.e.
+    /// priority band (out-of-band) data can be written.
+    ///
+    /// Only returned when the source is registered with [band writable]
+    /// interest, it's independent of [writable readiness].
+    ///
+    /// [band writable]: ../struct.Interest.html#associatedconstant.BAND_WRITABLE
+    /// [writable readiness]: #method.is_writable
+    ///
+    /// # Notes
+    ///
+    /// Method is available on all platforms, but not all platforms trigger the
+    /// band writable event.
+    ///
+    /// The table below shows what flags are checked on what OS.
+    ///
+    /// | [OS selector] | Flag(s) checked |
+    /// |---------------|-----------------|
+    /// | [epoll]       | `EPOLLWRBAND`   |
+    /// | [kqueue]      | *Not supported* |
+    ///
+    /// [OS selector]: ../struct.Poll.html#implementation-notes
+    /// [epoll]: http://man7.org/linux/man-pages/man7/epoll.7.html
+    /// [kqueue]: https://www.freebsd.org/cgi/man.cgi?query=kqueue&sektion=2
+    #[inline]
+    pub fn is_band_writable(&self) -> bool {
+        sys::event::is_band_writable(&self.inner)
+    }
+
     /// Returns true if the event contains AIO readiness.
     ///
     /// # Notes
@@ -174,6 +222,11 @@ impl Event {
         sys::event::is_lio(&self.inner)
     }
 
+    /// Returns the platform specific event.
+    pub(crate) fn into_sys_event(self) -> sys::Event {
+        self.inner
+    }
+
     /// Create a reference to an `Event` from a platform specific event.
     pub(crate) fn from_sys_event_ref(sys_event: &sys::Event) -> &Event {
         unsafe {
@@ -201,6 +254,7 @@ impl fmt::Debug for Event {
             .field("read_closed", &self.is_read_closed())
             .field("write_closed", &self.is_write_closed())
             .field("priority", &self.is_priority())
+            .field("band_writable", &self.is_band_writable())
             .field("aio", &self.is_aio())
             .field("lio", &self.is_lio());
 
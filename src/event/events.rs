@@ -1,7 +1,9 @@
 use crate::event::Event;
-use crate::sys;
+use crate::{sys, Token};
 
 use std::fmt;
+use std::iter::FusedIterator;
+use std::time::Instant;
 
 /// A collection of readiness events.
 ///
@@ -10,6 +12,10 @@ use std::fmt;
 /// single `Events` instance is created at the same time as a [`Poll`] and
 /// reused on each call to [`Poll::poll`].
 ///
+/// `Events` only holds the events returned by the last call to
+/// [`Poll::poll`] and doesn't refer to the `Poll` instance, so it can be
+/// passed to different `Poll` instances in turn.
+///
 /// See [`Poll`] for more documentation on polling.
 ///
 /// [`Poll::poll`]: ../struct.Poll.html#method.poll
@@ -41,6 +47,13 @@ use std::fmt;
 /// ```
 pub struct Events {
     inner: sys::Events,
+    /// Capacity requested in `with_capacity`, `inner` can grow beyond it when
+    /// events are added using `Extend`.
+    capacity: usize,
+    ready_at: Option<Instant>,
+    deadline_expired: bool,
+    /// Number of events returned by `drain_bounded`.
+    drained: usize,
 }
 
 /// [`Events`] iterator.
@@ -76,11 +89,21 @@ pub struct Events {
 pub struct Iter<'a> {
     inner: &'a Events,
     pos: usize,
+    end: usize,
 }
 
 impl Events {
     /// Return a new `Events` capable of holding up to `capacity` events.
     ///
+    /// If more event sources are ready than fit in `capacity` the remaining
+    /// events are not lost, they are returned by the following calls to
+    /// [`Poll::poll`]. On Linux and Android `epoll(7)` returns the ready
+    /// sources round robin, so no source is starved by the others: with `n`
+    /// ready sources all of them are returned within `ceil(n / capacity)`
+    /// polls. Other platforms don't guarantee the order of the events.
+    ///
+    /// [`Poll::poll`]: ../struct.Poll.html#method.poll
+    ///
     /// # Examples
     ///
     /// ```
@@ -92,11 +115,22 @@ impl Events {
     pub fn with_capacity(capacity: usize) -> Events {
         Events {
             inner: sys::Events::with_capacity(capacity),
+            capacity,
+            ready_at: None,
+            deadline_expired: false,
+            drained: 0,
         }
     }
 
     /// Returns the number of `Event` values that `self` can hold.
     ///
+    /// This is the maximum number of events returned by a single call to
+    /// [`Poll::poll`]. It's not changed by adding events using `extend`,
+    /// which means that [`len`] can be larger than the capacity.
+    ///
+    /// [`Poll::poll`]: ../struct.Poll.html#method.poll
+    /// [`len`]: #method.len
+    ///
     /// ```
     /// use mio::Events;
     ///
@@ -104,7 +138,7 @@ impl Events {
     /// assert_eq!(1024, events.capacity());
     /// ```
     pub fn capacity(&self) -> usize {
-        self.inner.capacity()
+        self.capacity
     }
 
     /// Returns `true` if `self` contains no `Event` values.
@@ -149,9 +183,189 @@ impl Events {
         Iter {
             inner: self,
             pos: 0,
+            end: self.len(),
+        }
+    }
+
+    /// Returns an iterator over at most `n` events that haven't been returned
+    /// by a previous call to `drain_bounded`.
+    ///
+    /// This can be used to handle the events in chunks of at most `n` events,
+    /// interleaved with other work, without calling [`Poll::poll`] in between.
+    /// Once all events are returned the iterator is empty. The events aren't
+    /// removed, [`iter`] still returns all of them. [`clear`] and
+    /// [`Poll::poll`] replace the events and start draining from the first
+    /// event again.
+    ///
+    /// [`Poll::poll`]: ../struct.Poll.html#method.poll
+    /// [`iter`]: #method.iter
+    /// [`clear`]: #method.clear
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(feature = "os-poll", doc = "```")]
+    #[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::{Events, Poll};
+    /// use std::time::Duration;
+    ///
+    /// let mut events = Events::with_capacity(1024);
+    /// let mut poll = Poll::new()?;
+    ///
+    /// // Register handles with `poll`.
+    ///
+    /// poll.poll(&mut events, Some(Duration::from_millis(100)))?;
+    ///
+    /// loop {
+    ///     let chunk = events.drain_bounded(16);
+    ///     if chunk.len() == 0 {
+    ///         break;
+    ///     }
+    ///     for event in chunk {
+    ///         println!("Got an event for {:?}", event.token());
+    ///     }
+    ///     // Do some other work.
+    /// }
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn drain_bounded(&mut self, n: usize) -> Iter<'_> {
+        let pos = self.drained;
+        self.drained = pos.saturating_add(n).min(self.len());
+        Iter {
+            inner: self,
+            pos,
+            end: self.drained,
         }
     }
 
+    /// Returns the number of `Event` values in `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mio::Events;
+    ///
+    /// let events = Events::with_capacity(1024);
+    /// assert_eq!(events.len(), 0);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Returns the `Event` at position `index`, or `None` if `index` is out of
+    /// bounds. Events are in the same order as returned by [`iter`].
+    ///
+    /// [`iter`]: #method.iter
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(feature = "os-poll", doc = "```")]
+    #[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::{Events, Poll};
+    /// use std::time::Duration;
+    ///
+    /// let mut events = Events::with_capacity(1024);
+    /// let mut poll = Poll::new()?;
+    ///
+    /// // Register handles with `poll`.
+    ///
+    /// poll.poll(&mut events, Some(Duration::from_millis(100)))?;
+    ///
+    /// // Handle the events in two halves.
+    /// let half = events.len() / 2;
+    /// for index in 0..half {
+    ///     let event = events.get(index).unwrap();
+    ///     println!("Got an event for {:?}", event.token());
+    /// }
+    /// for event in events.iter().skip(half) {
+    ///     println!("Got an event for {:?}", event.token());
+    /// }
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn get(&self, index: usize) -> Option<&Event> {
+        self.inner.get(index).map(Event::from_sys_event_ref)
+    }
+
+    /// Returns an iterator over the tokens of the `Event` values.
+    ///
+    /// The token is returned once per event, regardless of the readiness of
+    /// the event. Note however that on platforms that use `kqueue(2)` a single
+    /// event source can be returned as two events, one for readable and one
+    /// for writable readiness, in which case its token is returned twice.
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(feature = "os-poll", doc = "```")]
+    #[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::{Events, Poll};
+    /// use std::time::Duration;
+    ///
+    /// let mut events = Events::with_capacity(1024);
+    /// let mut poll = Poll::new()?;
+    ///
+    /// // Register handles with `poll`.
+    ///
+    /// poll.poll(&mut events, Some(Duration::from_millis(100)))?;
+    ///
+    /// for token in events.ready_tokens() {
+    ///     println!("{:?} is ready", token);
+    /// }
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn ready_tokens(&self) -> impl Iterator<Item = Token> + '_ {
+        self.iter().map(Event::token)
+    }
+
+    /// Returns an iterator over the events with [readable] readiness.
+    ///
+    /// [readable]: Event::is_readable
+    pub fn readable(&self) -> impl Iterator<Item = &Event> + '_ {
+        self.iter().filter(|event| event.is_readable())
+    }
+
+    /// Returns an iterator over the events with [write closed] readiness, e.g.
+    /// because the peer closed the receiving end of a pipe.
+    ///
+    /// This is a convenience to handle the write side closing separately from
+    /// the other readiness, the events are also returned by [`iter`].
+    ///
+    /// [write closed]: Event::is_write_closed
+    /// [`iter`]: Events::iter
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(feature = "os-poll", doc = "```")]
+    #[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::{Events, Poll};
+    /// use std::time::Duration;
+    ///
+    /// let mut events = Events::with_capacity(1024);
+    /// let mut poll = Poll::new()?;
+    ///
+    /// // Register handles with `poll`.
+    ///
+    /// poll.poll(&mut events, Some(Duration::from_millis(100)))?;
+    ///
+    /// for event in events.write_closed() {
+    ///     println!("{:?} can't be written to anymore", event.token());
+    /// }
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn write_closed(&self) -> impl Iterator<Item = &Event> + '_ {
+        self.iter().filter(|event| event.is_write_closed())
+    }
+
     /// Clearing all `Event` values from container explicitly.
     ///
     /// # Notes
@@ -183,10 +397,76 @@ impl Events {
     /// ```
     pub fn clear(&mut self) {
         self.inner.clear();
+        self.ready_at = None;
+        self.deadline_expired = false;
+        self.drained = 0;
+    }
+
+    /// Returns the moment the events were collected by the last call to
+    /// [`Poll::poll`].
+    ///
+    /// The timestamp is taken once per call, right after the underlying
+    /// system call returns, so `ready_at().elapsed()` can be used to measure
+    /// the delay between the OS reporting readiness and an event being
+    /// handled. Returns `None` if `self` wasn't passed to a successful call to
+    /// `poll` yet, or if it was [cleared] since.
+    ///
+    /// [`Poll::poll`]: ../struct.Poll.html#method.poll
+    /// [cleared]: #method.clear
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(feature = "os-poll", doc = "```")]
+    #[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::{Events, Poll};
+    /// use std::time::Duration;
+    ///
+    /// let mut events = Events::with_capacity(1024);
+    /// let mut poll = Poll::new()?;
+    /// assert!(events.ready_at().is_none());
+    ///
+    /// poll.poll(&mut events, Some(Duration::from_millis(10)))?;
+    ///
+    /// if let Some(ready_at) = events.ready_at() {
+    ///     println!("events collected {:?} ago", ready_at.elapsed());
+    /// }
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn ready_at(&self) -> Option<Instant> {
+        self.ready_at
     }
 
-    /// Returns the inner `sys::Events`.
+    /// Returns `true` if the last call to [`Poll::poll`] returned because the
+    /// deadline set by [`Poll::wake_after`] expired.
+    ///
+    /// Note that `self` may still contain events received before the deadline.
+    ///
+    /// [`Poll::poll`]: ../struct.Poll.html#method.poll
+    /// [`Poll::wake_after`]: ../struct.Poll.html#method.wake_after
+    pub fn deadline_expired(&self) -> bool {
+        self.deadline_expired
+    }
+
+    pub(crate) fn set_deadline_expired(&mut self, expired: bool) {
+        self.deadline_expired = expired;
+    }
+
+    /// Sets the moment the events were collected, see [`Events::ready_at`].
+    pub(crate) fn set_ready_at(&mut self, ready_at: Option<Instant>) {
+        self.ready_at = ready_at;
+    }
+
+    /// Returns the inner `sys::Events` to collect new events into.
     pub(crate) fn sys(&mut self) -> &mut sys::Events {
+        self.drained = 0;
+        if self.inner.capacity() > self.capacity {
+            // Drop the allocation grown by `Extend`, so the selector returns
+            // at most `capacity` events again.
+            self.inner = sys::Events::with_capacity(self.capacity);
+        }
         &mut self.inner
     }
 }
@@ -200,29 +480,108 @@ impl<'a> IntoIterator for &'a Events {
     }
 }
 
+/// Add events to `Events`, e.g. to merge the events of multiple [`Poll`]
+/// instances.
+///
+/// Events are appended after the events already present, the tokens of the
+/// events are preserved. More events can be added than `Events` has
+/// [capacity] for, the capacity itself doesn't change: the next call to
+/// [`Poll::poll`] replaces all events and returns at most `capacity` events.
+///
+/// The added events don't change [`ready_at`] and [`deadline_expired`], those
+/// keep describing the last call to `Poll::poll` using `self` (if any). Keep
+/// the values of the polled `Events` separately if they're needed for the
+/// merged events.
+///
+/// [`Poll`]: ../struct.Poll.html
+/// [`Poll::poll`]: ../struct.Poll.html#method.poll
+/// [capacity]: struct.Events.html#method.capacity
+/// [`ready_at`]: struct.Events.html#method.ready_at
+/// [`deadline_expired`]: struct.Events.html#method.deadline_expired
+///
+/// # Examples
+///
+#[cfg_attr(feature = "os-poll", doc = "```")]
+#[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use mio::{Events, Poll};
+/// use std::time::Duration;
+///
+/// let mut poll1 = Poll::new()?;
+/// let mut poll2 = Poll::new()?;
+/// let mut events = Events::with_capacity(1024);
+/// let mut all_events = Events::with_capacity(1024);
+///
+/// // Register handles with `poll1` and `poll2`.
+///
+/// poll1.poll(&mut events, Some(Duration::from_millis(100)))?;
+/// all_events.extend(&events);
+/// poll2.poll(&mut events, Some(Duration::from_millis(100)))?;
+/// all_events.extend(&events);
+///
+/// for event in all_events.iter() {
+///     println!("Got an event for {:?}", event.token());
+/// }
+/// #     Ok(())
+/// # }
+/// ```
+impl Extend<Event> for Events {
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = Event>,
+    {
+        for event in iter {
+            self.inner.push(event.into_sys_event());
+        }
+    }
+}
+
+impl<'a> Extend<&'a Event> for Events {
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = &'a Event>,
+    {
+        self.extend(iter.into_iter().cloned())
+    }
+}
+
 impl<'a> Iterator for Iter<'a> {
     type Item = &'a Event;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let ret = self
-            .inner
-            .inner
-            .get(self.pos)
-            .map(Event::from_sys_event_ref);
+        if self.pos == self.end {
+            return None;
+        }
+        let ret = self.inner.get(self.pos);
         self.pos += 1;
         ret
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let size = self.inner.inner.len();
+        let size = self.end - self.pos;
         (size, Some(size))
     }
 
     fn count(self) -> usize {
-        self.inner.inner.len()
+        self.end - self.pos
     }
 }
 
+impl<'a> DoubleEndedIterator for Iter<'a> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.pos == self.end {
+            return None;
+        }
+        self.end -= 1;
+        self.inner.get(self.end)
+    }
+}
+
+impl<'a> ExactSizeIterator for Iter<'a> {}
+
+impl<'a> FusedIterator for Iter<'a> {}
+
 impl fmt::Debug for Events {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_list().entries(self).finish()
@@ -32,9 +32,54 @@ const WRITABLE: u8 = 0b0010;
 const AIO: u8 = 0b0100;
 #[cfg_attr(not(target_os = "freebsd"), allow(dead_code))]
 const LIO: u8 = 0b1000;
+#[cfg_attr(
+    not(any(
+        target_os = "android",
+        target_os = "illumos",
+        target_os = "linux",
+        target_os = "solaris"
+    )),
+    allow(dead_code)
+)]
+const PRIORITY: u8 = 0b0001_0000;
+#[cfg_attr(
+    not(any(
+        target_os = "android",
+        target_os = "illumos",
+        target_os = "linux",
+        target_os = "solaris"
+    )),
+    allow(dead_code)
+)]
+const BAND_WRITABLE: u8 = 0b0010_0000;
+#[cfg_attr(
+    not(any(
+        target_os = "android",
+        target_os = "illumos",
+        target_os = "linux",
+        target_os = "solaris"
+    )),
+    allow(dead_code)
+)]
+const ONESHOT: u8 = 0b0100_0000;
+#[cfg_attr(
+    not(any(
+        target_os = "android",
+        target_os = "illumos",
+        target_os = "linux",
+        target_os = "solaris"
+    )),
+    allow(dead_code)
+)]
+const READ_CLOSED: u8 = 0b1000_0000;
 
 impl Interest {
     /// Returns a `Interest` set representing readable interests.
+    ///
+    /// This only includes normal data, on platforms that support it priority
+    /// (out-of-band) data requires [priority] interest.
+    ///
+    /// [priority]: struct.Interest.html#associatedconstant.PRIORITY
     pub const READABLE: Interest = Interest(unsafe { NonZeroU8::new_unchecked(READABLE) });
 
     /// Returns a `Interest` set representing writable interests.
@@ -53,6 +98,79 @@ impl Interest {
     #[cfg(target_os = "freebsd")]
     pub const LIO: Interest = Interest(unsafe { NonZeroU8::new_unchecked(LIO) });
 
+    /// Returns a `Interest` set representing priority completion interests.
+    ///
+    /// This is independent of [readable] interest, registering only priority
+    /// interest will not deliver events for normal data.
+    ///
+    /// [readable]: struct.Interest.html#associatedconstant.READABLE
+    #[cfg(any(
+        target_os = "android",
+        target_os = "illumos",
+        target_os = "linux",
+        target_os = "solaris"
+    ))]
+    pub const PRIORITY: Interest = Interest(unsafe { NonZeroU8::new_unchecked(PRIORITY) });
+
+    /// Returns a `Interest` set representing band writable interests, i.e.
+    /// when priority band (out-of-band) data can be written.
+    ///
+    /// This is independent of [writable] interest, registering only band
+    /// writable interest will not deliver normal writable events.
+    ///
+    /// [writable]: struct.Interest.html#associatedconstant.WRITABLE
+    #[cfg(any(
+        target_os = "android",
+        target_os = "illumos",
+        target_os = "linux",
+        target_os = "solaris"
+    ))]
+    pub const BAND_WRITABLE: Interest =
+        Interest(unsafe { NonZeroU8::new_unchecked(BAND_WRITABLE) });
+
+    /// Returns a `Interest` set representing oneshot registration.
+    ///
+    /// This is not an interest in any readiness itself, but modifies the
+    /// other interests: once a single event is returned for the event source
+    /// it is disabled, no further events are returned until the event source
+    /// is [reregistered]. Like all registrations this is still edge-triggered,
+    /// so reregistering only returns an event if the event source is ready at
+    /// that point.
+    ///
+    /// As it isn't a readiness interest it must be combined with at least one,
+    /// registering only oneshot interest returns an [`InvalidInput`] error.
+    ///
+    /// Only available on platforms using epoll. kqueue's `EV_ONESHOT` applies
+    /// to each filter separately, so a source registered with readable and
+    /// writable interests would keep returning events for the filter that
+    /// didn't fire yet, which doesn't match the semantics above.
+    ///
+    /// [reregistered]: struct.Registry.html#method.reregister
+    /// [`InvalidInput`]: std::io::ErrorKind::InvalidInput
+    #[cfg(any(
+        target_os = "android",
+        target_os = "illumos",
+        target_os = "linux",
+        target_os = "solaris"
+    ))]
+    pub const ONESHOT: Interest = Interest(unsafe { NonZeroU8::new_unchecked(ONESHOT) });
+
+    /// Returns a `Interest` set representing read closed interests, i.e. when
+    /// the peer shuts down the write half of its socket.
+    ///
+    /// This is independent of [readable] interest, registering only read
+    /// closed interest will not deliver events when data is received. Events
+    /// are still returned when the event source enters an error state.
+    ///
+    /// [readable]: struct.Interest.html#associatedconstant.READABLE
+    #[cfg(any(
+        target_os = "android",
+        target_os = "illumos",
+        target_os = "linux",
+        target_os = "solaris"
+    ))]
+    pub const READ_CLOSED: Interest = Interest(unsafe { NonZeroU8::new_unchecked(READ_CLOSED) });
+
     /// Add together two `Interest`.
     ///
     /// This does the same thing as the `BitOr` implementation, but is a
@@ -72,7 +190,9 @@ impl Interest {
 
     /// Removes `other` `Interest` from `self`.
     ///
-    /// Returns `None` if the set would be empty after removing `other`.
+    /// Returns `None` if the set would be empty after removing `other`. Note
+    /// that only [oneshot] can remain, which can't be used to register an
+    /// event source as it isn't an interest in any readiness.
     ///
     /// ```
     /// use mio::Interest;
@@ -90,6 +210,8 @@ impl Interest {
     /// // Its also possible to remove multiple interests at once.
     /// assert_eq!(RW_INTERESTS.remove(RW_INTERESTS), None);
     /// ```
+    ///
+    /// [oneshot]: struct.Interest.html#associatedconstant.ONESHOT
     pub fn remove(self, other: Interest) -> Option<Interest> {
         NonZeroU8::new(self.0.get() & !other.0.get()).map(Interest)
     }
@@ -113,6 +235,32 @@ impl Interest {
     pub const fn is_lio(self) -> bool {
         (self.0.get() & LIO) != 0
     }
+
+    /// Returns true if `Interest` contains priority readiness.
+    pub const fn is_priority(self) -> bool {
+        (self.0.get() & PRIORITY) != 0
+    }
+
+    /// Returns true if `Interest` contains band writable readiness.
+    pub const fn is_band_writable(self) -> bool {
+        (self.0.get() & BAND_WRITABLE) != 0
+    }
+
+    /// Returns true if `Interest` contains oneshot registration.
+    pub const fn is_oneshot(self) -> bool {
+        (self.0.get() & ONESHOT) != 0
+    }
+
+    /// Returns true if `Interest` contains read closed readiness.
+    pub const fn is_read_closed(self) -> bool {
+        (self.0.get() & READ_CLOSED) != 0
+    }
+
+    /// Returns false if `Interest` doesn't contain any readiness, i.e. only
+    /// oneshot registration.
+    pub(crate) const fn has_readiness(self) -> bool {
+        self.0.get() != ONESHOT
+    }
 }
 
 impl ops::BitOr for Interest {
@@ -173,6 +321,42 @@ impl fmt::Debug for Interest {
                 one = true
             }
         }
+        #[cfg(any(
+            target_os = "android",
+            target_os = "illumos",
+            target_os = "linux",
+            target_os = "solaris"
+        ))]
+        {
+            if self.is_priority() {
+                if one {
+                    write!(fmt, " | ")?
+                }
+                write!(fmt, "PRIORITY")?;
+                one = true
+            }
+            if self.is_band_writable() {
+                if one {
+                    write!(fmt, " | ")?
+                }
+                write!(fmt, "BAND_WRITABLE")?;
+                one = true
+            }
+            if self.is_oneshot() {
+                if one {
+                    write!(fmt, " | ")?
+                }
+                write!(fmt, "ONESHOT")?;
+                one = true
+            }
+            if self.is_read_closed() {
+                if one {
+                    write!(fmt, " | ")?
+                }
+                write!(fmt, "READ_CLOSED")?;
+                one = true
+            }
+        }
         debug_assert!(one, "printing empty interests");
         Ok(())
     }
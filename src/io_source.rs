@@ -142,7 +142,13 @@ where
     ) -> io::Result<()> {
         #[cfg(debug_assertions)]
         self.selector_id.associate(registry)?;
-        poll::selector(registry).register(self.inner.as_raw_fd(), token, interests)
+        let res = poll::selector(registry).register(self.inner.as_raw_fd(), token, interests);
+        #[cfg(debug_assertions)]
+        if res.is_err() {
+            // Not registered, so the source can be registered again.
+            let _ = self.selector_id.remove_association(registry);
+        }
+        res
     }
 
     fn reregister(
@@ -176,8 +182,15 @@ where
     ) -> io::Result<()> {
         #[cfg(debug_assertions)]
         self.selector_id.associate(registry)?;
-        self.state
-            .register(registry, token, interests, self.inner.as_raw_socket())
+        let res = self
+            .state
+            .register(registry, token, interests, self.inner.as_raw_socket());
+        #[cfg(debug_assertions)]
+        if res.is_err() {
+            // Not registered, so the source can be registered again.
+            let _ = self.selector_id.remove_association(registry);
+        }
+        res
     }
 
     fn reregister(
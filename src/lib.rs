@@ -45,6 +45,7 @@
 #[macro_use]
 mod macros;
 
+mod capabilities;
 mod interest;
 mod poll;
 mod sys;
@@ -52,6 +53,7 @@ mod token;
 mod waker;
 
 pub mod event;
+pub mod timers;
 
 cfg_io_source! {
     mod io_source;
@@ -61,9 +63,19 @@ cfg_net! {
     pub mod net;
 }
 
+#[cfg(feature = "reactor")]
+#[cfg_attr(docsrs, doc(cfg(feature = "reactor")))]
+pub mod reactor;
+
+#[cfg(all(unix, feature = "capi"))]
+#[cfg_attr(docsrs, doc(cfg(all(unix, feature = "capi"))))]
+pub mod capi;
+
 #[doc(no_inline)]
 pub use event::Events;
 pub use interest::Interest;
+#[cfg(all(unix, feature = "os-poll"))]
+pub use poll::RegistrationGuard;
 pub use poll::{Poll, Registry};
 pub use token::Token;
 pub use waker::Waker;
@@ -78,7 +90,47 @@ pub mod unix {
         //!
         //! See the [`new`] function for documentation.
 
-        pub use crate::sys::pipe::{new, Receiver, Sender};
+        pub use crate::sys::pipe::{
+            new, open_fifo, stdio_receiver, stdio_sender, Receiver, Sender,
+        };
+    }
+
+    pub mod multi_poll {
+        //! Polling multiple `Poll` instances at once.
+        //!
+        //! See the [`MultiPoll`] type for documentation.
+
+        pub use crate::sys::multi_poll::{Iter, MultiPoll};
+    }
+
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub mod inotify {
+        //! Linux inotify file descriptors.
+        //!
+        //! See the [`Inotify`] type for documentation.
+
+        pub use crate::sys::inotify::{Inotify, InotifyEvent, InotifyEvents, WatchDescriptor};
+    }
+
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub mod pidfd {
+        //! Linux process file descriptors.
+        //!
+        //! See the [`PidFd`] type for documentation.
+
+        pub use crate::sys::pidfd::PidFd;
+    }
+
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub mod signalfd {
+        //! Linux signal file descriptors.
+        //!
+        //! See the [`SignalFd`] type for documentation.
+
+        pub use crate::sys::signalfd::{SignalFd, SignalInfo};
     }
 
     pub use crate::sys::SourceFd;
@@ -95,7 +147,11 @@ pub mod windows {
 pub mod features {
     //! # Mio's optional features.
     //!
-    //! This document describes the available features in Mio.
+    //! This document describes the available features in Mio. The features
+    //! provided by the system selector can be queried at runtime using
+    //! [`Poll::capabilities`].
+    //!
+    //! [`Poll::capabilities`]: ../struct.Poll.html#method.capabilities
     //!
     #![cfg_attr(feature = "os-poll", doc = "## `os-poll` (enabled)")]
     #![cfg_attr(not(feature = "os-poll"), doc = "## `os-poll` (disabled)")]
@@ -116,6 +172,27 @@ pub mod features {
     #![cfg_attr(not(feature = "net"), doc = "## Network types (disabled)")]
     //!
     //! The `net` feature enables networking primitives in the `net` module.
+    //!
+    #![cfg_attr(feature = "reactor", doc = "## `reactor` (enabled)")]
+    #![cfg_attr(not(feature = "reactor"), doc = "## `reactor` (disabled)")]
+    //!
+    //! The `reactor` feature enables the `reactor` module, a small event loop
+    //! that routes events to handlers. It implies `os-poll`.
+    //!
+    #![cfg_attr(feature = "capi", doc = "## `capi` (enabled)")]
+    #![cfg_attr(not(feature = "capi"), doc = "## `capi` (disabled)")]
+    //!
+    //! The `capi` feature enables the `capi` module, a C API to use `Poll`
+    //! from C programs on Unix platforms. It implies `os-poll`.
+    //!
+    #![cfg_attr(feature = "packet", doc = "## `packet` (enabled)")]
+    #![cfg_attr(not(feature = "packet"), doc = "## `packet` (disabled)")]
+    //!
+    //! The `packet` feature enables `PacketSocket` in the `net` module, Linux
+    //! `AF_PACKET` sockets to send and receive packets at the link layer. It
+    //! implies `net` and `os-poll`.
+
+    pub use crate::capabilities::{Capabilities, Triggering};
 }
 
 pub mod guide {
@@ -0,0 +1,210 @@
+use crate::net::{TcpListener, TcpStream, UdpSocket, UnixDatagram, UnixListener, UnixStream};
+use crate::{event, sys, Interest, Registry, Token};
+
+use std::io;
+use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
+
+/// Address family of a socket, see for example [`TcpStream::socket_family`].
+///
+/// [`TcpStream::socket_family`]: struct.TcpStream.html#method.socket_family
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Family {
+    /// IPv4, `AF_INET`.
+    Ipv4,
+    /// IPv6, `AF_INET6`.
+    Ipv6,
+    /// Unix domain socket, `AF_UNIX`.
+    Unix,
+    /// Virtual machine socket, `AF_VSOCK`. Only returned on Android and Linux.
+    Vsock,
+    /// Netlink socket, `AF_NETLINK`. Only returned on Android and Linux.
+    Netlink,
+    /// Any other family, containing the raw `AF_*` value.
+    Other(i32),
+}
+
+/// Type of a socket, see for example [`TcpStream::socket_type`].
+///
+/// [`TcpStream::socket_type`]: struct.TcpStream.html#method.socket_type
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Kind {
+    /// Stream socket, `SOCK_STREAM`.
+    Stream,
+    /// Datagram socket, `SOCK_DGRAM`.
+    Datagram,
+    /// Sequenced packet socket, `SOCK_SEQPACKET`.
+    Seqpacket,
+    /// Raw socket, `SOCK_RAW`.
+    Raw,
+    /// Any other type, containing the raw `SOCK_*` value.
+    Other(i32),
+}
+
+/// Any of the socket types in this module.
+///
+/// This is useful when receiving a socket of which the type isn't known up
+/// front, for example a file descriptor passed by a parent process. Use
+/// [`AnySocket::from_raw_fd`] to determine the type.
+///
+/// # Examples
+///
+#[cfg_attr(feature = "os-poll", doc = "```")]
+#[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use std::os::unix::io::IntoRawFd;
+///
+/// use mio::net::{AnySocket, Family};
+///
+/// let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+/// listener.set_nonblocking(true)?;
+///
+/// let socket = unsafe { AnySocket::from_raw_fd(listener.into_raw_fd())? };
+/// match socket {
+///     AnySocket::TcpListener(listener) => {
+///         assert_eq!(listener.socket_family()?, Family::Ipv4);
+///     }
+///     _ => unreachable!(),
+/// }
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub enum AnySocket {
+    /// A listening TCP socket.
+    TcpListener(TcpListener),
+    /// A TCP stream.
+    TcpStream(TcpStream),
+    /// A UDP socket.
+    UdpSocket(UdpSocket),
+    /// A listening Unix stream socket.
+    UnixListener(UnixListener),
+    /// A Unix stream.
+    UnixStream(UnixStream),
+    /// A Unix datagram socket.
+    UnixDatagram(UnixDatagram),
+}
+
+impl AnySocket {
+    /// Creates a socket from a raw file descriptor, determining the type based
+    /// on the [family], [type] and whether or not the socket [is listening].
+    ///
+    /// Returns an `InvalidInput` error if the socket isn't one of the types
+    /// supported by Mio, e.g. a raw or netlink socket, in which case `fd` is
+    /// **not** closed.
+    ///
+    /// [family]: #method.socket_family
+    /// [type]: #method.socket_type
+    /// [is listening]: #method.is_listening
+    ///
+    /// # Safety
+    ///
+    /// `fd` must be an open socket and the caller must pass ownership of it,
+    /// just like for the [`FromRawFd`] implementations of the socket types.
+    /// Like those implementations this doesn't put the socket in non-blocking
+    /// mode, this is up to the caller.
+    pub unsafe fn from_raw_fd(fd: RawFd) -> io::Result<AnySocket> {
+        let family = sys::net::socket_family(fd)?;
+        let kind = sys::net::socket_type(fd)?;
+        let socket = match (family, kind) {
+            (Family::Ipv4, Kind::Stream) | (Family::Ipv6, Kind::Stream) => {
+                if sys::net::is_listening(fd)? {
+                    AnySocket::TcpListener(TcpListener::from_raw_fd(fd))
+                } else {
+                    AnySocket::TcpStream(TcpStream::from_raw_fd(fd))
+                }
+            }
+            (Family::Ipv4, Kind::Datagram) | (Family::Ipv6, Kind::Datagram) => {
+                AnySocket::UdpSocket(UdpSocket::from_raw_fd(fd))
+            }
+            (Family::Unix, Kind::Stream) => {
+                if sys::net::is_listening(fd)? {
+                    AnySocket::UnixListener(UnixListener::from_raw_fd(fd))
+                } else {
+                    AnySocket::UnixStream(UnixStream::from_raw_fd(fd))
+                }
+            }
+            (Family::Unix, Kind::Datagram) => AnySocket::UnixDatagram(UnixDatagram::from_raw_fd(fd)),
+            _ => return Err(io::ErrorKind::InvalidInput.into()),
+        };
+        Ok(socket)
+    }
+
+    /// Returns the address family of the socket.
+    pub fn socket_family(&self) -> io::Result<Family> {
+        sys::net::socket_family(self.as_raw_fd())
+    }
+
+    /// Returns the type of the socket.
+    pub fn socket_type(&self) -> io::Result<Kind> {
+        sys::net::socket_type(self.as_raw_fd())
+    }
+
+    /// Returns `true` if the socket is listening for connections.
+    pub fn is_listening(&self) -> io::Result<bool> {
+        sys::net::is_listening(self.as_raw_fd())
+    }
+
+    fn as_source(&mut self) -> &mut dyn event::Source {
+        match self {
+            AnySocket::TcpListener(socket) => socket,
+            AnySocket::TcpStream(socket) => socket,
+            AnySocket::UdpSocket(socket) => socket,
+            AnySocket::UnixListener(socket) => socket,
+            AnySocket::UnixStream(socket) => socket,
+            AnySocket::UnixDatagram(socket) => socket,
+        }
+    }
+}
+
+impl event::Source for AnySocket {
+    fn register(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        self.as_source().register(registry, token, interests)
+    }
+
+    fn reregister(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        self.as_source().reregister(registry, token, interests)
+    }
+
+    fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
+        self.as_source().deregister(registry)
+    }
+}
+
+impl AsRawFd for AnySocket {
+    fn as_raw_fd(&self) -> RawFd {
+        match self {
+            AnySocket::TcpListener(socket) => socket.as_raw_fd(),
+            AnySocket::TcpStream(socket) => socket.as_raw_fd(),
+            AnySocket::UdpSocket(socket) => socket.as_raw_fd(),
+            AnySocket::UnixListener(socket) => socket.as_raw_fd(),
+            AnySocket::UnixStream(socket) => socket.as_raw_fd(),
+            AnySocket::UnixDatagram(socket) => socket.as_raw_fd(),
+        }
+    }
+}
+
+impl IntoRawFd for AnySocket {
+    fn into_raw_fd(self) -> RawFd {
+        match self {
+            AnySocket::TcpListener(socket) => socket.into_raw_fd(),
+            AnySocket::TcpStream(socket) => socket.into_raw_fd(),
+            AnySocket::UdpSocket(socket) => socket.into_raw_fd(),
+            AnySocket::UnixListener(socket) => socket.into_raw_fd(),
+            AnySocket::UnixStream(socket) => socket.into_raw_fd(),
+            AnySocket::UnixDatagram(socket) => socket.into_raw_fd(),
+        }
+    }
+}
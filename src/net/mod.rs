@@ -8,7 +8,9 @@
 //! [portability guidelines]: ../struct.Poll.html#portability
 
 mod tcp;
-pub use self::tcp::{TcpListener, TcpSocket, TcpStream, TcpKeepalive};
+pub use self::tcp::{
+    AcceptedOptions, ConnectRace, TcpKeepalive, TcpListener, TcpSocket, TcpStream,
+};
 
 mod udp;
 pub use self::udp::UdpSocket;
@@ -16,4 +18,13 @@ pub use self::udp::UdpSocket;
 #[cfg(unix)]
 mod uds;
 #[cfg(unix)]
-pub use self::uds::{SocketAddr, UnixDatagram, UnixListener, UnixStream};
+pub use self::uds::{BindOptions, SocketAddr, UnixDatagram, UnixListener, UnixStream};
+
+#[cfg(unix)]
+mod any;
+#[cfg(unix)]
+pub use self::any::{AnySocket, Family, Kind};
+
+#[cfg(all(target_os = "linux", feature = "packet"))]
+#[cfg_attr(docsrs, doc(cfg(all(target_os = "linux", feature = "packet"))))]
+pub use crate::sys::packet::{LinkAddr, PacketSocket, SockFilter};
@@ -5,8 +5,10 @@ use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
 use std::os::windows::io::{AsRawSocket, FromRawSocket, IntoRawSocket, RawSocket};
 use std::{fmt, io};
 
-use super::{TcpSocket, TcpStream};
+use super::{TcpKeepalive, TcpSocket, TcpStream};
 use crate::io_source::IoSource;
+#[cfg(unix)]
+use crate::net::{Family, Kind};
 use crate::{event, sys, Interest, Registry, Token};
 
 /// A structure representing a socket server
@@ -92,6 +94,47 @@ impl TcpListener {
         })
     }
 
+    /// Accepts a new `TcpStream`, applying `options` to it.
+    ///
+    /// Works the same as [`accept`], but sets the options in `options` on the
+    /// accepted stream before returning it. If setting any of the options
+    /// fails the accepted stream is closed and the error is returned.
+    ///
+    /// [`accept`]: TcpListener::accept
+    ///
+    /// # Notes
+    ///
+    /// Which options an accepted stream inherits from the listening socket
+    /// differs per platform, so all options set in `options` are set on every
+    /// accepted stream, each with its own system call.
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(feature = "os-poll", doc = "```")]
+    #[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::net::{AcceptedOptions, TcpListener};
+    ///
+    /// let listener = TcpListener::bind("127.0.0.1:0".parse()?)?;
+    ///
+    /// let mut options = AcceptedOptions::default();
+    /// options.nodelay = Some(true);
+    ///
+    /// match listener.accept_with(&options) {
+    ///     Ok((stream, _)) => assert!(stream.nodelay()?),
+    ///     Err(ref err) if err.kind() == std::io::ErrorKind::WouldBlock => {}
+    ///     Err(err) => return Err(err.into()),
+    /// }
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn accept_with(&self, options: &AcceptedOptions) -> io::Result<(TcpStream, SocketAddr)> {
+        let (stream, addr) = self.inner.do_io(sys::tcp::accept)?;
+        options.apply(&stream)?;
+        Ok((TcpStream::from_std(stream), addr))
+    }
+
     /// Returns the local socket address of this listener.
     pub fn local_addr(&self) -> io::Result<SocketAddr> {
         self.inner.local_addr()
@@ -119,9 +162,91 @@ impl TcpListener {
     /// This will retrieve the stored error in the underlying socket, clearing
     /// the field in the process. This can be useful for checking errors between
     /// calls.
+    ///
+    /// # Error events
+    ///
+    /// A listener can receive an event with [error] readiness, e.g. when the
+    /// system is under memory pressure. Some selectors report such an event as
+    /// [write closed] as well, as they can't differentiate a listener from
+    /// other kinds of file descriptors. Neither means the listener is closed,
+    /// the error should be cleared using this method after which the listener
+    /// can continue to [accept] connections. Errors returned by `accept` for a
+    /// single connection, e.g. `ConnectionAborted`, don't close the listener
+    /// either.
+    ///
+    /// [error]: crate::event::Event::is_error
+    /// [write closed]: crate::event::Event::is_write_closed
+    /// [accept]: TcpListener::accept
     pub fn take_error(&self) -> io::Result<Option<io::Error>> {
         self.inner.take_error()
     }
+
+    /// Returns the address family of the socket, see [`Family`].
+    ///
+    /// [`Family`]: enum.Family.html
+    #[cfg(unix)]
+    #[cfg_attr(docsrs, doc(cfg(unix)))]
+    pub fn socket_family(&self) -> io::Result<Family> {
+        sys::net::socket_family(self.as_raw_fd())
+    }
+
+    /// Returns the type of the socket, see [`Kind`].
+    ///
+    /// [`Kind`]: enum.Kind.html
+    #[cfg(unix)]
+    #[cfg_attr(docsrs, doc(cfg(unix)))]
+    pub fn socket_type(&self) -> io::Result<Kind> {
+        sys::net::socket_type(self.as_raw_fd())
+    }
+
+    /// Returns `true` if the socket is listening for connections, i.e. the
+    /// value of the `SO_ACCEPTCONN` option.
+    #[cfg(unix)]
+    #[cfg_attr(docsrs, doc(cfg(unix)))]
+    pub fn is_listening(&self) -> io::Result<bool> {
+        sys::net::is_listening(self.as_raw_fd())
+    }
+}
+
+/// Options set on streams accepted by [`TcpListener::accept_with`].
+///
+/// Options set to `None`, the default, are not changed.
+#[derive(Clone, Debug, Default)]
+#[non_exhaustive]
+pub struct AcceptedOptions {
+    /// Sets the value of `TCP_NODELAY`, see [`TcpStream::set_nodelay`].
+    pub nodelay: Option<bool>,
+    /// Enables keepalive with the parameters, see
+    /// [`TcpSocket::set_keepalive_params`].
+    pub keepalive: Option<TcpKeepalive>,
+    /// Sets the value of `SO_RCVBUF`, see [`TcpSocket::set_recv_buffer_size`].
+    pub recv_buf: Option<u32>,
+    /// Sets the value of `SO_SNDBUF`, see [`TcpSocket::set_send_buffer_size`].
+    pub send_buf: Option<u32>,
+}
+
+impl AcceptedOptions {
+    fn apply(&self, stream: &net::TcpStream) -> io::Result<()> {
+        #[cfg(unix)]
+        let socket = stream.as_raw_fd();
+        #[cfg(windows)]
+        let socket = stream.as_raw_socket() as sys::tcp::TcpSocket;
+
+        if let Some(nodelay) = self.nodelay {
+            stream.set_nodelay(nodelay)?;
+        }
+        if let Some(keepalive) = &self.keepalive {
+            sys::tcp::set_keepalive(socket, true)?;
+            sys::tcp::set_keepalive_params(socket, keepalive.clone())?;
+        }
+        if let Some(size) = self.recv_buf {
+            sys::tcp::set_recv_buffer_size(socket, size)?;
+        }
+        if let Some(size) = self.send_buf {
+            sys::tcp::set_send_buffer_size(socket, size)?;
+        }
+        Ok(())
+    }
 }
 
 impl event::Source for TcpListener {
@@ -1,5 +1,8 @@
 mod listener;
-pub use self::listener::TcpListener;
+pub use self::listener::{AcceptedOptions, TcpListener};
+
+mod race;
+pub use self::race::ConnectRace;
 
 mod socket;
 pub use self::socket::{TcpSocket, TcpKeepalive};
@@ -0,0 +1,265 @@
+use std::io;
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+
+use crate::event::Event;
+use crate::net::TcpStream;
+use crate::{Interest, Registry, Token};
+
+/// A race between connects to multiple addresses, created by
+/// [`TcpStream::connect_first`].
+///
+/// This implements the connection attempt part of Happy Eyeballs ([RFC
+/// 8305]): the addresses are tried in order with the address families
+/// interleaved, starting a new attempt every `stagger` duration (or as soon as
+/// the previous attempt fails) while the previous attempts continue. The first
+/// connect to complete wins, the remaining attempts are deregistered and
+/// closed.
+///
+/// Attempt `n` (counting from zero) is registered using `Token(base_token.0 +
+/// n)`, all events for those tokens must be passed to [`on_event`]. To start
+/// the staggered attempts on time the timeout passed to [`Poll::poll`] must be
+/// at most [`timeout`], after which [`on_timeout`] must be called.
+///
+/// [RFC 8305]: https://tools.ietf.org/html/rfc8305
+/// [`on_event`]: ConnectRace::on_event
+/// [`Poll::poll`]: crate::Poll::poll
+/// [`timeout`]: ConnectRace::timeout
+/// [`on_timeout`]: ConnectRace::on_timeout
+///
+/// # Examples
+///
+#[cfg_attr(feature = "os-poll", doc = "```")]
+#[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// # let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+/// # let addr = listener.local_addr()?;
+/// use std::time::Duration;
+///
+/// use mio::net::TcpStream;
+/// use mio::{Events, Poll, Token};
+///
+/// let mut poll = Poll::new()?;
+/// let mut events = Events::with_capacity(8);
+///
+/// let addrs = [addr];
+/// let stagger = Duration::from_millis(250);
+/// let mut race = TcpStream::connect_first(&addrs, poll.registry(), Token(0), stagger)?;
+///
+/// let stream = 'race: loop {
+///     poll.poll(&mut events, race.timeout())?;
+///     for event in events.iter() {
+///         if let Some(result) = race.on_event(poll.registry(), event) {
+///             break 'race result?;
+///         }
+///     }
+///     if let Some(result) = race.on_timeout(poll.registry()) {
+///         break 'race result?;
+///     }
+/// };
+/// println!("connected to {}", stream.peer_addr()?);
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ConnectRace {
+    /// Addresses in the order they are tried.
+    addrs: Vec<SocketAddr>,
+    base_token: Token,
+    stagger: Duration,
+    /// In progress connects, indexed by `token - base_token`.
+    attempts: Vec<Option<TcpStream>>,
+    /// Index of the next address to connect to.
+    next: usize,
+    /// When the next connect should be started, `None` if all addresses are
+    /// tried or the race is finished.
+    next_attempt: Option<Instant>,
+    /// Error of the last failed attempt, returned if all attempts fail.
+    last_error: Option<io::Error>,
+}
+
+impl TcpStream {
+    /// Connects to the first of `addrs` that accepts the connection, see
+    /// [`ConnectRace`].
+    ///
+    /// The first connect is started immediately, if it (and all following
+    /// attempts) fail to start the error is returned.
+    ///
+    /// Returns an `InvalidInput` error if `addrs` is empty or if the tokens
+    /// used for the attempts would overflow.
+    pub fn connect_first(
+        addrs: &[SocketAddr],
+        registry: &Registry,
+        base_token: Token,
+        stagger: Duration,
+    ) -> io::Result<ConnectRace> {
+        if addrs.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "no addresses to connect to",
+            ));
+        }
+        if base_token.0.checked_add(addrs.len() - 1).is_none() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "too many addresses for base token",
+            ));
+        }
+
+        let mut race = ConnectRace {
+            addrs: interleave(addrs),
+            base_token,
+            stagger,
+            attempts: (0..addrs.len()).map(|_| None).collect(),
+            next: 0,
+            next_attempt: None,
+            last_error: None,
+        };
+        if race.start_next(registry) {
+            Ok(race)
+        } else {
+            Err(race.take_error())
+        }
+    }
+}
+
+impl ConnectRace {
+    /// Returns the maximum timeout to use in [`Poll::poll`] to start the next
+    /// attempt on time, or `None` if there are no more attempts to start.
+    ///
+    /// [`Poll::poll`]: crate::Poll::poll
+    pub fn timeout(&self) -> Option<Duration> {
+        crate::timers::poll_timeout(self.next_attempt.into_iter(), Instant::now())
+    }
+
+    /// Returns the number of connects in progress.
+    pub fn in_progress(&self) -> usize {
+        self.attempts.iter().filter(|a| a.is_some()).count()
+    }
+
+    /// Processes `event`, returns the result of the race once it's finished.
+    ///
+    /// Events for other tokens are ignored. The returned stream is
+    /// deregistered and can be registered again using any token. An error is
+    /// only returned once all attempts failed, it's the error of the last
+    /// attempt.
+    pub fn on_event(
+        &mut self,
+        registry: &Registry,
+        event: &Event,
+    ) -> Option<io::Result<TcpStream>> {
+        let index = event.token().0.wrapping_sub(self.base_token.0);
+        let stream = self.attempts.get(index)?.as_ref()?;
+
+        let result = match stream.take_error() {
+            Ok(Some(err)) | Err(err) => Err(err),
+            Ok(None) => match stream.peer_addr() {
+                Ok(_) => Ok(()),
+                // Spurious event, the connect is still in progress.
+                Err(ref err) if err.kind() == io::ErrorKind::NotConnected => return None,
+                Err(err) => Err(err),
+            },
+        };
+
+        let mut stream = self.attempts[index].take().unwrap();
+        let _ = registry.deregister(&mut stream);
+        match result {
+            Ok(()) => {
+                self.finish(registry);
+                Some(Ok(stream))
+            }
+            Err(err) => {
+                drop(stream);
+                self.last_error = Some(err);
+                // Don't wait for the stagger delay, start the next attempt
+                // right away.
+                if !self.start_next(registry) && self.in_progress() == 0 {
+                    Some(Err(self.take_error()))
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    /// Starts the next attempt if its stagger delay has passed.
+    ///
+    /// Returns an error if all attempts failed, which is only possible if no
+    /// connect was in progress.
+    pub fn on_timeout(&mut self, registry: &Registry) -> Option<io::Result<TcpStream>> {
+        match self.next_attempt {
+            Some(deadline) if deadline <= Instant::now() => {}
+            _ => return None,
+        }
+        if !self.start_next(registry) && self.in_progress() == 0 {
+            Some(Err(self.take_error()))
+        } else {
+            None
+        }
+    }
+
+    /// Starts connecting to the next address, skipping addresses for which
+    /// the connect fails immediately. Returns `false` if no connect could be
+    /// started.
+    fn start_next(&mut self, registry: &Registry) -> bool {
+        while self.next < self.addrs.len() {
+            let index = self.next;
+            self.next += 1;
+            let token = Token(self.base_token.0 + index);
+            let result = TcpStream::connect(self.addrs[index]).and_then(|mut stream| {
+                registry
+                    .register(&mut stream, token, Interest::WRITABLE)
+                    .map(|()| stream)
+            });
+            match result {
+                Ok(stream) => {
+                    self.attempts[index] = Some(stream);
+                    self.next_attempt = if self.next < self.addrs.len() {
+                        Some(Instant::now() + self.stagger)
+                    } else {
+                        None
+                    };
+                    return true;
+                }
+                Err(err) => self.last_error = Some(err),
+            }
+        }
+        self.next_attempt = None;
+        false
+    }
+
+    /// Deregisters and closes all attempts in progress.
+    fn finish(&mut self, registry: &Registry) {
+        for mut stream in self.attempts.iter_mut().filter_map(Option::take) {
+            let _ = registry.deregister(&mut stream);
+        }
+        self.next = self.addrs.len();
+        self.next_attempt = None;
+    }
+
+    fn take_error(&mut self) -> io::Error {
+        self.last_error
+            .take()
+            .unwrap_or_else(|| io::Error::new(io::ErrorKind::NotConnected, "no connect attempts"))
+    }
+}
+
+/// Orders `addrs` so that the address families alternate, starting with the
+/// family of the first address, keeping the order within each family.
+fn interleave(addrs: &[SocketAddr]) -> Vec<SocketAddr> {
+    let first_v6 = addrs[0].is_ipv6();
+    let (first, second): (Vec<_>, Vec<_>) = addrs
+        .iter()
+        .copied()
+        .partition(|addr| addr.is_ipv6() == first_v6);
+    let mut first = first.into_iter();
+    let mut second = second.into_iter();
+    let mut ordered = Vec::with_capacity(addrs.len());
+    loop {
+        match (first.next(), second.next()) {
+            (None, None) => return ordered,
+            (a, b) => ordered.extend(a.into_iter().chain(b)),
+        }
+    }
+}
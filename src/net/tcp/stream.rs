@@ -8,7 +8,9 @@ use std::os::windows::io::{AsRawSocket, FromRawSocket, IntoRawSocket, RawSocket}
 
 use crate::io_source::IoSource;
 use crate::net::TcpSocket;
-use crate::{event, Interest, Registry, Token};
+#[cfg(unix)]
+use crate::net::{Family, Kind};
+use crate::{event, sys, Interest, Registry, Token};
 
 /// A non-blocking TCP stream between a local socket and a remote socket.
 ///
@@ -49,6 +51,18 @@ pub struct TcpStream {
 impl TcpStream {
     /// Create a new TCP stream and issue a non-blocking connect to the
     /// specified address.
+    ///
+    /// The connect is complete once the stream is reported as writable, which
+    /// is also the case if the connect failed, so registering with only
+    /// [`WRITABLE`] interest is sufficient. After the event [`take_error`]
+    /// returns the error of a failed connect, e.g. `ConnectionRefused`. If it
+    /// returns `None` and [`peer_addr`] returns `Ok` the stream is connected,
+    /// if `peer_addr` returns a `NotConnected` error the connect is still in
+    /// progress.
+    ///
+    /// [`WRITABLE`]: Interest::WRITABLE
+    /// [`take_error`]: TcpStream::take_error
+    /// [`peer_addr`]: TcpStream::peer_addr
     pub fn connect(addr: SocketAddr) -> io::Result<TcpStream> {
         let socket = TcpSocket::new_for_addr(addr)?;
         socket.connect(addr)
@@ -152,6 +166,50 @@ impl TcpStream {
         self.inner.ttl()
     }
 
+    /// Sets the value of `SO_RCVBUF` on this socket.
+    ///
+    /// The size of the receive buffer limits how much data is buffered by the
+    /// OS before it's read.
+    pub fn set_recv_buffer_size(&self, size: u32) -> io::Result<()> {
+        sys::tcp::set_recv_buffer_size(self.sys_socket(), size)
+    }
+
+    /// Gets the value of `SO_RCVBUF` on this socket.
+    ///
+    /// The returned value may differ from the one passed to
+    /// [`set_recv_buffer_size`]: the OS clamps the value to its minimum and
+    /// maximum sizes, and Linux doubles the value to account for bookkeeping
+    /// overhead (returning the doubled value), see
+    /// [`TcpSocket::get_recv_buffer_size`].
+    ///
+    /// [`set_recv_buffer_size`]: TcpStream::set_recv_buffer_size
+    /// [`TcpSocket::get_recv_buffer_size`]: crate::net::TcpSocket::get_recv_buffer_size
+    pub fn recv_buffer_size(&self) -> io::Result<u32> {
+        sys::tcp::get_recv_buffer_size(self.sys_socket())
+    }
+
+    /// Sets the value of `SO_SNDBUF` on this socket.
+    ///
+    /// The size of the send buffer limits how much data can be written before
+    /// writing returns a [`WouldBlock`] error, after which a writable event is
+    /// only returned once enough of the buffer is available again.
+    ///
+    /// [`WouldBlock`]: io::ErrorKind::WouldBlock
+    pub fn set_send_buffer_size(&self, size: u32) -> io::Result<()> {
+        sys::tcp::set_send_buffer_size(self.sys_socket(), size)
+    }
+
+    /// Gets the value of `SO_SNDBUF` on this socket.
+    ///
+    /// Like [`recv_buffer_size`] the returned value may differ from the one
+    /// passed to [`set_send_buffer_size`], on Linux it's doubled.
+    ///
+    /// [`recv_buffer_size`]: TcpStream::recv_buffer_size
+    /// [`set_send_buffer_size`]: TcpStream::set_send_buffer_size
+    pub fn send_buffer_size(&self) -> io::Result<u32> {
+        sys::tcp::get_send_buffer_size(self.sys_socket())
+    }
+
     /// Get the value of the `SO_ERROR` option on this socket.
     ///
     /// This will retrieve the stored error in the underlying socket, clearing
@@ -161,6 +219,86 @@ impl TcpStream {
         self.inner.take_error()
     }
 
+    /// Returns the address family of the socket, see [`Family`].
+    ///
+    /// [`Family`]: enum.Family.html
+    #[cfg(unix)]
+    #[cfg_attr(docsrs, doc(cfg(unix)))]
+    pub fn socket_family(&self) -> io::Result<Family> {
+        sys::net::socket_family(self.as_raw_fd())
+    }
+
+    /// Returns the type of the socket, see [`Kind`].
+    ///
+    /// [`Kind`]: enum.Kind.html
+    #[cfg(unix)]
+    #[cfg_attr(docsrs, doc(cfg(unix)))]
+    pub fn socket_type(&self) -> io::Result<Kind> {
+        sys::net::socket_type(self.as_raw_fd())
+    }
+
+    /// Returns `true` if the socket is listening for connections, i.e. the
+    /// value of the `SO_ACCEPTCONN` option.
+    #[cfg(unix)]
+    #[cfg_attr(docsrs, doc(cfg(unix)))]
+    pub fn is_listening(&self) -> io::Result<bool> {
+        sys::net::is_listening(self.as_raw_fd())
+    }
+
+    /// Sets the value of the `SO_RCVLOWAT` option on this socket.
+    ///
+    /// This is the minimum number of bytes that must be received before the
+    /// socket is reported as readable (and before a blocking read returns).
+    /// This can be used to avoid being woken up for every few bytes of a large
+    /// message. Note that the socket is still reported as readable if the peer
+    /// closed the connection or an error occurred, with less data available.
+    #[cfg(unix)]
+    #[cfg_attr(docsrs, doc(cfg(unix)))]
+    pub fn set_recv_low_water_mark(&self, bytes: usize) -> io::Result<()> {
+        sys::net::set_recv_low_water_mark(self.as_raw_fd(), bytes)
+    }
+
+    /// Gets the value of the `SO_RCVLOWAT` option on this socket.
+    ///
+    /// For more information about this option, see
+    /// [`set_recv_low_water_mark`].
+    ///
+    /// [`set_recv_low_water_mark`]: TcpStream::set_recv_low_water_mark
+    #[cfg(unix)]
+    #[cfg_attr(docsrs, doc(cfg(unix)))]
+    pub fn recv_low_water_mark(&self) -> io::Result<usize> {
+        sys::net::recv_low_water_mark(self.as_raw_fd())
+    }
+
+    /// Sets the value of the `SO_SNDLOWAT` option on this socket.
+    ///
+    /// This is the minimum amount of free space in the send buffer before the
+    /// socket is reported as writable, which avoids being woken up to write
+    /// only a few bytes of a large message at a time.
+    ///
+    /// # Notes
+    ///
+    /// Linux doesn't support changing this option, there this returns an
+    /// error (`ENOPROTOOPT`) and the socket is reported as writable once the
+    /// send buffer is at least half empty.
+    #[cfg(unix)]
+    #[cfg_attr(docsrs, doc(cfg(unix)))]
+    pub fn set_send_low_water_mark(&self, bytes: usize) -> io::Result<()> {
+        sys::net::set_send_low_water_mark(self.as_raw_fd(), bytes)
+    }
+
+    /// Gets the value of the `SO_SNDLOWAT` option on this socket.
+    ///
+    /// For more information about this option, see
+    /// [`set_send_low_water_mark`].
+    ///
+    /// [`set_send_low_water_mark`]: TcpStream::set_send_low_water_mark
+    #[cfg(unix)]
+    #[cfg_attr(docsrs, doc(cfg(unix)))]
+    pub fn send_low_water_mark(&self) -> io::Result<usize> {
+        sys::net::send_low_water_mark(self.as_raw_fd())
+    }
+
     /// Receives data on the socket from the remote address to which it is
     /// connected, without removing that data from the queue. On success,
     /// returns the number of bytes peeked.
@@ -170,6 +308,31 @@ impl TcpStream {
     pub fn peek(&self, buf: &mut [u8]) -> io::Result<usize> {
         self.inner.peek(buf)
     }
+
+    /// Returns the number of bytes that can be read without blocking, using
+    /// `ioctl(FIONREAD)` (`ioctlsocket` on Windows).
+    ///
+    /// This can be used as a hint to size the read buffer after receiving a
+    /// readable event. If no data is available this returns `Ok(0)`, not a
+    /// [`WouldBlock`] error, note that this is also returned once the peer
+    /// has shutdown the connection.
+    ///
+    /// [`WouldBlock`]: std::io::ErrorKind::WouldBlock
+    pub fn bytes_available(&self) -> io::Result<usize> {
+        sys::tcp::bytes_available(&self.inner)
+    }
+
+    /// Returns the socket for the buffer size functions in `sys::tcp`.
+    #[cfg(unix)]
+    fn sys_socket(&self) -> sys::tcp::TcpSocket {
+        self.as_raw_fd()
+    }
+
+    /// Returns the socket for the buffer size functions in `sys::tcp`.
+    #[cfg(windows)]
+    fn sys_socket(&self) -> sys::tcp::TcpSocket {
+        self.as_raw_socket() as sys::tcp::TcpSocket
+    }
 }
 
 impl Read for TcpStream {
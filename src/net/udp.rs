@@ -8,10 +8,14 @@
 //! [portability guidelines]: ../struct.Poll.html#portability
 
 use crate::io_source::IoSource;
+#[cfg(unix)]
+use crate::net::{Family, Kind};
 use crate::{event, sys, Interest, Registry, Token};
 
 use std::fmt;
 use std::io;
+#[cfg(unix)]
+use std::io::{IoSlice, IoSliceMut};
 use std::net;
 use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr};
 #[cfg(unix)]
@@ -317,6 +321,140 @@ impl UdpSocket {
         self.inner.do_io(|inner| inner.peek(buf))
     }
 
+    /// Sends the data in `bufs` as a single datagram to the given address,
+    /// using `sendmsg(2)`. On success, returns the number of bytes written.
+    ///
+    /// Returns an `InvalidInput` error if `bufs` contains more than `IOV_MAX`
+    /// (1024) buffers, rather than sending a truncated datagram.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use std::io::IoSlice;
+    ///
+    /// use mio::net::UdpSocket;
+    ///
+    /// let socket = UdpSocket::bind("127.0.0.1:0".parse()?)?;
+    ///
+    /// // We must check if the socket is writable before calling
+    /// // send_to_vectored, or we could run into a WouldBlock error.
+    ///
+    /// let header = [1; 4];
+    /// let body = [2; 8];
+    /// let bufs = [IoSlice::new(&header), IoSlice::new(&body)];
+    /// let bytes_sent = socket.send_to_vectored(&bufs, "127.0.0.1:11100".parse()?)?;
+    /// assert_eq!(bytes_sent, 12);
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    #[cfg(unix)]
+    #[cfg_attr(docsrs, doc(cfg(unix)))]
+    pub fn send_to_vectored(&self, bufs: &[IoSlice<'_>], target: SocketAddr) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::udp::send_to_vectored(inner, bufs, target))
+    }
+
+    /// Receives a single datagram into `bufs`, using `recvmsg(2)`. On
+    /// success, returns the number of bytes read and the address from whence
+    /// the data came.
+    ///
+    /// Like [`recv_from`] any part of the datagram that doesn't fit in `bufs`
+    /// is discarded. At most `IOV_MAX` (1024) buffers are used.
+    ///
+    /// [`recv_from`]: UdpSocket::recv_from
+    #[cfg(unix)]
+    #[cfg_attr(docsrs, doc(cfg(unix)))]
+    pub fn recv_from_vectored(
+        &self,
+        bufs: &mut [IoSliceMut<'_>],
+    ) -> io::Result<(usize, SocketAddr)> {
+        self.inner
+            .do_io(|inner| sys::udp::recv_from_vectored(inner, bufs))
+    }
+
+    /// Sends the data in `bufs` as a single datagram to the address
+    /// previously bound via connect(). See [`send_to_vectored`].
+    ///
+    /// [`send_to_vectored`]: UdpSocket::send_to_vectored
+    #[cfg(unix)]
+    #[cfg_attr(docsrs, doc(cfg(unix)))]
+    pub fn send_vectored(&self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::udp::send_vectored(inner, bufs))
+    }
+
+    /// Receives a single datagram into `bufs` from the socket previously
+    /// bound with connect(). See [`recv_from_vectored`].
+    ///
+    /// [`recv_from_vectored`]: UdpSocket::recv_from_vectored
+    #[cfg(unix)]
+    #[cfg_attr(docsrs, doc(cfg(unix)))]
+    pub fn recv_vectored(&self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::udp::recv_vectored(inner, bufs))
+    }
+
+    /// Receives a single datagram into `buf`, reporting whether it was
+    /// truncated. On success, returns the length of the datagram, whether it
+    /// was truncated because it didn't fit in `buf`, and the address from
+    /// whence the data came.
+    ///
+    /// Like [`recv_from`] any part of the datagram that doesn't fit in `buf` is
+    /// discarded, but unlike `recv_from` this can be detected.
+    ///
+    /// # Notes
+    ///
+    /// On Linux and Android the returned length is the real length of the
+    /// datagram, which can be larger than `buf` if it was truncated. On other
+    /// platforms it's the number of bytes written to `buf`, so only the
+    /// returned `bool` indicates the datagram was truncated.
+    ///
+    /// [`recv_from`]: UdpSocket::recv_from
+    #[cfg(unix)]
+    #[cfg_attr(docsrs, doc(cfg(unix)))]
+    pub fn recv_from_full(&self, buf: &mut [u8]) -> io::Result<(usize, bool, SocketAddr)> {
+        self.inner
+            .do_io(|inner| sys::udp::recv_from_full(inner, buf))
+    }
+
+    /// Receives a single datagram into `buf` from the socket's peer,
+    /// reporting whether it was truncated. See [`recv_from_full`].
+    ///
+    /// [`recv_from_full`]: UdpSocket::recv_from_full
+    #[cfg(unix)]
+    #[cfg_attr(docsrs, doc(cfg(unix)))]
+    pub fn recv_full(&self, buf: &mut [u8]) -> io::Result<(usize, bool)> {
+        self.inner.do_io(|inner| sys::udp::recv_full(inner, buf))
+    }
+
+    /// Returns the size of the next datagram that can be received, using
+    /// `ioctl(FIONREAD)` (`ioctlsocket` on Windows).
+    ///
+    /// This can be used as a hint to size the buffer passed to [`recv`] or
+    /// [`recv_from`]. If no datagram is queued this returns `Ok(0)`, not a
+    /// [`WouldBlock`] error.
+    ///
+    /// [`recv`]: UdpSocket::recv
+    /// [`recv_from`]: UdpSocket::recv_from
+    /// [`WouldBlock`]: std::io::ErrorKind::WouldBlock
+    ///
+    /// # Notes
+    ///
+    /// The semantics of `FIONREAD` differ per platform:
+    ///
+    /// * On Linux, Android and Windows this returns the size of the next
+    ///   datagram. As zero sized datagrams are allowed, `Ok(0)` doesn't
+    ///   guarantee that the receive queue is empty.
+    /// * On other platforms, e.g. the BSDs and macOS, this returns the total
+    ///   number of bytes of **all** queued datagrams. This is an upper bound
+    ///   for the size of the next datagram.
+    pub fn next_datagram_size(&self) -> io::Result<usize> {
+        sys::udp::bytes_available(&self.inner)
+    }
+
     /// Connects the UDP socket setting the default destination for `send()`
     /// and limiting packets that are read via `recv` from the address specified
     /// in `addr`.
@@ -490,6 +628,50 @@ impl UdpSocket {
         self.inner.ttl()
     }
 
+    /// Sets the value of `SO_RCVBUF` on this socket.
+    ///
+    /// The size of the receive buffer limits how much data is buffered by the
+    /// OS before it's read, datagrams received while it is full are dropped.
+    pub fn set_recv_buffer_size(&self, size: u32) -> io::Result<()> {
+        sys::tcp::set_recv_buffer_size(self.sys_socket(), size)
+    }
+
+    /// Gets the value of `SO_RCVBUF` on this socket.
+    ///
+    /// The returned value may differ from the one passed to
+    /// [`set_recv_buffer_size`]: the OS clamps the value to its minimum and
+    /// maximum sizes, and Linux doubles the value to account for bookkeeping
+    /// overhead (returning the doubled value), see
+    /// [`TcpSocket::get_recv_buffer_size`].
+    ///
+    /// [`set_recv_buffer_size`]: UdpSocket::set_recv_buffer_size
+    /// [`TcpSocket::get_recv_buffer_size`]: crate::net::TcpSocket::get_recv_buffer_size
+    pub fn recv_buffer_size(&self) -> io::Result<u32> {
+        sys::tcp::get_recv_buffer_size(self.sys_socket())
+    }
+
+    /// Sets the value of `SO_SNDBUF` on this socket.
+    ///
+    /// The size of the send buffer limits how much data can be written before
+    /// writing returns a [`WouldBlock`] error, after which a writable event is
+    /// only returned once enough of the buffer is available again.
+    ///
+    /// [`WouldBlock`]: io::ErrorKind::WouldBlock
+    pub fn set_send_buffer_size(&self, size: u32) -> io::Result<()> {
+        sys::tcp::set_send_buffer_size(self.sys_socket(), size)
+    }
+
+    /// Gets the value of `SO_SNDBUF` on this socket.
+    ///
+    /// Like [`recv_buffer_size`] the returned value may differ from the one
+    /// passed to [`set_send_buffer_size`], on Linux it's doubled.
+    ///
+    /// [`recv_buffer_size`]: UdpSocket::recv_buffer_size
+    /// [`set_send_buffer_size`]: UdpSocket::set_send_buffer_size
+    pub fn send_buffer_size(&self) -> io::Result<u32> {
+        sys::tcp::get_send_buffer_size(self.sys_socket())
+    }
+
     /// Executes an operation of the `IP_ADD_MEMBERSHIP` type.
     ///
     /// This function specifies a new multicast group for this socket to join.
@@ -545,9 +727,57 @@ impl UdpSocket {
     /// This will retrieve the stored error in the underlying socket, clearing
     /// the field in the process. This can be useful for checking errors between
     /// calls.
+    ///
+    /// For connected sockets the OS might store an error received from the
+    /// peer, e.g. `ECONNREFUSED` after an ICMP port unreachable message. This is
+    /// reported as an [error event], after which this function can be used to
+    /// retrieve (and clear) the error without an additional `send` or `recv`.
+    /// Note that on some platforms the event may also be reported as
+    /// [write closed], this doesn't mean the socket can't be used anymore.
+    ///
+    /// [error event]: ../event/struct.Event.html#method.is_error
+    /// [write closed]: ../event/struct.Event.html#method.is_write_closed
     pub fn take_error(&self) -> io::Result<Option<io::Error>> {
         self.inner.take_error()
     }
+
+    /// Returns the address family of the socket, see [`Family`].
+    ///
+    /// [`Family`]: enum.Family.html
+    #[cfg(unix)]
+    #[cfg_attr(docsrs, doc(cfg(unix)))]
+    pub fn socket_family(&self) -> io::Result<Family> {
+        sys::net::socket_family(self.as_raw_fd())
+    }
+
+    /// Returns the type of the socket, see [`Kind`].
+    ///
+    /// [`Kind`]: enum.Kind.html
+    #[cfg(unix)]
+    #[cfg_attr(docsrs, doc(cfg(unix)))]
+    pub fn socket_type(&self) -> io::Result<Kind> {
+        sys::net::socket_type(self.as_raw_fd())
+    }
+
+    /// Returns `true` if the socket is listening for connections, i.e. the
+    /// value of the `SO_ACCEPTCONN` option.
+    #[cfg(unix)]
+    #[cfg_attr(docsrs, doc(cfg(unix)))]
+    pub fn is_listening(&self) -> io::Result<bool> {
+        sys::net::is_listening(self.as_raw_fd())
+    }
+
+    /// Returns the socket for the buffer size functions in `sys::tcp`.
+    #[cfg(unix)]
+    fn sys_socket(&self) -> sys::tcp::TcpSocket {
+        self.as_raw_fd()
+    }
+
+    /// Returns the socket for the buffer size functions in `sys::tcp`.
+    #[cfg(windows)]
+    fn sys_socket(&self) -> sys::tcp::TcpSocket {
+        self.as_raw_socket() as sys::tcp::TcpSocket
+    }
 }
 
 impl event::Source for UdpSocket {
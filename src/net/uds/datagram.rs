@@ -1,6 +1,9 @@
+use super::options::UnlinkOnDrop;
 use crate::io_source::IoSource;
+use crate::net::{BindOptions, Family, Kind};
 use crate::{event, sys, Interest, Registry, Token};
 
+use std::io::{IoSlice, IoSliceMut};
 use std::net::Shutdown;
 use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
 use std::os::unix::net;
@@ -10,6 +13,7 @@ use std::{fmt, io};
 /// A Unix datagram socket.
 pub struct UnixDatagram {
     inner: IoSource<net::UnixDatagram>,
+    unlink_on_drop: UnlinkOnDrop,
 }
 
 impl UnixDatagram {
@@ -18,6 +22,20 @@ impl UnixDatagram {
         sys::uds::datagram::bind(path.as_ref()).map(UnixDatagram::from_std)
     }
 
+    /// Creates a Unix datagram socket bound to the given path, using `options`.
+    ///
+    /// See [`BindOptions`] for the available options.
+    ///
+    /// [`BindOptions`]: struct.BindOptions.html
+    pub fn bind_with<P: AsRef<Path>>(path: P, options: &BindOptions) -> io::Result<UnixDatagram> {
+        let path = path.as_ref();
+        sys::uds::datagram::bind_with(path, options).map(|socket| {
+            let mut socket = UnixDatagram::from_std(socket);
+            socket.unlink_on_drop = UnlinkOnDrop::new(path, options);
+            socket
+        })
+    }
+
     /// Creates a new `UnixDatagram` from a standard `net::UnixDatagram`.
     ///
     /// This function is intended to be used to wrap a Unix datagram from the
@@ -27,6 +45,7 @@ impl UnixDatagram {
     pub fn from_std(socket: net::UnixDatagram) -> UnixDatagram {
         UnixDatagram {
             inner: IoSource::new(socket),
+            unlink_on_drop: UnlinkOnDrop::none(),
         }
     }
 
@@ -78,6 +97,20 @@ impl UnixDatagram {
         self.inner.do_io(|inner| inner.recv(buf))
     }
 
+    /// Returns the size of the next datagram that can be received, using
+    /// `ioctl(FIONREAD)`.
+    ///
+    /// If no datagram is queued this returns `Ok(0)`, not a [`WouldBlock`]
+    /// error. Like [`UdpSocket::next_datagram_size`] on Linux and Android this
+    /// returns the size of the next datagram, on other platforms it's the
+    /// total number of bytes of all queued datagrams.
+    ///
+    /// [`WouldBlock`]: std::io::ErrorKind::WouldBlock
+    /// [`UdpSocket::next_datagram_size`]: crate::net::UdpSocket::next_datagram_size
+    pub fn next_datagram_size(&self) -> io::Result<usize> {
+        sys::uds::datagram::bytes_available(&self.inner)
+    }
+
     /// Sends data on the socket to the specified address.
     ///
     /// On success, returns the number of bytes written.
@@ -95,11 +128,106 @@ impl UnixDatagram {
         self.inner.do_io(|inner| inner.send(buf))
     }
 
+    /// Sends the data in `bufs` as a single datagram to the specified
+    /// address, using `sendmsg(2)`.
+    ///
+    /// On success, returns the number of bytes written. Returns an
+    /// `InvalidInput` error if `bufs` contains more than `IOV_MAX` (1024)
+    /// buffers, rather than sending a truncated datagram.
+    pub fn send_to_vectored<P: AsRef<Path>>(
+        &self,
+        bufs: &[IoSlice<'_>],
+        path: P,
+    ) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::uds::datagram::send_to_vectored(inner, bufs, path.as_ref()))
+    }
+
+    /// Receives a single datagram into `bufs`, using `recvmsg(2)`.
+    ///
+    /// On success, returns the number of bytes read and the address from
+    /// whence the data came. Any part of the datagram that doesn't fit in
+    /// `bufs` is discarded. At most `IOV_MAX` (1024) buffers are used.
+    pub fn recv_from_vectored(
+        &self,
+        bufs: &mut [IoSliceMut<'_>],
+    ) -> io::Result<(usize, sys::SocketAddr)> {
+        self.inner
+            .do_io(|inner| sys::uds::datagram::recv_from_vectored(inner, bufs))
+    }
+
+    /// Sends the data in `bufs` as a single datagram to the socket's peer, see
+    /// [`send_to_vectored`].
+    ///
+    /// [`send_to_vectored`]: UnixDatagram::send_to_vectored
+    pub fn send_vectored(&self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::uds::datagram::send_vectored(inner, bufs))
+    }
+
+    /// Receives a single datagram into `bufs` from the socket's peer, see
+    /// [`recv_from_vectored`].
+    ///
+    /// [`recv_from_vectored`]: UnixDatagram::recv_from_vectored
+    pub fn recv_vectored(&self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::uds::datagram::recv_vectored(inner, bufs))
+    }
+
+    /// Receives a single datagram into `buf`, reporting whether it was
+    /// truncated. On success, returns the length of the datagram, whether it
+    /// was truncated because it didn't fit in `buf`, and the address from
+    /// whence the data came.
+    ///
+    /// Like [`recv_from`] any part of the datagram that doesn't fit in `buf` is
+    /// discarded, but unlike `recv_from` this can be detected.
+    ///
+    /// # Notes
+    ///
+    /// On Linux and Android the returned length is the real length of the
+    /// datagram, which can be larger than `buf` if it was truncated. On other
+    /// platforms it's the number of bytes written to `buf`, so only the
+    /// returned `bool` indicates the datagram was truncated.
+    ///
+    /// [`recv_from`]: UnixDatagram::recv_from
+    pub fn recv_from_full(&self, buf: &mut [u8]) -> io::Result<(usize, bool, sys::SocketAddr)> {
+        self.inner
+            .do_io(|inner| sys::uds::datagram::recv_from_full(inner, buf))
+    }
+
+    /// Receives a single datagram into `buf` from the socket's peer,
+    /// reporting whether it was truncated. See [`recv_from_full`].
+    ///
+    /// [`recv_from_full`]: UnixDatagram::recv_from_full
+    pub fn recv_full(&self, buf: &mut [u8]) -> io::Result<(usize, bool)> {
+        self.inner.do_io(|inner| sys::uds::datagram::recv_full(inner, buf))
+    }
+
     /// Returns the value of the `SO_ERROR` option.
     pub fn take_error(&self) -> io::Result<Option<io::Error>> {
         self.inner.take_error()
     }
 
+    /// Returns the address family of the socket, see [`Family`].
+    ///
+    /// [`Family`]: enum.Family.html
+    pub fn socket_family(&self) -> io::Result<Family> {
+        sys::net::socket_family(self.as_raw_fd())
+    }
+
+    /// Returns the type of the socket, see [`Kind`].
+    ///
+    /// [`Kind`]: enum.Kind.html
+    pub fn socket_type(&self) -> io::Result<Kind> {
+        sys::net::socket_type(self.as_raw_fd())
+    }
+
+    /// Returns `true` if the socket is listening for connections, i.e. the
+    /// value of the `SO_ACCEPTCONN` option.
+    pub fn is_listening(&self) -> io::Result<bool> {
+        sys::net::is_listening(self.as_raw_fd())
+    }
+
     /// Shut down the read, write, or both halves of this connection.
     ///
     /// This function will cause all pending and future I/O calls on the
@@ -141,7 +269,9 @@ impl fmt::Debug for UnixDatagram {
 }
 
 impl IntoRawFd for UnixDatagram {
-    fn into_raw_fd(self) -> RawFd {
+    fn into_raw_fd(mut self) -> RawFd {
+        // The caller now owns the socket, including its path.
+        self.unlink_on_drop.disarm();
         self.inner.into_inner().into_raw_fd()
     }
 }
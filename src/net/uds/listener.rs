@@ -1,5 +1,6 @@
+use super::options::UnlinkOnDrop;
 use crate::io_source::IoSource;
-use crate::net::{SocketAddr, UnixStream};
+use crate::net::{BindOptions, Family, Kind, SocketAddr, UnixStream};
 use crate::{event, sys, Interest, Registry, Token};
 
 use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
@@ -10,6 +11,7 @@ use std::{fmt, io};
 /// A non-blocking Unix domain socket server.
 pub struct UnixListener {
     inner: IoSource<net::UnixListener>,
+    unlink_on_drop: UnlinkOnDrop,
 }
 
 impl UnixListener {
@@ -18,6 +20,21 @@ impl UnixListener {
         sys::uds::listener::bind(path.as_ref()).map(UnixListener::from_std)
     }
 
+    /// Creates a new `UnixListener` bound to the specified socket, using
+    /// `options`.
+    ///
+    /// See [`BindOptions`] for the available options.
+    ///
+    /// [`BindOptions`]: struct.BindOptions.html
+    pub fn bind_with<P: AsRef<Path>>(path: P, options: &BindOptions) -> io::Result<UnixListener> {
+        let path = path.as_ref();
+        sys::uds::listener::bind_with(path, options).map(|listener| {
+            let mut listener = UnixListener::from_std(listener);
+            listener.unlink_on_drop = UnlinkOnDrop::new(path, options);
+            listener
+        })
+    }
+
     /// Creates a new `UnixListener` from a standard `net::UnixListener`.
     ///
     /// This function is intended to be used to wrap a Unix listener from the
@@ -27,6 +44,7 @@ impl UnixListener {
     pub fn from_std(listener: net::UnixListener) -> UnixListener {
         UnixListener {
             inner: IoSource::new(listener),
+            unlink_on_drop: UnlinkOnDrop::none(),
         }
     }
 
@@ -47,6 +65,26 @@ impl UnixListener {
     pub fn take_error(&self) -> io::Result<Option<io::Error>> {
         self.inner.take_error()
     }
+
+    /// Returns the address family of the socket, see [`Family`].
+    ///
+    /// [`Family`]: enum.Family.html
+    pub fn socket_family(&self) -> io::Result<Family> {
+        sys::net::socket_family(self.as_raw_fd())
+    }
+
+    /// Returns the type of the socket, see [`Kind`].
+    ///
+    /// [`Kind`]: enum.Kind.html
+    pub fn socket_type(&self) -> io::Result<Kind> {
+        sys::net::socket_type(self.as_raw_fd())
+    }
+
+    /// Returns `true` if the socket is listening for connections, i.e. the
+    /// value of the `SO_ACCEPTCONN` option.
+    pub fn is_listening(&self) -> io::Result<bool> {
+        sys::net::is_listening(self.as_raw_fd())
+    }
 }
 
 impl event::Source for UnixListener {
@@ -80,7 +118,9 @@ impl fmt::Debug for UnixListener {
 }
 
 impl IntoRawFd for UnixListener {
-    fn into_raw_fd(self) -> RawFd {
+    fn into_raw_fd(mut self) -> RawFd {
+        // The caller now owns the socket, including its path.
+        self.unlink_on_drop.disarm();
         self.inner.into_inner().into_raw_fd()
     }
 }
@@ -7,4 +7,7 @@ pub use self::listener::UnixListener;
 mod stream;
 pub use self::stream::UnixStream;
 
+mod options;
+pub use self::options::BindOptions;
+
 pub use crate::sys::SocketAddr;
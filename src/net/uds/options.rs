@@ -0,0 +1,88 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Options for binding a Unix socket to a path.
+///
+/// Used by [`UnixListener::bind_with`] and [`UnixDatagram::bind_with`]. The
+/// default options result in the same behaviour as `bind`.
+///
+/// [`UnixListener::bind_with`]: struct.UnixListener.html#method.bind_with
+/// [`UnixDatagram::bind_with`]: struct.UnixDatagram.html#method.bind_with
+///
+/// # Examples
+///
+#[cfg_attr(feature = "os-poll", doc = "```")]
+#[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use mio::net::{BindOptions, UnixListener};
+/// # let dir = std::env::temp_dir().join("mio_bind_options_doc");
+/// # let _ = std::fs::create_dir_all(&dir);
+/// # let path = dir.join("server.sock");
+///
+/// let mut options = BindOptions::default();
+/// // Remove the socket left behind by a previous run.
+/// options.unlink_existing = true;
+/// // Remove the socket once the listener is dropped.
+/// options.unlink_on_drop = true;
+/// // Only allow the owner to connect.
+/// options.mode = Some(0o600);
+/// let listener = UnixListener::bind_with(&path, &options)?;
+/// # drop(listener);
+/// # assert!(!path.exists());
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+#[non_exhaustive]
+pub struct BindOptions {
+    /// Remove an existing socket at the path before binding.
+    ///
+    /// Only sockets are removed, if the path points to another kind of file
+    /// binding fails with an error of kind `AlreadyExists`.
+    pub unlink_existing: bool,
+    /// Remove the socket file when the socket is dropped.
+    pub unlink_on_drop: bool,
+    /// File mode (permissions) set on the socket file.
+    ///
+    /// The mode is set after binding, but before the socket starts accepting
+    /// connections.
+    pub mode: Option<u32>,
+}
+
+/// Removes the socket file at `path`, if any, when dropped.
+#[derive(Debug)]
+pub(super) struct UnlinkOnDrop {
+    path: Option<PathBuf>,
+}
+
+impl UnlinkOnDrop {
+    pub(super) fn new(path: &Path, options: &BindOptions) -> UnlinkOnDrop {
+        UnlinkOnDrop {
+            path: if options.unlink_on_drop {
+                Some(path.to_owned())
+            } else {
+                None
+            },
+        }
+    }
+
+    pub(super) fn none() -> UnlinkOnDrop {
+        UnlinkOnDrop { path: None }
+    }
+
+    /// Don't remove the socket file, e.g. when the ownership of the socket is
+    /// transferred.
+    pub(super) fn disarm(&mut self) {
+        self.path = None;
+    }
+}
+
+impl Drop for UnlinkOnDrop {
+    fn drop(&mut self) {
+        if let Some(path) = self.path.take() {
+            // Can't do much with the error.
+            let _ = fs::remove_file(path);
+        }
+    }
+}
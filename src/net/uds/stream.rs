@@ -1,4 +1,5 @@
 use crate::io_source::IoSource;
+use crate::net::{Family, Kind};
 use crate::{event, sys, Interest, Registry, Token};
 
 use std::fmt;
@@ -56,11 +57,42 @@ impl UnixStream {
         sys::uds::stream::peer_addr(&self.inner)
     }
 
+    /// Returns the number of bytes that can be read without blocking, using
+    /// `ioctl(FIONREAD)`.
+    ///
+    /// If no data is available this returns `Ok(0)`, not a [`WouldBlock`]
+    /// error.
+    ///
+    /// [`WouldBlock`]: std::io::ErrorKind::WouldBlock
+    pub fn bytes_available(&self) -> io::Result<usize> {
+        sys::uds::stream::bytes_available(&self.inner)
+    }
+
     /// Returns the value of the `SO_ERROR` option.
     pub fn take_error(&self) -> io::Result<Option<io::Error>> {
         self.inner.take_error()
     }
 
+    /// Returns the address family of the socket, see [`Family`].
+    ///
+    /// [`Family`]: enum.Family.html
+    pub fn socket_family(&self) -> io::Result<Family> {
+        sys::net::socket_family(self.as_raw_fd())
+    }
+
+    /// Returns the type of the socket, see [`Kind`].
+    ///
+    /// [`Kind`]: enum.Kind.html
+    pub fn socket_type(&self) -> io::Result<Kind> {
+        sys::net::socket_type(self.as_raw_fd())
+    }
+
+    /// Returns `true` if the socket is listening for connections, i.e. the
+    /// value of the `SO_ACCEPTCONN` option.
+    pub fn is_listening(&self) -> io::Result<bool> {
+        sys::net::is_listening(self.as_raw_fd())
+    }
+
     /// Shuts down the read, write, or both halves of this connection.
     ///
     /// This function will cause all pending and future I/O calls on the
@@ -1,8 +1,17 @@
+#[cfg(unix)]
+use crate::event::Event;
+use crate::features::Capabilities;
 use crate::{event, sys, Events, Interest, Token};
+#[cfg(all(feature = "os-poll", any(target_os = "linux", target_os = "android")))]
+use log::debug;
 use log::trace;
 #[cfg(unix)]
+use std::mem::MaybeUninit;
+#[cfg(unix)]
 use std::os::unix::io::{AsRawFd, RawFd};
-use std::time::Duration;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use std::{fmt, io};
 
 /// Polls for readiness events on all registered values.
@@ -95,6 +104,16 @@ use std::{fmt, io};
 ///
 /// [`WouldBlock`]: std::io::ErrorKind::WouldBlock
 ///
+/// ### Readiness on registration
+///
+/// Readiness events are edge-triggered, an event is only delivered when the
+/// readiness of an event source changes. An event source that is already ready
+/// when it's registered, e.g. a connected socket with room in its send buffer,
+/// will receive a single readiness event after registering. That single
+/// writable event must be handled like any other, no further writable events
+/// are delivered until the socket's send buffer first fills up (i.e. a write
+/// returned [`WouldBlock`]) and then has room again.
+///
 /// ### Readiness operations
 ///
 /// The only readiness operations that are guaranteed to be present on all
@@ -203,11 +222,25 @@ use std::{fmt, io};
 /// [`Poll::poll`]: struct.Poll.html#method.poll
 pub struct Poll {
     registry: Registry,
+    /// Deadline set by [`Poll::wake_after`].
+    wake_deadline: Option<Instant>,
+    /// Timer that wakes up the selector at `wake_deadline`, created by the
+    /// first call to [`Poll::wake_after`].
+    #[cfg(all(feature = "os-poll", any(target_os = "linux", target_os = "android")))]
+    wake_timer: Option<sys::WakeTimer>,
+    /// Buffer used by [`Poll::poll_owned`], allocated on first use.
+    owned_events: Option<Events>,
 }
 
 /// Registers I/O resources.
 pub struct Registry {
     selector: sys::Selector,
+    /// Set once the `Poll` instance is dropped or [`Registry::shutdown`] is
+    /// called, shared with all clones.
+    closed: Arc<AtomicBool>,
+    /// Waker used by [`Registry::shutdown`], kept alive so the wake up isn't
+    /// lost, shared with all clones.
+    shutdown_waker: Arc<Mutex<Option<sys::Waker>>>,
 }
 
 impl Poll {
@@ -217,6 +250,14 @@ impl Poll {
         &self.registry
     }
 
+    /// Returns the guarantees provided by the system selector, see
+    /// [`Capabilities`].
+    ///
+    /// [`Capabilities`]: features/struct.Capabilities.html
+    pub fn capabilities() -> Capabilities {
+        sys::Selector::CAPABILITIES
+    }
+
     /// Wait for readiness events
     ///
     /// Blocks the current thread and waits for readiness events for any of the
@@ -228,7 +269,10 @@ impl Poll {
     /// The supplied `events` will be cleared and newly received readiness events
     /// will be pushed onto the end. At most `events.capacity()` events will be
     /// returned. If there are further pending readiness events, they will be
-    /// returned on the next call to `poll`.
+    /// returned on the next call to `poll`. This means `events` can be reused
+    /// between calls: after `poll` returns it only contains the events of that
+    /// call, none of the events of a previous call remain, even if that call
+    /// returned more events.
     ///
     /// A single call to `poll` may result in multiple readiness events being
     /// returned for a single event source. For example, if a TCP socket becomes
@@ -241,6 +285,10 @@ impl Poll {
     /// granularity (usually 1ms), and kernel scheduling delays mean that
     /// the blocking interval may be overrun by a small amount.
     ///
+    /// On platforms using epoll a `timeout` longer than `i32::MAX`
+    /// milliseconds (about 24.8 days), e.g. `Duration::MAX`, is treated as no
+    /// timeout (`None`).
+    ///
     /// See the [struct] level documentation for a higher level discussion of
     /// polling.
     ///
@@ -256,6 +304,10 @@ impl Poll {
     /// of Mio would automatically retry the poll call if it was interrupted
     /// (if `EINTR` was returned).
     ///
+    /// Errors returned by the system selector are passed through as is, so
+    /// [`io::Error::raw_os_error`] returns the original error code, e.g.
+    /// `EINTR` or `EINVAL`.
+    ///
     /// # Examples
     ///
     /// A basic example -- establishing a `TcpStream` connection.
@@ -311,7 +363,378 @@ impl Poll {
     ///
     /// [struct]: #
     pub fn poll(&mut self, events: &mut Events, timeout: Option<Duration>) -> io::Result<()> {
-        self.registry.selector.select(events.sys(), timeout)
+        if self.is_shutdown() {
+            events.clear();
+            return Err(shutdown_error());
+        }
+
+        let timeout = self.wake_timeout(timeout);
+        let res = self.registry.selector.select(events.sys(), timeout);
+        if self.is_shutdown() {
+            // Drop the events, they include the wake up by `shutdown`.
+            events.clear();
+            return Err(shutdown_error());
+        }
+        // Take the timestamp as close to the system call as possible.
+        let now = Instant::now();
+        events.set_ready_at(res.as_ref().ok().map(|_| now));
+
+        self.remove_wake_timer_events(events.sys());
+        let expired = res.is_ok() && self.expire_wake_deadline(now);
+        events.set_deadline_expired(expired);
+        res
+    }
+
+    /// Wait for readiness events, like [`Poll::poll`], using an `Events`
+    /// buffer owned by the `Poll` instance.
+    ///
+    /// The buffer is allocated on the first call, with capacity for
+    /// [`OWNED_EVENTS_CAPACITY`] events, and reused by all following calls.
+    /// The returned events are replaced by the next call to `poll_owned`. For
+    /// control over the capacity, or to keep the events while polling again,
+    /// use [`Poll::poll`] with an `Events` buffer of your own.
+    ///
+    /// [`OWNED_EVENTS_CAPACITY`]: Poll::OWNED_EVENTS_CAPACITY
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(feature = "os-poll", doc = "```")]
+    #[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use std::time::Duration;
+    ///
+    /// use mio::Poll;
+    ///
+    /// let mut poll = Poll::new()?;
+    ///
+    /// let events = poll.poll_owned(Some(Duration::from_millis(10)))?;
+    /// for event in events.iter() {
+    ///     println!("got event: {:?}", event);
+    /// }
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn poll_owned(&mut self, timeout: Option<Duration>) -> io::Result<&Events> {
+        let mut events = self
+            .owned_events
+            .take()
+            .unwrap_or_else(|| Events::with_capacity(Poll::OWNED_EVENTS_CAPACITY));
+        let res = self.poll(&mut events, timeout);
+        let events = self.owned_events.get_or_insert(events);
+        res.map(move |()| &*events)
+    }
+
+    /// Wait for readiness events, like [`Poll::poll`], storing them in the
+    /// caller provided `buf` instead of an `Events` buffer.
+    ///
+    /// At most `buf.len()` events are returned, if more event sources are
+    /// ready the remaining events are returned by the next call. This never
+    /// allocates. Returns the initialised part of `buf`, i.e. the events.
+    ///
+    /// Returns an `InvalidInput` error if `buf` is empty.
+    ///
+    /// The deadline set by [`Poll::wake_after`] is respected and cleared once
+    /// it's reached, the same as in `poll`. But as there is no `Events` to
+    /// report it to this can't be told apart from a timeout.
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(feature = "os-poll", doc = "```")]
+    #[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use std::mem::MaybeUninit;
+    /// use std::time::Duration;
+    ///
+    /// use mio::event::Event;
+    /// use mio::Poll;
+    ///
+    /// let mut poll = Poll::new()?;
+    /// let mut buf: [MaybeUninit<Event>; 16] = unsafe { MaybeUninit::uninit().assume_init() };
+    ///
+    /// let events = poll.poll_into(&mut buf, Some(Duration::from_millis(10)))?;
+    /// for event in events {
+    ///     println!("got event: {:?}", event);
+    /// }
+    /// #     Ok(())
+    /// # }
+    /// ```
+    #[cfg(unix)]
+    #[cfg_attr(docsrs, doc(cfg(unix)))]
+    pub fn poll_into<'a>(
+        &mut self,
+        buf: &'a mut [MaybeUninit<Event>],
+        timeout: Option<Duration>,
+    ) -> io::Result<&'a [Event]> {
+        if self.is_shutdown() {
+            return Err(shutdown_error());
+        }
+        if buf.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "empty events buffer",
+            ));
+        }
+
+        // Safety: `Event` has the same layout as `sys::Event` due to the
+        // `repr(transparent)` attribute.
+        let sys_buf =
+            unsafe { &mut *(buf as *mut [MaybeUninit<Event>] as *mut [MaybeUninit<sys::Event>]) };
+        let timeout = self.wake_timeout(timeout);
+        let res = self.registry.selector.select_into(sys_buf, timeout);
+        if self.is_shutdown() {
+            return Err(shutdown_error());
+        }
+        let n_events = res?;
+        // Safety: `select_into` initialised the first `n_events` events.
+        let n_events = self.remove_wake_timer_events_from(unsafe {
+            std::slice::from_raw_parts_mut(sys_buf.as_mut_ptr() as *mut sys::Event, n_events)
+        });
+        self.expire_wake_deadline(Instant::now());
+
+        // Safety: `select_into` initialised the first `n_events` events.
+        Ok(unsafe { std::slice::from_raw_parts(buf.as_ptr() as *const Event, n_events) })
+    }
+
+    /// Capacity of the `Events` buffer used by [`Poll::poll_owned`].
+    pub const OWNED_EVENTS_CAPACITY: usize = 1024;
+
+    /// Returns `true` if [`Registry::shutdown`] was called.
+    ///
+    /// Once shut down all calls to [`Poll::poll`] return an error.
+    pub fn is_shutdown(&self) -> bool {
+        self.registry.closed.load(Ordering::Acquire)
+    }
+
+    /// Wake up the poll loop after `after` has elapsed.
+    ///
+    /// The next call(s) to [`Poll::poll`] will not block past the deadline set
+    /// by this function, regardless of the `timeout` passed to it. Once the
+    /// deadline is reached `poll` returns, even if no events were received,
+    /// and [`Events::deadline_expired`] returns `true` to distinguish it from
+    /// a timeout or a readiness wakeup. The deadline is then cleared, it only
+    /// fires once.
+    ///
+    /// Only a single deadline is kept, calling this again replaces the previous
+    /// deadline.
+    ///
+    /// [`Events::deadline_expired`]: ./event/struct.Events.html#method.deadline_expired
+    ///
+    /// # Notes
+    ///
+    /// On Linux and Android the deadline is implemented using a [`timerfd`]
+    /// registered with the system selector, using a [reserved token]. Its
+    /// event isn't returned. On other platforms, or if creating the timer
+    /// fails, it's implemented by limiting the timeout passed to the system
+    /// selector, so it has the same (usually millisecond) granularity as
+    /// `poll`'s `timeout` argument.
+    ///
+    /// [`timerfd`]: https://man7.org/linux/man-pages/man2/timerfd_create.2.html
+    /// [reserved token]: struct.Token.html#method.is_reserved
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(feature = "os-poll", doc = "```")]
+    #[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::{Events, Poll};
+    /// use std::time::Duration;
+    ///
+    /// let mut poll = Poll::new()?;
+    /// let mut events = Events::with_capacity(8);
+    ///
+    /// poll.wake_after(Duration::from_millis(10));
+    ///
+    /// // Without any registered sources this would block forever, but the
+    /// // deadline wakes us up.
+    /// poll.poll(&mut events, None)?;
+    /// assert!(events.deadline_expired());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn wake_after(&mut self, after: Duration) {
+        self.wake_deadline = Some(Instant::now() + after);
+        self.set_wake_timer(after);
+    }
+
+    /// Returns the timeout to pass to the selector to not block past the
+    /// deadline set by `wake_after`.
+    fn wake_timeout(&self, timeout: Option<Duration>) -> Option<Duration> {
+        match self.wake_deadline {
+            // The timer wakes us up, unless it already expired.
+            Some(deadline) if self.has_wake_timer() && Instant::now() < deadline => timeout,
+            Some(deadline) => timeout_until(deadline, timeout),
+            None => timeout,
+        }
+    }
+
+    /// Clears the deadline set by `wake_after` if it expired at `now`,
+    /// returning `true` if so.
+    fn expire_wake_deadline(&mut self, now: Instant) -> bool {
+        match self.wake_deadline {
+            Some(deadline) if now >= deadline => {
+                self.wake_deadline = None;
+                self.reset_wake_timer();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Run an event loop, calling `on_events` and `on_tick` after every call
+    /// to [`poll`].
+    ///
+    /// Every iteration polls for events using `events` and `timeout`, passes
+    /// the events to `on_events` and then calls `on_tick` to perform
+    /// maintenance, e.g. expire timers. `on_tick` is also called if `poll`
+    /// returned without events, e.g. because `timeout` elapsed. The loop stops
+    /// once `on_tick` returns `false`, so to stop the loop from another thread
+    /// wake it using a [`Waker`] and check a flag in `on_tick`.
+    ///
+    /// If `poll` is interrupted (returns an [`Interrupted`] error) it's
+    /// retried, without calling either closure. Any other error, including
+    /// errors returned by `on_events`, stops the loop and is returned.
+    ///
+    /// [`poll`]: Poll::poll
+    /// [`Waker`]: crate::Waker
+    /// [`Interrupted`]: io::ErrorKind::Interrupted
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(feature = "os-poll", doc = "```")]
+    #[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use std::time::Duration;
+    ///
+    /// use mio::{Events, Poll};
+    ///
+    /// let mut poll = Poll::new()?;
+    /// let mut events = Events::with_capacity(128);
+    ///
+    /// // Register `event::Source`s with `poll`.
+    ///
+    /// let mut ticks = 0;
+    /// poll.run(
+    ///     &mut events,
+    ///     Some(Duration::from_millis(10)),
+    ///     |_registry, events| {
+    ///         for event in events.iter() {
+    ///             println!("Got an event for {:?}", event.token());
+    ///         }
+    ///         Ok(())
+    ///     },
+    ///     |_registry| {
+    ///         ticks += 1;
+    ///         // Stop after three iterations.
+    ///         ticks < 3
+    ///     },
+    /// )?;
+    /// assert_eq!(ticks, 3);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn run<E, T>(
+        &mut self,
+        events: &mut Events,
+        timeout: Option<Duration>,
+        mut on_events: E,
+        mut on_tick: T,
+    ) -> io::Result<()>
+    where
+        E: FnMut(&Registry, &Events) -> io::Result<()>,
+        T: FnMut(&Registry) -> bool,
+    {
+        loop {
+            match self.poll(events, timeout) {
+                Ok(()) => {}
+                Err(ref err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+            }
+            on_events(&self.registry, events)?;
+            if !on_tick(&self.registry) {
+                return Ok(());
+            }
+        }
+    }
+}
+
+#[cfg(all(feature = "os-poll", any(target_os = "linux", target_os = "android")))]
+impl Poll {
+    /// Arms the timer for the deadline set by [`Poll::wake_after`]. If that
+    /// fails `poll` limits its timeout instead.
+    fn set_wake_timer(&mut self, after: Duration) {
+        if self.wake_timer.is_none() {
+            match sys::WakeTimer::new(&self.registry.selector, WAKE_TIMER_TOKEN) {
+                Ok(timer) => self.wake_timer = Some(timer),
+                Err(err) => {
+                    debug!("error creating wake timer: {}", err);
+                    return;
+                }
+            }
+        }
+        if let Err(err) = self.wake_timer.as_ref().unwrap().set(after) {
+            debug!("error setting wake timer: {}", err);
+            self.wake_timer = None;
+        }
+    }
+
+    /// Returns `true` if the deadline is tracked by the timer.
+    fn has_wake_timer(&self) -> bool {
+        self.wake_timer.is_some()
+    }
+
+    /// Resets the timer once the deadline expired.
+    fn reset_wake_timer(&mut self) {
+        if let Some(timer) = &self.wake_timer {
+            if let Err(err) = timer.reset() {
+                debug!("error resetting wake timer: {}", err);
+                self.wake_timer = None;
+            }
+        }
+    }
+
+    /// Removes the timer's events from `events`.
+    fn remove_wake_timer_events(&self, events: &mut sys::Events) {
+        let n_events = self.remove_wake_timer_events_from(events);
+        events.truncate(n_events);
+    }
+
+    /// Moves the timer's events to the end of `events`, returning the number
+    /// of other events (which keep their order).
+    fn remove_wake_timer_events_from(&self, events: &mut [sys::Event]) -> usize {
+        let timer = match &self.wake_timer {
+            Some(timer) => timer,
+            None => return events.len(),
+        };
+        let mut n_events = 0;
+        for index in 0..events.len() {
+            if !timer.is_event(&events[index]) {
+                events.swap(n_events, index);
+                n_events += 1;
+            }
+        }
+        n_events
+    }
+}
+
+#[cfg(not(all(feature = "os-poll", any(target_os = "linux", target_os = "android"))))]
+impl Poll {
+    fn set_wake_timer(&mut self, _: Duration) {}
+
+    fn has_wake_timer(&self) -> bool {
+        false
+    }
+
+    fn reset_wake_timer(&mut self) {}
+
+    fn remove_wake_timer_events(&self, _: &mut sys::Events) {}
+
+    #[cfg(unix)]
+    fn remove_wake_timer_events_from(&self, events: &mut [sys::Event]) -> usize {
+        events.len()
     }
 }
 
@@ -352,7 +775,15 @@ cfg_os_poll! {
         /// ```
         pub fn new() -> io::Result<Poll> {
             sys::Selector::new().map(|selector| Poll {
-                registry: Registry { selector },
+                registry: Registry {
+                    selector,
+                    closed: Arc::new(AtomicBool::new(false)),
+                    shutdown_waker: Arc::new(Mutex::new(None)),
+                },
+                wake_deadline: None,
+                #[cfg(any(target_os = "linux", target_os = "android"))]
+                wake_timer: None,
+                owned_events: None,
             })
         }
     }
@@ -365,6 +796,85 @@ impl AsRawFd for Poll {
     }
 }
 
+/// On Unix the system selector is itself a file descriptor, which becomes
+/// readable once it has pending events. This allows a `Poll` instance to be
+/// registered with another `Poll` instance, to drive nested event loops from a
+/// single outer loop. See `unix::multi_poll::MultiPoll` (requires the
+/// `os-ext` feature) for a higher level API.
+///
+/// An event for the inner `Poll` means that calling [`poll`] on it with a zero
+/// timeout will likely return events, but it could also return no events, e.g.
+/// if the event source was deregistered in the meantime. Like all event sources
+/// the inner `Poll` is edge-triggered: if `poll` filled the entire [`Events`]
+/// buffer it must be polled again, as it might still have pending events for
+/// which the outer `Poll` won't return another event.
+///
+/// Only [readable] interest is meaningful, and a `Poll` instance can't be
+/// registered with itself.
+///
+/// [`poll`]: Poll::poll
+/// [readable]: Interest::READABLE
+///
+/// # Examples
+///
+#[cfg_attr(feature = "net", doc = "```")]
+#[cfg_attr(not(feature = "net"), doc = "```ignore")]
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use std::time::Duration;
+///
+/// use mio::net::UdpSocket;
+/// use mio::{Events, Interest, Poll, Token};
+///
+/// let mut outer = Poll::new()?;
+/// let mut inner = Poll::new()?;
+/// let mut events = Events::with_capacity(8);
+///
+/// outer.registry().register(&mut inner, Token(0), Interest::READABLE)?;
+///
+/// let mut socket = UdpSocket::bind("127.0.0.1:0".parse()?)?;
+/// inner.registry().register(&mut socket, Token(1), Interest::WRITABLE)?;
+///
+/// outer.poll(&mut events, None)?;
+/// assert_eq!(events.iter().next().unwrap().token(), Token(0));
+///
+/// inner.poll(&mut events, Some(Duration::from_millis(0)))?;
+/// assert_eq!(events.iter().next().unwrap().token(), Token(1));
+/// #     Ok(())
+/// # }
+/// ```
+#[cfg(all(unix, feature = "os-poll"))]
+#[cfg_attr(docsrs, doc(cfg(all(unix, feature = "os-poll"))))]
+impl event::Source for Poll {
+    fn register(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        sys::SourceFd(&self.as_raw_fd()).register(registry, token, interests)
+    }
+
+    fn reregister(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        sys::SourceFd(&self.as_raw_fd()).reregister(registry, token, interests)
+    }
+
+    fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
+        sys::SourceFd(&self.as_raw_fd()).deregister(registry)
+    }
+}
+
+impl Drop for Poll {
+    fn drop(&mut self) {
+        self.registry.closed.store(true, Ordering::Release);
+    }
+}
+
 impl fmt::Debug for Poll {
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
         fmt.debug_struct("Poll").finish()
@@ -401,6 +911,10 @@ impl Registry {
     /// If a socket is registered with readable interest and the socket becomes
     /// writable, no event will be returned from [`poll`].
     ///
+    /// Interests must contain at least one readiness, registering with only
+    /// `Interest::ONESHOT` returns an `InvalidInput` error. So does using a
+    /// [reserved token].
+    ///
     /// The readiness interest for an `event::Source` can be changed at any time
     /// by calling [`reregister`].
     ///
@@ -417,8 +931,17 @@ impl Registry {
     /// if the event source is deregistered from the poll instance using
     /// [`deregister`].
     ///
+    /// Registering an event source while another thread is blocked in
+    /// [`poll`] (using a [cloned] `Registry`) is supported. The event source is
+    /// monitored by the in-progress call immediately, if it becomes ready the
+    /// blocked call returns an event for it, it's not needed to wake up the
+    /// thread (e.g. using a [`Waker`]) after registering.
+    ///
     /// [`event::Source`]: ./event/trait.Source.html
+    /// [reserved token]: struct.Token.html#method.is_reserved
     /// [`poll`]: struct.Poll.html#method.poll
+    /// [cloned]: struct.Registry.html#method.try_clone
+    /// [`Waker`]: struct.Waker.html
     /// [`reregister`]: struct.Registry.html#method.reregister
     /// [`deregister`]: struct.Registry.html#method.deregister
     /// [`Token`]: struct.Token.html
@@ -480,6 +1003,8 @@ impl Registry {
             token,
             interests
         );
+        self.check_open()?;
+        check_registration(token, interests)?;
         source.register(self, token, interests)
     }
 
@@ -497,6 +1022,15 @@ impl Registry {
     /// The event source must have previously been registered with this instance
     /// of `Poll`, otherwise the behavior is undefined.
     ///
+    /// Reregistering makes the OS selector check the current readiness of the
+    /// event source, even when the interests didn't change. If the source is
+    /// ready an event is returned by the next poll, even though its readiness
+    /// didn't change since the last event. This can be used to "rearm" an
+    /// event source, e.g. after reading stopped before a [`WouldBlock`] error
+    /// was returned, which otherwise would mean no new event is returned.
+    ///
+    /// [`WouldBlock`]: std::io::ErrorKind::WouldBlock
+    ///
     /// See the [`register`] documentation for details about the function
     /// arguments and see the [`struct`] docs for a high level overview of
     /// polling.
@@ -548,6 +1082,8 @@ impl Registry {
             token,
             interests
         );
+        self.check_open()?;
+        check_registration(token, interests)?;
         source.reregister(self, token, interests)
     }
 
@@ -564,6 +1100,14 @@ impl Registry {
     /// The event source must have previously been registered with this instance
     /// of `Poll`, otherwise the behavior is undefined.
     ///
+    /// Deregistering only affects the readiness events, not the data queued
+    /// in the event source. For example when shutting down a connection it can
+    /// be deregistered right away, even if a readable event is still pending,
+    /// any buffered data can still be read until a [`WouldBlock`] error is
+    /// returned or the peer closed the connection.
+    ///
+    /// [`WouldBlock`]: std::io::ErrorKind::WouldBlock
+    ///
     /// A handle can be passed back to `register` after it has been
     /// deregistered; however, it must be passed back to the **same** `Poll`
     /// instance, otherwise the behavior is undefined.
@@ -607,17 +1151,182 @@ impl Registry {
         S: event::Source + ?Sized,
     {
         trace!("deregistering event source from poller");
+        self.check_open()?;
         source.deregister(self)
     }
 
+    /// Transfer an [`event::Source`] from this `Registry` to `target`.
+    ///
+    /// This deregisters `source` from `self` and registers it with `target`,
+    /// using `token` and `interests`. This can be used to hand off a source to
+    /// another `Poll` instance, e.g. one owned by a worker thread.
+    ///
+    /// No readiness is lost in the transfer, but only because registering a
+    /// source that is already ready (e.g. it's readable) makes the selector
+    /// report an event for it, so `target` returns an event for the source
+    /// without it becoming ready again. Events the original `Poll` instance
+    /// already received, but weren't yet returned by [`Poll::poll`], are
+    /// dropped. Also see [`Registry::register`].
+    ///
+    /// # Errors
+    ///
+    /// If `self` or `target` is closed, or `token` or `interests` can't be
+    /// used to register a source, an error is returned before `source` is
+    /// deregistered, so it stays registered with `self`. If registering with
+    /// `target` fails after `source` was deregistered from `self`, the source
+    /// isn't registered with either `Registry`. The error of registering is
+    /// returned and the source can be registered again, using
+    /// [`Registry::register`] with either `self` or `target`.
+    ///
+    /// [`event::Source`]: ./event/trait.Source.html
+    /// [`Poll::poll`]: struct.Poll.html#method.poll
+    /// [`Registry::register`]: #method.register
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(all(feature = "os-poll", feature = "net"), doc = "```")]
+    #[cfg_attr(not(all(feature = "os-poll", feature = "net")), doc = "```ignore")]
+    /// # use std::error::Error;
+    /// # use std::net;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::{Poll, Interest, Token};
+    /// use mio::net::TcpStream;
+    ///
+    /// let acceptor = Poll::new()?;
+    /// let worker = Poll::new()?;
+    ///
+    /// let listener = net::TcpListener::bind("127.0.0.1:0")?;
+    /// let mut socket = TcpStream::connect(listener.local_addr()?)?;
+    ///
+    /// acceptor.registry().register(&mut socket, Token(0), Interest::READABLE)?;
+    ///
+    /// // Hand the socket off to the worker.
+    /// acceptor.registry().transfer(
+    ///     &mut socket,
+    ///     worker.registry(),
+    ///     Token(1),
+    ///     Interest::READABLE | Interest::WRITABLE)?;
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn transfer<S>(
+        &self,
+        source: &mut S,
+        target: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()>
+    where
+        S: event::Source + ?Sized,
+    {
+        trace!(
+            "transferring event source to other poller: token={:?}, interests={:?}",
+            token,
+            interests
+        );
+        self.check_open()?;
+        target.check_open()?;
+        check_registration(token, interests)?;
+        source.deregister(self)?;
+        source.register(target, token, interests)
+    }
+
     /// Creates a new independently owned `Registry`.
     ///
     /// Event sources registered with this `Registry` will be registered with
     /// the original `Registry` and `Poll` instance.
+    ///
+    /// The cloned `Registry` can outlive the `Poll` instance. Once the `Poll`
+    /// instance is dropped (or [shut down]) [`register`], [`reregister`] and
+    /// [`deregister`] return an error (of kind `Other`) on all clones, as no
+    /// events would be returned for the event sources anymore.
+    ///
+    /// [shut down]: #method.shutdown
+    /// [`register`]: #method.register
+    /// [`reregister`]: #method.reregister
+    /// [`deregister`]: #method.deregister
     pub fn try_clone(&self) -> io::Result<Registry> {
-        self.selector
-            .try_clone()
-            .map(|selector| Registry { selector })
+        self.selector.try_clone().map(|selector| Registry {
+            selector,
+            closed: self.closed.clone(),
+            shutdown_waker: self.shutdown_waker.clone(),
+        })
+    }
+
+    /// Shut down the `Poll` instance, e.g. to stop an event loop running on
+    /// another thread.
+    ///
+    /// This wakes up a thread blocked in [`Poll::poll`], even if it was called
+    /// without a timeout. The in-flight call and all subsequent calls to
+    /// `poll` return an error (of kind `Other`), without returning any events,
+    /// and [`Poll::is_shutdown`] returns `true` so the error can be told apart
+    /// from other errors. Like after the `Poll` instance is dropped
+    /// [`register`], [`reregister`] and [`deregister`] return an error on all
+    /// clones.
+    ///
+    /// Shutting down can't be undone, calling this again has no effect. If
+    /// waking up the `Poll` instance fails an error is returned and the
+    /// instance isn't shut down, so this can be retried.
+    ///
+    /// [`register`]: #method.register
+    /// [`reregister`]: #method.reregister
+    /// [`deregister`]: #method.deregister
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(feature = "os-poll", doc = "```")]
+    #[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use std::thread;
+    ///
+    /// use mio::{Events, Poll};
+    ///
+    /// let mut poll = Poll::new()?;
+    /// let registry = poll.registry().try_clone()?;
+    ///
+    /// let handle = thread::spawn(move || {
+    ///     let mut events = Events::with_capacity(8);
+    ///     loop {
+    ///         if let Err(err) = poll.poll(&mut events, None) {
+    ///             assert!(poll.is_shutdown());
+    ///             return err;
+    ///         }
+    ///     }
+    /// });
+    ///
+    /// registry.shutdown()?;
+    /// let _err = handle.join().unwrap();
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn shutdown(&self) -> io::Result<()> {
+        let mut shutdown_waker = self.shutdown_waker.lock().unwrap();
+        if shutdown_waker.is_some() {
+            return Ok(());
+        }
+        let waker = sys::Waker::new(&self.selector, SHUTDOWN_TOKEN)?;
+        // Set the flag before waking, so the woken poll sees it.
+        let was_closed = self.closed.swap(true, Ordering::AcqRel);
+        if let Err(err) = waker.wake() {
+            if !was_closed {
+                self.closed.store(false, Ordering::Release);
+            }
+            return Err(err);
+        }
+        *shutdown_waker = Some(waker);
+        Ok(())
+    }
+
+    /// Returns an error if the `Poll` instance was dropped or shut down.
+    // `io::Error::other` isn't available on our MSRV.
+    #[allow(unknown_lints, clippy::io_other_error)]
+    fn check_open(&self) -> io::Result<()> {
+        if self.closed.load(Ordering::Acquire) {
+            Err(io::Error::new(io::ErrorKind::Other, "poll instance closed"))
+        } else {
+            Ok(())
+        }
     }
 
     /// Internal check to ensure only a single `Waker` is active per [`Poll`]
@@ -630,6 +1339,139 @@ impl Registry {
     }
 }
 
+#[cfg(unix)]
+cfg_os_poll! {
+    use log::error;
+    use std::ops::{Deref, DerefMut};
+
+    impl Registry {
+        /// Register an [`event::Source`] with the `Poll` instance, returning a
+        /// guard that deregisters the source when dropped.
+        ///
+        /// This works the same as [`register`], but the returned
+        /// [`RegistrationGuard`] will deregister the source once it's dropped.
+        /// This prevents forgetting to deregister a source before dropping it.
+        ///
+        /// The guard mutably borrows `source` for its lifetime, it can be
+        /// accessed through the guard (it implements `Deref` and `DerefMut`).
+        ///
+        /// As the guard also borrows `self`, a guard created using the `Registry`
+        /// returned by [`Poll::registry`] can't be kept alive while calling
+        /// [`Poll::poll`]. Use [`Registry::try_clone`] to create an
+        /// independently owned `Registry` for this.
+        ///
+        /// [`Poll::registry`]: struct.Poll.html#method.registry
+        /// [`Poll::poll`]: struct.Poll.html#method.poll
+        /// [`Registry::try_clone`]: #method.try_clone
+        ///
+        /// [`event::Source`]: ./event/trait.Source.html
+        /// [`register`]: #method.register
+        /// [`RegistrationGuard`]: struct.RegistrationGuard.html
+        ///
+        /// # Examples
+        ///
+        #[cfg_attr(all(feature = "os-poll", feature = "net"), doc = "```")]
+        #[cfg_attr(not(all(feature = "os-poll", feature = "net")), doc = "```ignore")]
+        /// # use std::error::Error;
+        /// # use std::net;
+        /// # fn main() -> Result<(), Box<dyn Error>> {
+        /// use mio::{Events, Poll, Interest, Token};
+        /// use mio::net::TcpStream;
+        /// use std::time::Duration;
+        ///
+        /// let mut poll = Poll::new()?;
+        ///
+        /// let listener = net::TcpListener::bind("127.0.0.1:0")?;
+        /// let mut socket = TcpStream::connect(listener.local_addr()?)?;
+        ///
+        /// let guard = poll.registry().register_guarded(
+        ///     &mut socket,
+        ///     Token(0),
+        ///     Interest::WRITABLE)?;
+        ///
+        /// // Deregisters the socket.
+        /// drop(guard);
+        ///
+        /// let mut events = Events::with_capacity(1024);
+        /// poll.poll(&mut events, Some(Duration::from_millis(100)))?;
+        /// assert!(events.is_empty());
+        /// #     Ok(())
+        /// # }
+        /// ```
+        pub fn register_guarded<'a, S>(
+            &'a self,
+            source: &'a mut S,
+            token: Token,
+            interests: Interest,
+        ) -> io::Result<RegistrationGuard<'a, S>>
+        where
+            S: event::Source + ?Sized,
+        {
+            self.register(source, token, interests)
+                .map(move |()| RegistrationGuard {
+                    registry: self,
+                    source,
+                })
+        }
+    }
+
+    /// Guard returned by [`Registry::register_guarded`].
+    ///
+    /// Deregisters the event source when dropped.
+    ///
+    /// [`Registry::register_guarded`]: struct.Registry.html#method.register_guarded
+    #[derive(Debug)]
+    #[must_use = "dropping the guard deregisters the event source"]
+    pub struct RegistrationGuard<'a, S: event::Source + ?Sized> {
+        registry: &'a Registry,
+        source: &'a mut S,
+    }
+
+    impl<'a, S: event::Source + ?Sized> RegistrationGuard<'a, S> {
+        /// Re-register the event source, see [`Registry::reregister`].
+        ///
+        /// [`Registry::reregister`]: struct.Registry.html#method.reregister
+        pub fn reregister(&mut self, token: Token, interests: Interest) -> io::Result<()> {
+            self.registry.reregister(self.source, token, interests)
+        }
+
+        /// Drop the guard **without** deregistering the event source.
+        ///
+        /// The source will remain registered and must be deregistered manually,
+        /// e.g. when the ownership of the source is handed to other code.
+        pub fn forget(self) {
+            std::mem::forget(self);
+        }
+    }
+
+    impl<'a, S: event::Source + ?Sized> Deref for RegistrationGuard<'a, S> {
+        type Target = S;
+
+        fn deref(&self) -> &S {
+            self.source
+        }
+    }
+
+    impl<'a, S: event::Source + ?Sized> DerefMut for RegistrationGuard<'a, S> {
+        fn deref_mut(&mut self) -> &mut S {
+            self.source
+        }
+    }
+
+    impl<'a, S: event::Source + ?Sized> Drop for RegistrationGuard<'a, S> {
+        fn drop(&mut self) {
+            match self.registry.deregister(self.source) {
+                Ok(()) => {}
+                // Already deregistered.
+                Err(ref err) if err.kind() == io::ErrorKind::NotFound => {}
+                // Nothing to deregister from once the poll instance is closed.
+                Err(_) if self.registry.closed.load(Ordering::Acquire) => {}
+                Err(err) => error!("error deregistering event source: {}", err),
+            }
+        }
+    }
+}
+
 impl fmt::Debug for Registry {
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
         fmt.debug_struct("Registry").finish()
@@ -643,6 +1485,57 @@ impl AsRawFd for Registry {
     }
 }
 
+/// Token of the waker used by [`Registry::shutdown`]. Its events are never
+/// returned (`poll` returns `shutdown_error` instead), so it can't be confused
+/// with a user's token, as the token is reserved.
+const SHUTDOWN_TOKEN: Token = Token(usize::MAX);
+
+/// Token of the timer used by [`Poll::wake_after`]. Its event is removed
+/// before returning the events. As the token is reserved no event source can
+/// use it, so this doesn't remove any other events.
+#[cfg(all(feature = "os-poll", any(target_os = "linux", target_os = "android")))]
+const WAKE_TIMER_TOKEN: Token = Token(usize::MAX);
+
+/// Error returned by [`Poll::poll`] after [`Registry::shutdown`] is called.
+// `io::Error::other` isn't available on our MSRV.
+#[allow(unknown_lints, clippy::io_other_error)]
+fn shutdown_error() -> io::Error {
+    io::Error::new(io::ErrorKind::Other, "poll instance shut down")
+}
+
+/// Returns an `InvalidInput` error if `token` is reserved.
+pub(crate) fn check_token(token: Token) -> io::Result<()> {
+    if token.is_reserved() {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "token is reserved for internal use",
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Returns an `InvalidInput` error if `token` is reserved or if `interests`
+/// doesn't contain any readiness, e.g. only `Interest::ONESHOT`.
+fn check_registration(token: Token, interests: Interest) -> io::Result<()> {
+    check_token(token)?;
+    if interests.has_readiness() {
+        Ok(())
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "interests don't contain any readiness",
+        ))
+    }
+}
+
+/// Returns the timeout to use to not block past `deadline`, respecting the
+/// (optional) `timeout` if it's shorter.
+pub(crate) fn timeout_until(deadline: Instant, timeout: Option<Duration>) -> Option<Duration> {
+    let remaining = crate::timers::poll_timeout(std::iter::once(deadline), Instant::now())?;
+    Some(timeout.map_or(remaining, |timeout| timeout.min(remaining)))
+}
+
 /// Get access to the `sys::Selector` from `Registry`.
 pub(crate) fn selector(registry: &Registry) -> &sys::Selector {
     &registry.selector
@@ -655,4 +1548,18 @@ cfg_os_poll! {
         let poll = Poll::new().unwrap();
         assert!(poll.as_raw_fd() > 0);
     }
+
+    #[cfg(all(debug_assertions, any(target_os = "linux", target_os = "android")))]
+    #[test]
+    pub fn wake_after_timer() {
+        let mut poll = Poll::new().unwrap();
+        let mut events = Events::with_capacity(8);
+
+        poll.wake_after(Duration::from_millis(20));
+        poll.poll(&mut events, None).unwrap();
+        assert!(events.deadline_expired());
+        assert!(events.is_empty());
+        // Woken up by the timer, not by limiting the timeout.
+        assert_eq!(poll.registry.selector.last_timeout(), Some(-1));
+    }
 }
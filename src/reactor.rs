@@ -0,0 +1,680 @@
+//! A small event loop on top of [`Poll`].
+//!
+//! See [`Reactor`] for documentation.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use std::{fmt, io, mem};
+
+use log::debug;
+
+use crate::event::{Event, Source};
+use crate::timers::{TimerHandle, TimerQueue};
+use crate::{poll, Events, Interest, Poll, Registry, Token, Waker};
+
+/// Token of the [`Waker`] returned by [`Reactor::waker`]. Slab indices never
+/// get this large, so it's never used for an event source.
+const WAKER_TOKEN: Token = Token::MAX_USABLE;
+
+/// Handles readiness events for a single event source in a [`Reactor`].
+///
+/// The handler is stored alongside its event source, its `ready` method is
+/// called with the source each time the source is ready or [woken].
+///
+/// [woken]: Control::wake
+pub trait Handler: Sized {
+    /// The type of event source the handler is registered with.
+    type Source: Source;
+
+    /// Called when `source` is ready.
+    ///
+    /// `control` can be used to change or remove the registration, add new
+    /// event sources to the reactor and wake other handlers.
+    fn ready(
+        &mut self,
+        source: &mut Self::Source,
+        readiness: Readiness,
+        control: &mut Control<'_, Self>,
+    );
+}
+
+/// An event loop that routes readiness events to [`Handler`]s.
+///
+/// The `Reactor` owns a [`Poll`] instance and all event sources registered
+/// with it. It takes care of allocating a [`Token`] for every event source
+/// and calls the [`Handler`] registered with the source for each event.
+///
+/// Event sources are identified by a [`Key`]. Keys are never reused, after an
+/// event source is removed its key becomes invalid. Within a single call to
+/// [`Reactor::run_once`] tokens are not reused either, so events for an event
+/// source that was removed (e.g. by an earlier handler) are never delivered
+/// to a different event source.
+///
+/// # Idle timeouts
+///
+/// An event source can be given an [idle timeout]. If the handler isn't called
+/// for the event source within the timeout, the handler is called one last
+/// time with [timed out] readiness, after which the event source is
+/// deregistered and both the source and the handler are dropped.
+///
+/// [idle timeout]: Reactor::set_idle_timeout
+/// [timed out]: Readiness::is_timed_out
+///
+/// # Examples
+///
+#[cfg_attr(all(unix, feature = "os-ext"), doc = "```")]
+#[cfg_attr(not(all(unix, feature = "os-ext")), doc = "```ignore")]
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use std::io::{Read, Write};
+/// use std::time::Duration;
+///
+/// use mio::Interest;
+/// use mio::reactor::{Control, Handler, Reactor, Readiness};
+/// use mio::unix::pipe;
+///
+/// struct Print;
+///
+/// impl Handler for Print {
+///     type Source = pipe::Receiver;
+///
+///     fn ready(
+///         &mut self,
+///         receiver: &mut pipe::Receiver,
+///         _: Readiness,
+///         control: &mut Control<'_, Self>,
+///     ) {
+///         let mut buf = [0; 64];
+///         if let Ok(n) = receiver.read(&mut buf) {
+///             println!("read: {:?}", &buf[..n]);
+///         }
+///         // We're only interested in a single message.
+///         control.remove();
+///     }
+/// }
+///
+/// let mut reactor = Reactor::new()?;
+/// let (mut sender, receiver) = pipe::new()?;
+/// reactor.insert(receiver, Interest::READABLE, Print)?;
+///
+/// sender.write_all(b"Hello")?;
+/// reactor.run_once(Some(Duration::from_secs(1)))?;
+/// assert!(reactor.is_empty());
+/// #     Ok(())
+/// # }
+/// ```
+pub struct Reactor<H: Handler> {
+    poll: Poll,
+    events: Events,
+    slab: Slab<H>,
+    /// Handlers to wake in the next call to `run_once`.
+    woken: Vec<Key>,
+    /// Tokens of expired idle timeouts, reused between calls to `run_once`.
+    expired: Vec<Token>,
+    /// Waker returned by `waker`, created on first use.
+    waker: Option<Arc<Waker>>,
+}
+
+/// Identifies an event source in a [`Reactor`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Key {
+    index: usize,
+    generation: u64,
+}
+
+/// Readiness passed to [`Handler::ready`].
+///
+/// This is a copy of the readiness of an [`Event`], see its methods for the
+/// meaning of each kind of readiness. Additionally a handler can be [woken]
+/// without its event source being ready.
+///
+/// [woken]: Readiness::is_woken
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct Readiness(u8);
+
+const READABLE: u8 = 0b0000_0001;
+const WRITABLE: u8 = 0b0000_0010;
+const ERROR: u8 = 0b0000_0100;
+const READ_CLOSED: u8 = 0b0000_1000;
+const WRITE_CLOSED: u8 = 0b0001_0000;
+const WOKEN: u8 = 0b0010_0000;
+const TIMED_OUT: u8 = 0b0100_0000;
+
+impl Readiness {
+    fn from_event(event: &Event) -> Readiness {
+        let mut readiness = 0;
+        if event.is_readable() {
+            readiness |= READABLE;
+        }
+        if event.is_writable() {
+            readiness |= WRITABLE;
+        }
+        if event.is_error() {
+            readiness |= ERROR;
+        }
+        if event.is_read_closed() {
+            readiness |= READ_CLOSED;
+        }
+        if event.is_write_closed() {
+            readiness |= WRITE_CLOSED;
+        }
+        Readiness(readiness)
+    }
+
+    /// See [`Event::is_readable`].
+    pub fn is_readable(self) -> bool {
+        self.0 & READABLE != 0
+    }
+
+    /// See [`Event::is_writable`].
+    pub fn is_writable(self) -> bool {
+        self.0 & WRITABLE != 0
+    }
+
+    /// See [`Event::is_error`].
+    pub fn is_error(self) -> bool {
+        self.0 & ERROR != 0
+    }
+
+    /// See [`Event::is_read_closed`].
+    pub fn is_read_closed(self) -> bool {
+        self.0 & READ_CLOSED != 0
+    }
+
+    /// See [`Event::is_write_closed`].
+    pub fn is_write_closed(self) -> bool {
+        self.0 & WRITE_CLOSED != 0
+    }
+
+    /// Returns true if the handler was woken using [`Control::wake`].
+    ///
+    /// A woken handler is not ready for any I/O, unless one of the other
+    /// methods returns true.
+    pub fn is_woken(self) -> bool {
+        self.0 & WOKEN != 0
+    }
+
+    /// Returns true if the [idle timeout] of the event source expired.
+    ///
+    /// This is the last time the handler is called, the event source is
+    /// removed once the handler returns.
+    ///
+    /// [idle timeout]: Reactor::set_idle_timeout
+    pub fn is_timed_out(self) -> bool {
+        self.0 & TIMED_OUT != 0
+    }
+}
+
+impl fmt::Debug for Readiness {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Readiness")
+            .field("readable", &self.is_readable())
+            .field("writable", &self.is_writable())
+            .field("error", &self.is_error())
+            .field("read_closed", &self.is_read_closed())
+            .field("write_closed", &self.is_write_closed())
+            .field("woken", &self.is_woken())
+            .field("timed_out", &self.is_timed_out())
+            .finish()
+    }
+}
+
+impl<H: Handler> fmt::Debug for Reactor<H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Reactor")
+            .field("poll", &self.poll)
+            .field("events", &self.events)
+            .field("len", &self.slab.len)
+            .field("woken", &self.woken)
+            .finish()
+    }
+}
+
+impl<H: Handler> Reactor<H> {
+    /// Create a new `Reactor`, receiving at most 1024 events per call to
+    /// [`Reactor::run_once`].
+    pub fn new() -> io::Result<Reactor<H>> {
+        Reactor::with_capacity(1024)
+    }
+
+    /// Create a new `Reactor`, receiving at most `capacity` events per call
+    /// to [`Reactor::run_once`].
+    pub fn with_capacity(capacity: usize) -> io::Result<Reactor<H>> {
+        Poll::new().map(|poll| Reactor {
+            poll,
+            events: Events::with_capacity(capacity),
+            slab: Slab::new(),
+            woken: Vec::new(),
+            expired: Vec::new(),
+            waker: None,
+        })
+    }
+
+    /// Returns the [`Registry`] of the underlying [`Poll`] instance.
+    ///
+    /// To wake the reactor from another thread use [`Reactor::waker`], rather
+    /// than creating a [`Waker`] using this registry: only a single `Waker`
+    /// can be active per `Poll` instance.
+    pub fn registry(&self) -> &Registry {
+        self.poll.registry()
+    }
+
+    /// Returns a [`Waker`] that wakes up the reactor, e.g. from another
+    /// thread.
+    ///
+    /// The waker is created by the first call, all calls return the same
+    /// waker. Wake ups don't call any handler, but make the current (or next)
+    /// call to [`Reactor::run_once`] return.
+    pub fn waker(&mut self) -> io::Result<Arc<Waker>> {
+        if let Some(waker) = &self.waker {
+            return Ok(waker.clone());
+        }
+        let waker = Arc::new(Waker::new(self.poll.registry(), WAKER_TOKEN)?);
+        self.waker = Some(waker.clone());
+        Ok(waker)
+    }
+
+    /// Returns the number of event sources in the reactor.
+    pub fn len(&self) -> usize {
+        self.slab.len
+    }
+
+    /// Returns `true` if the reactor holds no event sources.
+    pub fn is_empty(&self) -> bool {
+        self.slab.len == 0
+    }
+
+    /// Register `source` with `interests` and `handler`.
+    pub fn insert(
+        &mut self,
+        source: H::Source,
+        interests: Interest,
+        handler: H,
+    ) -> io::Result<Key> {
+        insert(
+            &mut self.slab,
+            self.poll.registry(),
+            source,
+            interests,
+            handler,
+        )
+    }
+
+    /// Change the interests of the event source identified by `key`.
+    ///
+    /// Returns a `NotFound` error if `key` is invalid.
+    pub fn modify(&mut self, key: Key, interests: Interest) -> io::Result<()> {
+        match self.slab.get_mut(key) {
+            Some(entry) => {
+                self.poll
+                    .registry()
+                    .reregister(&mut entry.0, Token(key.index), interests)
+            }
+            None => Err(not_found()),
+        }
+    }
+
+    /// Deregister and remove the event source identified by `key`, returning
+    /// it along with its handler.
+    ///
+    /// Returns `None` if `key` is invalid.
+    pub fn remove(&mut self, key: Key) -> Option<(H::Source, H)> {
+        let mut entry = self.slab.take(key)?;
+        self.slab.release(key.index);
+        self.slab.free_pending();
+        if let Err(err) = self.poll.registry().deregister(&mut entry.0) {
+            debug!("error deregistering event source: {}", err);
+        }
+        Some(entry)
+    }
+
+    /// Set the idle timeout of the event source identified by `key`, see
+    /// [idle timeouts]. `None` removes the timeout.
+    ///
+    /// The timeout is restarted now and every time the handler is called.
+    ///
+    /// Returns a `NotFound` error if `key` is invalid.
+    ///
+    /// [idle timeouts]: Reactor#idle-timeouts
+    pub fn set_idle_timeout(&mut self, key: Key, timeout: Option<Duration>) -> io::Result<()> {
+        if self.slab.get_mut(key).is_none() {
+            return Err(not_found());
+        }
+        self.slab.set_timeout(key.index, timeout, Instant::now());
+        Ok(())
+    }
+
+    /// Returns the event source and handler identified by `key`.
+    pub fn get_mut(&mut self, key: Key) -> Option<(&mut H::Source, &mut H)> {
+        self.slab
+            .get_mut(key)
+            .map(|entry| (&mut entry.0, &mut entry.1))
+    }
+
+    /// Wait for readiness events, calling the handler of each ready event
+    /// source.
+    ///
+    /// `timeout` is used as in [`Poll::poll`], but this doesn't block if a
+    /// handler was [woken] before, or past the first [idle timeout].
+    ///
+    /// Returns the number of handlers called.
+    ///
+    /// [woken]: Control::wake
+    /// [idle timeout]: Reactor::set_idle_timeout
+    pub fn run_once(&mut self, timeout: Option<Duration>) -> io::Result<usize> {
+        let timeout = if !self.woken.is_empty() {
+            Some(Duration::from_millis(0))
+        } else if let Some(deadline) = self.slab.timers.next_deadline() {
+            poll::timeout_until(deadline, timeout)
+        } else {
+            timeout
+        };
+        self.poll.poll(&mut self.events, timeout)?;
+
+        let Reactor {
+            poll,
+            events,
+            slab,
+            woken,
+            expired,
+            ..
+        } = self;
+        let registry = poll.registry();
+        let now = Instant::now();
+        let mut called = 0;
+
+        // Handlers woken during this call are called in the next call.
+        for key in mem::take(woken) {
+            if dispatch(slab, registry, woken, key, Readiness(WOKEN), now) {
+                called += 1;
+            }
+        }
+
+        for event in events.iter() {
+            let index = event.token().0;
+            let key = match slab.key(index) {
+                Some(key) => key,
+                // Removed by an earlier handler, or the token of a `Waker`.
+                None => continue,
+            };
+            if dispatch(
+                slab,
+                registry,
+                woken,
+                key,
+                Readiness::from_event(event),
+                now,
+            ) {
+                called += 1;
+            }
+        }
+
+        // Calling a handler restarts its idle timeout, so this only includes
+        // event sources that didn't get an event above.
+        expired.clear();
+        expired.extend(slab.timers.expired(Instant::now()));
+        for token in expired.iter() {
+            let key = match slab.key(token.0) {
+                Some(key) => key,
+                None => continue,
+            };
+            // The timer already expired, so don't cancel it when removing it.
+            slab.slots[key.index].timeout = None;
+            if dispatch(slab, registry, woken, key, Readiness(TIMED_OUT), now) {
+                called += 1;
+            }
+        }
+
+        // Only now allow the slots of removed event sources to be reused, see
+        // the type documentation.
+        slab.free_pending();
+        Ok(called)
+    }
+}
+
+/// Calls the handler identified by `key`, returns false if `key` is invalid.
+fn dispatch<H: Handler>(
+    slab: &mut Slab<H>,
+    registry: &Registry,
+    woken: &mut Vec<Key>,
+    key: Key,
+    readiness: Readiness,
+    now: Instant,
+) -> bool {
+    // Take the entry out of the slab, allowing the handler to insert new
+    // sources using `Control`.
+    let (mut source, mut handler) = match slab.take(key) {
+        Some(entry) => entry,
+        None => return false,
+    };
+
+    let mut control = Control {
+        key,
+        registry,
+        slab,
+        woken,
+        remove: false,
+        idle_timeout: None,
+    };
+    handler.ready(&mut source, readiness, &mut control);
+
+    if control.remove || readiness.is_timed_out() {
+        control.slab.release(key.index);
+        if let Err(err) = registry.deregister(&mut source) {
+            debug!("error deregistering event source: {}", err);
+        }
+    } else {
+        let slab = control.slab;
+        let timeout = match control.idle_timeout {
+            Some(timeout) => timeout,
+            None => slab.slots[key.index]
+                .timeout
+                .as_ref()
+                .map(|timeout| timeout.duration),
+        };
+        slab.set_timeout(key.index, timeout, now);
+        slab.put(key.index, (source, handler));
+    }
+    true
+}
+
+fn insert<H: Handler>(
+    slab: &mut Slab<H>,
+    registry: &Registry,
+    mut source: H::Source,
+    interests: Interest,
+    handler: H,
+) -> io::Result<Key> {
+    let key = slab.vacant_key();
+    if let Err(err) = registry.register(&mut source, Token(key.index), interests) {
+        slab.free.push(key.index);
+        return Err(err);
+    }
+    slab.put(key.index, (source, handler));
+    slab.len += 1;
+    Ok(key)
+}
+
+fn not_found() -> io::Error {
+    io::Error::new(io::ErrorKind::NotFound, "invalid reactor key")
+}
+
+/// Passed to [`Handler::ready`] to control the [`Reactor`].
+pub struct Control<'a, H: Handler> {
+    key: Key,
+    registry: &'a Registry,
+    slab: &'a mut Slab<H>,
+    woken: &'a mut Vec<Key>,
+    remove: bool,
+    /// `Some` if the idle timeout should be changed.
+    idle_timeout: Option<Option<Duration>>,
+}
+
+impl<'a, H: Handler> fmt::Debug for Control<'a, H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Control")
+            .field("key", &self.key)
+            .field("remove", &self.remove)
+            .finish()
+    }
+}
+
+impl<'a, H: Handler> Control<'a, H> {
+    /// Returns the key of the event source the handler is called for.
+    pub fn key(&self) -> Key {
+        self.key
+    }
+
+    /// Change the interests of `source`, the event source the handler is
+    /// called for.
+    pub fn reregister(&self, source: &mut H::Source, interests: Interest) -> io::Result<()> {
+        self.registry
+            .reregister(source, Token(self.key.index), interests)
+    }
+
+    /// Remove the event source the handler is called for, once the handler
+    /// returns. Both the event source and the handler are dropped.
+    ///
+    /// Events for the event source not yet delivered are discarded.
+    pub fn remove(&mut self) {
+        self.remove = true;
+    }
+
+    /// Set the idle timeout of the event source the handler is called for,
+    /// see [`Reactor::set_idle_timeout`].
+    pub fn set_idle_timeout(&mut self, timeout: Option<Duration>) {
+        self.idle_timeout = Some(timeout);
+    }
+
+    /// Register a new event source with the reactor, see
+    /// [`Reactor::insert`].
+    ///
+    /// The handler of the new event source is only called once the source is
+    /// ready in a next call to [`Reactor::run_once`].
+    pub fn insert(
+        &mut self,
+        source: H::Source,
+        interests: Interest,
+        handler: H,
+    ) -> io::Result<Key> {
+        insert(self.slab, self.registry, source, interests, handler)
+    }
+
+    /// Wake the handler identified by `key` in the next call to
+    /// [`Reactor::run_once`], even if its event source isn't ready.
+    ///
+    /// Waking the handler currently being called is allowed.
+    pub fn wake(&mut self, key: Key) {
+        self.woken.push(key);
+    }
+}
+
+/// Storage for the event sources and handlers.
+struct Slab<H: Handler> {
+    slots: Vec<Slot<H>>,
+    /// Indices into `slots` that can be reused.
+    free: Vec<usize>,
+    /// Indices into `slots` that can be reused after the current call to
+    /// `run_once`.
+    pending: Vec<usize>,
+    /// Number of event sources.
+    len: usize,
+    /// Idle timeouts, using the index of the slot as token.
+    timers: TimerQueue,
+}
+
+struct Slot<H: Handler> {
+    /// Incremented every time an entry is removed from this slot.
+    generation: u64,
+    /// `None` if the slot is free or the entry is currently being used by
+    /// `dispatch`.
+    entry: Option<(H::Source, H)>,
+    timeout: Option<Timeout>,
+}
+
+struct Timeout {
+    duration: Duration,
+    timer: TimerHandle,
+}
+
+impl<H: Handler> Slab<H> {
+    fn new() -> Slab<H> {
+        Slab {
+            slots: Vec::new(),
+            free: Vec::new(),
+            pending: Vec::new(),
+            len: 0,
+            timers: TimerQueue::new(),
+        }
+    }
+
+    /// Returns the key of the slot at `index`.
+    fn key(&self, index: usize) -> Option<Key> {
+        self.slots.get(index).map(|slot| Key {
+            index,
+            generation: slot.generation,
+        })
+    }
+
+    /// Returns the key of a free slot, the slot must be filled using `put`.
+    fn vacant_key(&mut self) -> Key {
+        let index = match self.free.pop() {
+            Some(index) => index,
+            None => {
+                self.slots.push(Slot {
+                    generation: 0,
+                    entry: None,
+                    timeout: None,
+                });
+                self.slots.len() - 1
+            }
+        };
+        Key {
+            index,
+            generation: self.slots[index].generation,
+        }
+    }
+
+    fn get_mut(&mut self, key: Key) -> Option<&mut (H::Source, H)> {
+        match self.slots.get_mut(key.index) {
+            Some(slot) if slot.generation == key.generation => slot.entry.as_mut(),
+            _ => None,
+        }
+    }
+
+    fn take(&mut self, key: Key) -> Option<(H::Source, H)> {
+        match self.slots.get_mut(key.index) {
+            Some(slot) if slot.generation == key.generation => slot.entry.take(),
+            _ => None,
+        }
+    }
+
+    fn put(&mut self, index: usize, entry: (H::Source, H)) {
+        self.slots[index].entry = Some(entry);
+    }
+
+    /// Replace the idle timeout of the slot at `index`, starting at `now`.
+    fn set_timeout(&mut self, index: usize, duration: Option<Duration>, now: Instant) {
+        if let Some(timeout) = self.slots[index].timeout.take() {
+            let _ = self.timers.cancel(timeout.timer);
+        }
+        self.slots[index].timeout = duration.map(|duration| Timeout {
+            duration,
+            timer: self.timers.add(now + duration, Token(index)),
+        });
+    }
+
+    /// Mark the (empty) slot at `index` as unused, invalidating its key.
+    fn release(&mut self, index: usize) {
+        if let Some(timeout) = self.slots[index].timeout.take() {
+            let _ = self.timers.cancel(timeout.timer);
+        }
+        self.slots[index].generation += 1;
+        self.pending.push(index);
+        self.len -= 1;
+    }
+
+    fn free_pending(&mut self) {
+        self.free.append(&mut self.pending);
+    }
+}
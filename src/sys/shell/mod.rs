@@ -14,6 +14,8 @@ cfg_net! {
     pub(crate) mod tcp;
     pub(crate) mod udp;
     #[cfg(unix)]
+    pub(crate) mod net;
+    #[cfg(unix)]
     pub(crate) mod uds;
 }
 
@@ -0,0 +1,31 @@
+use crate::net::{Family, Kind};
+use std::io;
+use std::os::unix::io::RawFd;
+
+pub(crate) fn socket_family(_: RawFd) -> io::Result<Family> {
+    os_required!()
+}
+
+pub(crate) fn socket_type(_: RawFd) -> io::Result<Kind> {
+    os_required!()
+}
+
+pub(crate) fn is_listening(_: RawFd) -> io::Result<bool> {
+    os_required!()
+}
+
+pub(crate) fn set_recv_low_water_mark(_: RawFd, _: usize) -> io::Result<()> {
+    os_required!()
+}
+
+pub(crate) fn recv_low_water_mark(_: RawFd) -> io::Result<usize> {
+    os_required!()
+}
+
+pub(crate) fn set_send_low_water_mark(_: RawFd, _: usize) -> io::Result<()> {
+    os_required!()
+}
+
+pub(crate) fn send_low_water_mark(_: RawFd) -> io::Result<usize> {
+    os_required!()
+}
@@ -1,8 +1,12 @@
 use std::io;
 #[cfg(unix)]
+use std::mem::MaybeUninit;
+#[cfg(unix)]
 use std::os::unix::io::{AsRawFd, RawFd};
 use std::time::Duration;
 
+use crate::features::{Capabilities, Triggering};
+
 pub type Event = usize;
 
 pub type Events = Vec<Event>;
@@ -11,6 +15,14 @@ pub type Events = Vec<Event>;
 pub struct Selector {}
 
 impl Selector {
+    pub const CAPABILITIES: Capabilities = Capabilities {
+        triggering: Triggering::Edge,
+        oneshot: false,
+        waker: false,
+        priority: false,
+        regular_files: false,
+    };
+
     pub fn try_clone(&self) -> io::Result<Selector> {
         os_required!();
     }
@@ -19,6 +31,11 @@ impl Selector {
         os_required!();
     }
 
+    #[cfg(unix)]
+    pub fn select_into(&self, _: &mut [MaybeUninit<Event>], _: Option<Duration>) -> io::Result<usize> {
+        os_required!();
+    }
+
     #[cfg(debug_assertions)]
     pub fn register_waker(&self) -> bool {
         os_required!();
@@ -94,6 +111,10 @@ pub mod event {
         os_required!();
     }
 
+    pub fn is_band_writable(_: &Event) -> bool {
+        os_required!();
+    }
+
     pub fn is_aio(_: &Event) -> bool {
         os_required!();
     }
@@ -125,3 +125,7 @@ pub fn accept(_: &net::TcpListener) -> io::Result<(net::TcpStream, SocketAddr)>
 pub(crate) fn get_localaddr(_: TcpSocket) -> io::Result<SocketAddr> {
     os_required!();
 }
+
+pub(crate) fn bytes_available(_: &net::TcpStream) -> io::Result<usize> {
+    os_required!();
+}
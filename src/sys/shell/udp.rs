@@ -1,4 +1,6 @@
 use std::io;
+#[cfg(unix)]
+use std::io::{IoSlice, IoSliceMut};
 use std::net::{self, SocketAddr};
 
 pub fn bind(_: SocketAddr) -> io::Result<net::UdpSocket> {
@@ -8,3 +10,47 @@ pub fn bind(_: SocketAddr) -> io::Result<net::UdpSocket> {
 pub(crate) fn only_v6(_: &net::UdpSocket) -> io::Result<bool> {
     os_required!()
 }
+
+pub(crate) fn bytes_available(_: &net::UdpSocket) -> io::Result<usize> {
+    os_required!()
+}
+
+#[cfg(unix)]
+pub(crate) fn send_to_vectored(
+    _: &net::UdpSocket,
+    _: &[IoSlice<'_>],
+    _: SocketAddr,
+) -> io::Result<usize> {
+    os_required!()
+}
+
+#[cfg(unix)]
+pub(crate) fn recv_from_vectored(
+    _: &net::UdpSocket,
+    _: &mut [IoSliceMut<'_>],
+) -> io::Result<(usize, SocketAddr)> {
+    os_required!()
+}
+
+#[cfg(unix)]
+pub(crate) fn send_vectored(_: &net::UdpSocket, _: &[IoSlice<'_>]) -> io::Result<usize> {
+    os_required!()
+}
+
+#[cfg(unix)]
+pub(crate) fn recv_vectored(_: &net::UdpSocket, _: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+    os_required!()
+}
+
+#[cfg(unix)]
+pub(crate) fn recv_from_full(
+    _: &net::UdpSocket,
+    _: &mut [u8],
+) -> io::Result<(usize, bool, SocketAddr)> {
+    os_required!()
+}
+
+#[cfg(unix)]
+pub(crate) fn recv_full(_: &net::UdpSocket, _: &mut [u8]) -> io::Result<(usize, bool)> {
+    os_required!()
+}
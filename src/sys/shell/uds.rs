@@ -1,6 +1,6 @@
 pub(crate) mod datagram {
-    use crate::net::SocketAddr;
-    use std::io;
+    use crate::net::{BindOptions, SocketAddr};
+    use std::io::{self, IoSlice, IoSliceMut};
     use std::os::unix::net;
     use std::path::Path;
 
@@ -8,6 +8,10 @@ pub(crate) mod datagram {
         os_required!()
     }
 
+    pub(crate) fn bind_with(_: &Path, _: &BindOptions) -> io::Result<net::UnixDatagram> {
+        os_required!()
+    }
+
     pub(crate) fn unbound() -> io::Result<net::UnixDatagram> {
         os_required!()
     }
@@ -30,10 +34,51 @@ pub(crate) mod datagram {
     ) -> io::Result<(usize, SocketAddr)> {
         os_required!()
     }
+
+    pub(crate) fn bytes_available(_: &net::UnixDatagram) -> io::Result<usize> {
+        os_required!()
+    }
+
+    pub(crate) fn send_to_vectored(
+        _: &net::UnixDatagram,
+        _: &[IoSlice<'_>],
+        _: &Path,
+    ) -> io::Result<usize> {
+        os_required!()
+    }
+
+    pub(crate) fn recv_from_vectored(
+        _: &net::UnixDatagram,
+        _: &mut [IoSliceMut<'_>],
+    ) -> io::Result<(usize, SocketAddr)> {
+        os_required!()
+    }
+
+    pub(crate) fn send_vectored(_: &net::UnixDatagram, _: &[IoSlice<'_>]) -> io::Result<usize> {
+        os_required!()
+    }
+
+    pub(crate) fn recv_vectored(
+        _: &net::UnixDatagram,
+        _: &mut [IoSliceMut<'_>],
+    ) -> io::Result<usize> {
+        os_required!()
+    }
+
+    pub(crate) fn recv_from_full(
+        _: &net::UnixDatagram,
+        _: &mut [u8],
+    ) -> io::Result<(usize, bool, SocketAddr)> {
+        os_required!()
+    }
+
+    pub(crate) fn recv_full(_: &net::UnixDatagram, _: &mut [u8]) -> io::Result<(usize, bool)> {
+        os_required!()
+    }
 }
 
 pub(crate) mod listener {
-    use crate::net::{SocketAddr, UnixStream};
+    use crate::net::{BindOptions, SocketAddr, UnixStream};
     use std::io;
     use std::os::unix::net;
     use std::path::Path;
@@ -42,6 +87,10 @@ pub(crate) mod listener {
         os_required!()
     }
 
+    pub(crate) fn bind_with(_: &Path, _: &BindOptions) -> io::Result<net::UnixListener> {
+        os_required!()
+    }
+
     pub(crate) fn accept(_: &net::UnixListener) -> io::Result<(UnixStream, SocketAddr)> {
         os_required!()
     }
@@ -72,4 +121,8 @@ pub(crate) mod stream {
     pub(crate) fn peer_addr(_: &net::UnixStream) -> io::Result<SocketAddr> {
         os_required!()
     }
+
+    pub(crate) fn bytes_available(_: &net::UnixStream) -> io::Result<usize> {
+        os_required!()
+    }
 }
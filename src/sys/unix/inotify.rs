@@ -0,0 +1,228 @@
+//! Linux inotify file descriptors.
+//!
+//! See the [`Inotify`] type for documentation.
+
+use std::ffi::{CString, OsStr};
+use std::fs::File;
+use std::io::{self, Read};
+use std::mem::size_of;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
+use std::path::Path;
+
+use crate::io_source::IoSource;
+use crate::{event, Interest, Registry, Token};
+
+/// A non-blocking inotify file descriptor, created using
+/// [`inotify_init1(2)`].
+///
+/// The file descriptor becomes readable when file system events are queued for
+/// one of its watches, so it can be registered with [`READABLE`] interest to
+/// handle them the same way as I/O. Watches are added using [`add_watch`], the
+/// queued events can be read using [`read_events`].
+///
+/// [`inotify_init1(2)`]: https://man7.org/linux/man-pages/man2/inotify_init1.2.html
+/// [`READABLE`]: Interest::READABLE
+/// [`add_watch`]: Inotify::add_watch
+/// [`read_events`]: Inotify::read_events
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io;
+///
+/// use mio::unix::inotify::Inotify;
+/// use mio::{Events, Interest, Poll, Token};
+///
+/// # fn main() -> io::Result<()> {
+/// let mut inotify = Inotify::new()?;
+/// inotify.add_watch("/tmp", libc::IN_CREATE | libc::IN_DELETE)?;
+///
+/// let mut poll = Poll::new()?;
+/// let mut events = Events::with_capacity(8);
+/// poll.registry().register(&mut inotify, Token(0), Interest::READABLE)?;
+///
+/// let mut buf = [0; 4096];
+/// loop {
+///     poll.poll(&mut events, None)?;
+///     loop {
+///         match inotify.read_events(&mut buf) {
+///             Ok(events) => {
+///                 for event in events {
+///                     println!("event {:#x} for {:?}", event.mask(), event.name());
+///                 }
+///             }
+///             Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => break,
+///             Err(err) => return Err(err),
+///         }
+///     }
+/// }
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Inotify {
+    inner: IoSource<File>,
+}
+
+impl Inotify {
+    /// Creates a new inotify file descriptor, without any watches.
+    ///
+    /// The file descriptor is in non-blocking mode and has the close-on-exec
+    /// flag set.
+    pub fn new() -> io::Result<Inotify> {
+        let fd = syscall!(inotify_init1(libc::IN_NONBLOCK | libc::IN_CLOEXEC))?;
+        // Safety: `inotify_init1` returned a new file descriptor.
+        Ok(unsafe { Inotify::from_raw_fd(fd) })
+    }
+
+    /// Adds a watch for the events in `mask` (`IN_*` constants, e.g.
+    /// `IN_CREATE`) on `path`, or updates the mask if `path` is already
+    /// watched.
+    pub fn add_watch<P: AsRef<Path>>(&self, path: P, mask: u32) -> io::Result<WatchDescriptor> {
+        let path = CString::new(path.as_ref().as_os_str().as_bytes())
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+        syscall!(inotify_add_watch(self.as_raw_fd(), path.as_ptr(), mask)).map(WatchDescriptor)
+    }
+
+    /// Removes the watch `wd`, this queues an `IN_IGNORED` event for it.
+    pub fn remove_watch(&self, wd: WatchDescriptor) -> io::Result<()> {
+        syscall!(inotify_rm_watch(self.as_raw_fd(), wd.0)).map(|_| ())
+    }
+
+    /// Reads the queued events into `buf`, returning an iterator over them.
+    ///
+    /// Returns a [`WouldBlock`] error if no events are queued. `buf` must be
+    /// large enough to hold at least a single event, including the name of
+    /// the file (up to `NAME_MAX` bytes), otherwise an `InvalidInput` error
+    /// is returned.
+    ///
+    /// [`WouldBlock`]: io::ErrorKind::WouldBlock
+    pub fn read_events<'a>(&self, buf: &'a mut [u8]) -> io::Result<InotifyEvents<'a>> {
+        let n = self.inner.do_io(|file| (&*file).read(buf))?;
+        Ok(InotifyEvents { buf: &buf[..n] })
+    }
+}
+
+impl event::Source for Inotify {
+    fn register(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        self.inner.register(registry, token, interests)
+    }
+
+    fn reregister(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        self.inner.reregister(registry, token, interests)
+    }
+
+    fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
+        self.inner.deregister(registry)
+    }
+}
+
+impl FromRawFd for Inotify {
+    unsafe fn from_raw_fd(fd: RawFd) -> Inotify {
+        Inotify {
+            inner: IoSource::new(File::from_raw_fd(fd)),
+        }
+    }
+}
+
+impl AsRawFd for Inotify {
+    fn as_raw_fd(&self) -> RawFd {
+        self.inner.as_raw_fd()
+    }
+}
+
+impl IntoRawFd for Inotify {
+    fn into_raw_fd(self) -> RawFd {
+        self.inner.into_inner().into_raw_fd()
+    }
+}
+
+/// Identifies a watch, returned by [`Inotify::add_watch`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct WatchDescriptor(libc::c_int);
+
+/// Iterator over the events returned by [`Inotify::read_events`].
+#[derive(Debug)]
+pub struct InotifyEvents<'a> {
+    buf: &'a [u8],
+}
+
+impl<'a> Iterator for InotifyEvents<'a> {
+    type Item = InotifyEvent<'a>;
+
+    fn next(&mut self) -> Option<InotifyEvent<'a>> {
+        // The kernel only returns whole `inotify_event` structures, but the
+        // buffer isn't necessarily aligned so the fields are copied out.
+        const HEADER: usize = size_of::<libc::inotify_event>();
+        if self.buf.len() < HEADER {
+            return None;
+        }
+        let field = |offset: usize| {
+            let mut bytes = [0; 4];
+            bytes.copy_from_slice(&self.buf[offset..offset + 4]);
+            bytes
+        };
+        let wd = libc::c_int::from_ne_bytes(field(0));
+        let mask = u32::from_ne_bytes(field(4));
+        let cookie = u32::from_ne_bytes(field(8));
+        let len = u32::from_ne_bytes(field(12)) as usize;
+        let end = (HEADER + len).min(self.buf.len());
+        // The name is padded with null bytes.
+        let name = &self.buf[HEADER..end];
+        let name = &name[..name.iter().position(|b| *b == 0).unwrap_or(name.len())];
+        self.buf = &self.buf[end..];
+        Some(InotifyEvent {
+            wd: WatchDescriptor(wd),
+            mask,
+            cookie,
+            name: if name.is_empty() {
+                None
+            } else {
+                Some(OsStr::from_bytes(name))
+            },
+        })
+    }
+}
+
+/// A file system event, returned by [`InotifyEvents`].
+#[derive(Debug)]
+pub struct InotifyEvent<'a> {
+    wd: WatchDescriptor,
+    mask: u32,
+    cookie: u32,
+    name: Option<&'a OsStr>,
+}
+
+impl<'a> InotifyEvent<'a> {
+    /// Returns the watch the event is for.
+    pub fn wd(&self) -> WatchDescriptor {
+        self.wd
+    }
+
+    /// Returns the `IN_*` mask describing the event, e.g. `IN_CREATE`.
+    pub fn mask(&self) -> u32 {
+        self.mask
+    }
+
+    /// Returns the cookie connecting related events, i.e. the
+    /// `IN_MOVED_FROM` and `IN_MOVED_TO` events of a rename.
+    pub fn cookie(&self) -> u32 {
+        self.cookie
+    }
+
+    /// Returns the name of the file within the watched directory, or `None`
+    /// if the event is for the watched file or directory itself.
+    pub fn name(&self) -> Option<&'a OsStr> {
+        self.name
+    }
+}
@@ -23,13 +23,21 @@ cfg_os_poll! {
     mod waker;
     pub(crate) use self::waker::Waker;
 
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    mod timerfd;
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub(crate) use self::timerfd::WakeTimer;
+
     cfg_net! {
-        mod net;
+        pub(crate) mod net;
 
         pub(crate) mod tcp;
         pub(crate) mod udp;
         pub(crate) mod uds;
         pub use self::uds::SocketAddr;
+
+        #[cfg(all(target_os = "linux", feature = "packet"))]
+        pub(crate) mod packet;
     }
 
     cfg_io_source! {
@@ -55,7 +63,14 @@ cfg_os_poll! {
     }
 
     cfg_os_ext! {
+        pub(crate) mod multi_poll;
         pub(crate) mod pipe;
+        #[cfg(target_os = "linux")]
+        pub(crate) mod inotify;
+        #[cfg(target_os = "linux")]
+        pub(crate) mod pidfd;
+        #[cfg(target_os = "linux")]
+        pub(crate) mod signalfd;
     }
 }
 
@@ -0,0 +1,198 @@
+//! Polling multiple `Poll` instances at once.
+//!
+//! See [`MultiPoll`] for documentation.
+
+use std::io;
+use std::os::unix::io::AsRawFd;
+use std::time::Duration;
+
+use crate::event::Event;
+use crate::unix::SourceFd;
+use crate::{Events, Interest, Poll, Registry, Token};
+
+/// Polls multiple [`Poll`] instances at once.
+///
+/// On Unix the system selector used by `Poll` (epoll or kqueue) is itself a
+/// file descriptor that becomes readable once it has pending events. This
+/// allows a `Poll` instance to be registered with another `Poll` instance,
+/// which is what `MultiPoll` does for all the children [added] to it. This can
+/// be used to build hierarchical reactors, e.g. a `Poll` instance per
+/// subsystem, without needing a thread per `Poll` instance.
+///
+/// [added]: MultiPoll::add
+///
+/// # System calls
+///
+/// Compared to using a single `Poll` instance using `MultiPoll` requires extra
+/// system calls. A call to [`MultiPoll::poll`] first polls the parent `Poll`
+/// instance, after which each child that is ready is polled (with a zero
+/// timeout). So polling `n` ready children requires `n + 1` system calls.
+///
+/// Because the children are registered using edge-triggered notifications a
+/// child that returns a full [`Events`] buffer is polled again on the next
+/// call to `poll`, even if the parent doesn't report it ready, as it might
+/// still have pending events.
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use std::io::Write;
+///
+/// use mio::{Interest, Poll, Token};
+/// use mio::unix::multi_poll::MultiPoll;
+/// use mio::unix::pipe;
+///
+/// let mut multi_poll = MultiPoll::new(8)?;
+/// let network = multi_poll.add(Poll::new()?, 128)?;
+/// let storage = multi_poll.add(Poll::new()?, 128)?;
+///
+/// let (mut sender, mut receiver) = pipe::new()?;
+/// multi_poll.registry(storage).unwrap()
+///     .register(&mut receiver, Token(0), Interest::READABLE)?;
+///
+/// sender.write(b"Hello")?;
+///
+/// multi_poll.poll(None)?;
+/// for (child, event) in multi_poll.iter() {
+///     assert_eq!(child, storage);
+///     assert_eq!(event.token(), Token(0));
+///     # let _ = network;
+/// }
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct MultiPoll {
+    parent: Poll,
+    events: Events,
+    children: Vec<Child>,
+}
+
+#[derive(Debug)]
+struct Child {
+    poll: Poll,
+    events: Events,
+    /// Whether or not the child needs to be polled.
+    ready: bool,
+}
+
+impl MultiPoll {
+    /// Create a new `MultiPoll`, without any children.
+    ///
+    /// `capacity` is the maximum number of children that can report readiness
+    /// in a single call to [`MultiPoll::poll`].
+    pub fn new(capacity: usize) -> io::Result<MultiPoll> {
+        Poll::new().map(|parent| MultiPoll {
+            parent,
+            events: Events::with_capacity(capacity),
+            children: Vec::new(),
+        })
+    }
+
+    /// Add a child `poll` instance.
+    ///
+    /// `capacity` is used to create an [`Events`] buffer for the child, see
+    /// [`Events::with_capacity`].
+    ///
+    /// Returns the index of the child, which is used in [`MultiPoll::iter`]
+    /// and [`MultiPoll::registry`].
+    pub fn add(&mut self, poll: Poll, capacity: usize) -> io::Result<usize> {
+        let index = self.children.len();
+        self.parent.registry().register(
+            &mut SourceFd(&poll.as_raw_fd()),
+            Token(index),
+            Interest::READABLE,
+        )?;
+        self.children.push(Child {
+            poll,
+            events: Events::with_capacity(capacity),
+            // Poll the child once, it could already have pending events.
+            ready: true,
+        });
+        Ok(index)
+    }
+
+    /// Returns the [`Registry`] of the child at `index`.
+    pub fn registry(&self, index: usize) -> Option<&Registry> {
+        self.children.get(index).map(|child| child.poll.registry())
+    }
+
+    /// Wait for readiness events on any of the children.
+    ///
+    /// This polls the parent with `timeout` (see [`Poll::poll`]), and then all
+    /// children that are ready. The events can be retrieved using
+    /// [`MultiPoll::iter`].
+    pub fn poll(&mut self, timeout: Option<Duration>) -> io::Result<()> {
+        for child in self.children.iter_mut() {
+            child.events.clear();
+        }
+
+        // Don't block if a child is already known to be ready.
+        let timeout = if self.children.iter().any(|child| child.ready) {
+            Some(Duration::from_millis(0))
+        } else {
+            timeout
+        };
+        self.parent.poll(&mut self.events, timeout)?;
+
+        for event in self.events.iter() {
+            if let Some(child) = self.children.get_mut(event.token().0) {
+                child.ready = true;
+            }
+        }
+
+        for child in self.children.iter_mut().filter(|child| child.ready) {
+            child
+                .poll
+                .poll(&mut child.events, Some(Duration::from_millis(0)))?;
+            // Because the children are edge-triggered we need to poll the child
+            // again if it filled the entire buffer.
+            child.ready = child.events.iter().count() == child.events.capacity();
+        }
+        Ok(())
+    }
+
+    /// Returns an iterator over the events received by the children in the
+    /// last call to [`MultiPoll::poll`].
+    ///
+    /// The iterator returns the child index along with the event.
+    pub fn iter(&self) -> Iter<'_> {
+        Iter {
+            children: &self.children,
+            child: 0,
+            inner: None,
+        }
+    }
+}
+
+/// [`MultiPoll`] events iterator.
+///
+/// This struct is created by the [`iter`] method on [`MultiPoll`].
+///
+/// [`iter`]: MultiPoll::iter
+#[derive(Debug, Clone)]
+pub struct Iter<'a> {
+    children: &'a [Child],
+    child: usize,
+    inner: Option<crate::event::Iter<'a>>,
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = (usize, &'a Event);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(inner) = self.inner.as_mut() {
+                if let Some(event) = inner.next() {
+                    return Some((self.child - 1, event));
+                }
+            }
+
+            let child = self.children.get(self.child)?;
+            self.inner = Some(child.events.iter());
+            self.child += 1;
+        }
+    }
+}
@@ -1,6 +1,10 @@
-use std::io;
+use crate::net::{Family, Kind};
+
+use std::cmp;
+use std::io::{self, IoSlice, IoSliceMut};
 use std::mem::size_of;
 use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+use std::os::unix::io::RawFd;
 
 pub(crate) fn new_ip_socket(addr: SocketAddr, socket_type: libc::c_int) -> io::Result<libc::c_int> {
     let domain = match addr {
@@ -167,3 +171,205 @@ pub(crate) unsafe fn to_socket_addr(
         _ => Err(io::ErrorKind::InvalidInput.into()),
     }
 }
+
+/// Returns the value of the integer socket option `opt` at the `SOL_SOCKET`
+/// level.
+fn get_socket_option(fd: RawFd, opt: libc::c_int) -> io::Result<libc::c_int> {
+    let mut optval: libc::c_int = 0;
+    let mut optlen = size_of::<libc::c_int>() as libc::socklen_t;
+    syscall!(getsockopt(
+        fd,
+        libc::SOL_SOCKET,
+        opt,
+        &mut optval as *mut _ as *mut _,
+        &mut optlen,
+    ))
+    .map(|_| optval)
+}
+
+/// Sets the value of the integer socket option `opt` at the `SOL_SOCKET`
+/// level.
+fn set_socket_option(fd: RawFd, opt: libc::c_int, optval: libc::c_int) -> io::Result<()> {
+    syscall!(setsockopt(
+        fd,
+        libc::SOL_SOCKET,
+        opt,
+        &optval as *const _ as *const libc::c_void,
+        size_of::<libc::c_int>() as libc::socklen_t,
+    ))
+    .map(|_| ())
+}
+
+/// Sets the `SO_RCVLOWAT` option of socket `fd`, clamping `bytes` to
+/// `c_int::MAX`.
+pub(crate) fn set_recv_low_water_mark(fd: RawFd, bytes: usize) -> io::Result<()> {
+    let bytes = cmp::min(bytes, libc::c_int::MAX as usize) as libc::c_int;
+    set_socket_option(fd, libc::SO_RCVLOWAT, bytes)
+}
+
+/// Returns the `SO_RCVLOWAT` option of socket `fd`.
+pub(crate) fn recv_low_water_mark(fd: RawFd) -> io::Result<usize> {
+    get_socket_option(fd, libc::SO_RCVLOWAT).map(|bytes| bytes as usize)
+}
+
+/// Sets the `SO_SNDLOWAT` option of socket `fd`, clamping `bytes` to
+/// `c_int::MAX`.
+pub(crate) fn set_send_low_water_mark(fd: RawFd, bytes: usize) -> io::Result<()> {
+    let bytes = cmp::min(bytes, libc::c_int::MAX as usize) as libc::c_int;
+    set_socket_option(fd, libc::SO_SNDLOWAT, bytes)
+}
+
+/// Returns the `SO_SNDLOWAT` option of socket `fd`.
+pub(crate) fn send_low_water_mark(fd: RawFd) -> io::Result<usize> {
+    get_socket_option(fd, libc::SO_SNDLOWAT).map(|bytes| bytes as usize)
+}
+
+/// Returns the address family of socket `fd`.
+pub(crate) fn socket_family(fd: RawFd) -> io::Result<Family> {
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    let domain = get_socket_option(fd, libc::SO_DOMAIN)?;
+
+    // Other platforms don't have `SO_DOMAIN`, but the address returned by
+    // `getsockname(2)` includes the family, even if the socket is unbound.
+    #[cfg(not(any(target_os = "android", target_os = "linux")))]
+    let domain = {
+        let mut storage: libc::sockaddr_storage = unsafe { std::mem::zeroed() };
+        let mut len = size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+        syscall!(getsockname(
+            fd,
+            &mut storage as *mut _ as *mut libc::sockaddr,
+            &mut len,
+        ))?;
+        libc::c_int::from(storage.ss_family)
+    };
+
+    Ok(match domain {
+        libc::AF_INET => Family::Ipv4,
+        libc::AF_INET6 => Family::Ipv6,
+        libc::AF_UNIX => Family::Unix,
+        #[cfg(any(target_os = "android", target_os = "linux"))]
+        libc::AF_VSOCK => Family::Vsock,
+        #[cfg(any(target_os = "android", target_os = "linux"))]
+        libc::AF_NETLINK => Family::Netlink,
+        domain => Family::Other(domain),
+    })
+}
+
+/// Returns the type of socket `fd`.
+pub(crate) fn socket_type(fd: RawFd) -> io::Result<Kind> {
+    Ok(match get_socket_option(fd, libc::SO_TYPE)? {
+        libc::SOCK_STREAM => Kind::Stream,
+        libc::SOCK_DGRAM => Kind::Datagram,
+        libc::SOCK_SEQPACKET => Kind::Seqpacket,
+        libc::SOCK_RAW => Kind::Raw,
+        kind => Kind::Other(kind),
+    })
+}
+
+/// Returns `true` if `listen(2)` was called on socket `fd`.
+pub(crate) fn is_listening(fd: RawFd) -> io::Result<bool> {
+    get_socket_option(fd, libc::SO_ACCEPTCONN).map(|accept| accept != 0)
+}
+
+/// Returns the number of bytes that can be read from socket `fd` without
+/// blocking, using `ioctl(FIONREAD)`.
+pub(crate) fn bytes_available(fd: RawFd) -> io::Result<usize> {
+    let mut available: libc::c_int = 0;
+    syscall!(ioctl(fd, libc::FIONREAD, &mut available)).map(|_| available as usize)
+}
+
+/// Maximum number of buffers passed to `sendmsg(2)` and `recvmsg(2)`, the
+/// value of `IOV_MAX` on all supported platforms.
+const IOV_MAX: usize = 1024;
+
+/// Sends `bufs` as a single datagram on socket `fd` using `sendmsg(2)`. If
+/// `addr` is null the datagram is sent to the connected peer.
+///
+/// Returns an `InvalidInput` error if `bufs` contains more than `IOV_MAX`
+/// buffers, as sending only some of them would send a truncated datagram.
+pub(crate) fn send_msg(
+    fd: RawFd,
+    bufs: &[IoSlice<'_>],
+    addr: *const libc::sockaddr,
+    addr_len: libc::socklen_t,
+) -> io::Result<usize> {
+    if bufs.len() > IOV_MAX {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "too many buffers, at most IOV_MAX can be sent in a single datagram",
+        ));
+    }
+
+    // Not all fields are public on all platforms, e.g. padding on musl.
+    let mut msg: libc::msghdr = unsafe { std::mem::zeroed() };
+    msg.msg_name = addr as *mut libc::c_void;
+    msg.msg_namelen = addr_len;
+    // `IoSlice` is guaranteed to be ABI compatible with `iovec`.
+    msg.msg_iov = bufs.as_ptr() as *mut libc::iovec;
+    msg.msg_iovlen = bufs.len() as _;
+
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    let flags = libc::MSG_NOSIGNAL;
+    #[cfg(not(any(target_os = "android", target_os = "linux")))]
+    let flags = 0;
+
+    syscall!(sendmsg(fd, &msg, flags)).map(|n| n as usize)
+}
+
+/// Receives a single datagram on socket `fd` into `bufs` using `recvmsg(2)`.
+/// If `addr` isn't null the source address is written to it, with `addr_len`
+/// updated to its length.
+///
+/// At most `IOV_MAX` buffers are used, like with a too small buffer the
+/// remainder of a datagram that doesn't fit is discarded.
+pub(crate) fn recv_msg(
+    fd: RawFd,
+    bufs: &mut [IoSliceMut<'_>],
+    addr: *mut libc::sockaddr,
+    addr_len: &mut libc::socklen_t,
+) -> io::Result<usize> {
+    recv_msg_flags(fd, bufs, addr, addr_len, 0).map(|(n, _)| n)
+}
+
+/// Receives a single datagram on socket `fd` into `buf`, like [`recv_msg`].
+/// Returns the length of the datagram and whether it was truncated.
+///
+/// On Linux and Android `MSG_TRUNC` is passed to get the real length of a
+/// truncated datagram, on other platforms the returned length is at most the
+/// length of `buf`.
+pub(crate) fn recv_msg_full(
+    fd: RawFd,
+    buf: &mut [u8],
+    addr: *mut libc::sockaddr,
+    addr_len: &mut libc::socklen_t,
+) -> io::Result<(usize, bool)> {
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    let flags = libc::MSG_TRUNC;
+    #[cfg(not(any(target_os = "android", target_os = "linux")))]
+    let flags = 0;
+
+    let (n, msg_flags) =
+        recv_msg_flags(fd, &mut [IoSliceMut::new(buf)], addr, addr_len, flags)?;
+    Ok((n, msg_flags & libc::MSG_TRUNC != 0))
+}
+
+/// Calls `recvmsg(2)` with `flags`, returning the number of bytes received and
+/// the flags set in `msg_flags`.
+fn recv_msg_flags(
+    fd: RawFd,
+    bufs: &mut [IoSliceMut<'_>],
+    addr: *mut libc::sockaddr,
+    addr_len: &mut libc::socklen_t,
+    flags: libc::c_int,
+) -> io::Result<(usize, libc::c_int)> {
+    let mut msg: libc::msghdr = unsafe { std::mem::zeroed() };
+    msg.msg_name = addr as *mut libc::c_void;
+    msg.msg_namelen = *addr_len;
+    // `IoSliceMut` is guaranteed to be ABI compatible with `iovec`.
+    msg.msg_iov = bufs.as_mut_ptr() as *mut libc::iovec;
+    msg.msg_iovlen = bufs.len().min(IOV_MAX) as _;
+
+    let n = syscall!(recvmsg(fd, &mut msg, flags))?;
+    *addr_len = msg.msg_namelen;
+    Ok((n as usize, msg.msg_flags))
+}
@@ -0,0 +1,469 @@
+//! Linux packet sockets.
+//!
+//! See the [`PacketSocket`] type for documentation.
+
+use std::fmt;
+use std::fs::File;
+use std::io;
+use std::mem::{self, size_of};
+use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
+
+use crate::io_source::IoSource;
+use crate::{event, Interest, Registry, Token};
+
+// Not defined by all supported versions of libc.
+const PACKET_ADD_MEMBERSHIP: libc::c_int = 1;
+const PACKET_DROP_MEMBERSHIP: libc::c_int = 2;
+const PACKET_MR_PROMISC: libc::c_ushort = 1;
+
+/// `struct packet_mreq` from `<linux/if_packet.h>`.
+#[repr(C)]
+struct PacketMreq {
+    mr_ifindex: libc::c_int,
+    mr_type: libc::c_ushort,
+    mr_alen: libc::c_ushort,
+    mr_address: [libc::c_uchar; 8],
+}
+
+/// `struct sock_fprog` from `<linux/filter.h>`.
+#[repr(C)]
+struct SockFprog {
+    len: libc::c_ushort,
+    filter: *const SockFilter,
+}
+
+/// A non-blocking `AF_PACKET` socket, used to send and receive packets at the
+/// link layer (see [`packet(7)`]).
+///
+/// Creating a packet socket requires the `CAP_NET_RAW` capability, without it
+/// [`new`] returns a `PermissionDenied` error (`EPERM`).
+///
+/// [`packet(7)`]: https://man7.org/linux/man-pages/man7/packet.7.html
+/// [`new`]: PacketSocket::new
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io;
+///
+/// use mio::net::PacketSocket;
+/// use mio::{Events, Interest, Poll, Token};
+///
+/// # fn main() -> io::Result<()> {
+/// // Receive all packets, on all interfaces.
+/// let mut socket = PacketSocket::new(libc::ETH_P_ALL as u16)?;
+///
+/// let mut poll = Poll::new()?;
+/// let mut events = Events::with_capacity(8);
+/// poll.registry().register(&mut socket, Token(0), Interest::READABLE)?;
+///
+/// let mut buf = [0; 65536];
+/// loop {
+///     poll.poll(&mut events, None)?;
+///     loop {
+///         match socket.recv_from(&mut buf) {
+///             Ok((n, addr)) => println!("received {} bytes on {}", n, addr.ifindex()),
+///             Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => break,
+///             Err(err) => return Err(err),
+///         }
+///     }
+/// }
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct PacketSocket {
+    inner: IoSource<File>,
+    /// Protocol in network byte order.
+    protocol: u16,
+}
+
+impl PacketSocket {
+    /// Creates a new raw (`SOCK_RAW`) packet socket for `protocol`, an
+    /// `ETH_P_*` constant in host byte order. Packets include the link layer
+    /// header.
+    pub fn new(protocol: u16) -> io::Result<PacketSocket> {
+        PacketSocket::new_with(libc::SOCK_RAW, protocol)
+    }
+
+    /// Creates a new cooked (`SOCK_DGRAM`) packet socket for `protocol`, see
+    /// [`new`]. The link layer header is removed from received packets and
+    /// added to sent packets based on the destination [`LinkAddr`].
+    ///
+    /// [`new`]: PacketSocket::new
+    pub fn new_dgram(protocol: u16) -> io::Result<PacketSocket> {
+        PacketSocket::new_with(libc::SOCK_DGRAM, protocol)
+    }
+
+    fn new_with(kind: libc::c_int, protocol: u16) -> io::Result<PacketSocket> {
+        let protocol = protocol.to_be();
+        let fd = syscall!(socket(
+            libc::AF_PACKET,
+            kind | libc::SOCK_NONBLOCK | libc::SOCK_CLOEXEC,
+            libc::c_int::from(protocol),
+        ))?;
+        Ok(PacketSocket {
+            // Safety: `socket` returned a new file descriptor.
+            inner: IoSource::new(unsafe { File::from_raw_fd(fd) }),
+            protocol,
+        })
+    }
+
+    /// Only receive packets from the interface with index `ifindex`, e.g. as
+    /// returned by `if_nametoindex(3)`.
+    pub fn bind_interface(&self, ifindex: u32) -> io::Result<()> {
+        let mut addr: libc::sockaddr_ll = unsafe { mem::zeroed() };
+        addr.sll_family = libc::AF_PACKET as libc::c_ushort;
+        addr.sll_protocol = self.protocol;
+        addr.sll_ifindex = ifindex as libc::c_int;
+        syscall!(bind(
+            self.as_raw_fd(),
+            &addr as *const _ as *const libc::sockaddr,
+            size_of::<libc::sockaddr_ll>() as libc::socklen_t,
+        ))
+        .map(|_| ())
+    }
+
+    /// Enables or disables promiscuous mode for the interface with index
+    /// `ifindex`, using `PACKET_MR_PROMISC` membership.
+    ///
+    /// Promiscuous mode is disabled once the socket is closed.
+    pub fn set_promiscuous(&self, ifindex: u32, promiscuous: bool) -> io::Result<()> {
+        let mreq = PacketMreq {
+            mr_ifindex: ifindex as libc::c_int,
+            mr_type: PACKET_MR_PROMISC,
+            mr_alen: 0,
+            mr_address: [0; 8],
+        };
+        let opt = if promiscuous {
+            PACKET_ADD_MEMBERSHIP
+        } else {
+            PACKET_DROP_MEMBERSHIP
+        };
+        syscall!(setsockopt(
+            self.as_raw_fd(),
+            libc::SOL_PACKET,
+            opt,
+            &mreq as *const _ as *const libc::c_void,
+            size_of::<PacketMreq>() as libc::socklen_t,
+        ))
+        .map(|_| ())
+    }
+
+    /// Attaches the classic BPF program `filter`, only packets accepted by
+    /// the program are received. Replaces the previously attached filter, if
+    /// any.
+    ///
+    /// Returns an `InvalidInput` error if `filter` has more than `u16::MAX`
+    /// instructions, the kernel returns an error if the program is invalid.
+    pub fn attach_filter(&self, filter: &[SockFilter]) -> io::Result<()> {
+        if filter.len() > u16::MAX as usize {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "too many filter instructions",
+            ));
+        }
+        let prog = SockFprog {
+            len: filter.len() as libc::c_ushort,
+            filter: filter.as_ptr(),
+        };
+        syscall!(setsockopt(
+            self.as_raw_fd(),
+            libc::SOL_SOCKET,
+            libc::SO_ATTACH_FILTER,
+            &prog as *const _ as *const libc::c_void,
+            size_of::<SockFprog>() as libc::socklen_t,
+        ))
+        .map(|_| ())
+    }
+
+    /// Detaches the filter attached using [`attach_filter`].
+    ///
+    /// [`attach_filter`]: PacketSocket::attach_filter
+    pub fn detach_filter(&self) -> io::Result<()> {
+        let value: libc::c_int = 0;
+        syscall!(setsockopt(
+            self.as_raw_fd(),
+            libc::SOL_SOCKET,
+            libc::SO_DETACH_FILTER,
+            &value as *const _ as *const libc::c_void,
+            size_of::<libc::c_int>() as libc::socklen_t,
+        ))
+        .map(|_| ())
+    }
+
+    /// Receives a single packet. On success, returns the number of bytes
+    /// read and the link layer address the packet came from.
+    ///
+    /// Any part of the packet that doesn't fit in `buf` is discarded.
+    pub fn recv_from(&self, buf: &mut [u8]) -> io::Result<(usize, LinkAddr)> {
+        self.inner.do_io(|_| {
+            let mut addr: libc::sockaddr_ll = unsafe { mem::zeroed() };
+            let mut len = size_of::<libc::sockaddr_ll>() as libc::socklen_t;
+            let n = syscall!(recvfrom(
+                self.as_raw_fd(),
+                buf.as_mut_ptr() as *mut libc::c_void,
+                buf.len(),
+                0,
+                &mut addr as *mut _ as *mut libc::sockaddr,
+                &mut len,
+            ))?;
+            Ok((n as usize, LinkAddr::from_sockaddr_ll(&addr)))
+        })
+    }
+
+    /// Receives a single packet, see [`recv_from`].
+    ///
+    /// [`recv_from`]: PacketSocket::recv_from
+    pub fn recv(&self, buf: &mut [u8]) -> io::Result<usize> {
+        self.inner.do_io(|_| {
+            syscall!(recv(
+                self.as_raw_fd(),
+                buf.as_mut_ptr() as *mut libc::c_void,
+                buf.len(),
+                0,
+            ))
+            .map(|n| n as usize)
+        })
+    }
+
+    /// Sends `buf` as a single packet to `addr`. On success, returns the
+    /// number of bytes written.
+    ///
+    /// For raw sockets `buf` must include the link layer header, for cooked
+    /// sockets the header is created based on `addr`.
+    pub fn send_to(&self, buf: &[u8], addr: &LinkAddr) -> io::Result<usize> {
+        self.inner.do_io(|_| {
+            let addr = addr.to_sockaddr_ll();
+            syscall!(sendto(
+                self.as_raw_fd(),
+                buf.as_ptr() as *const libc::c_void,
+                buf.len(),
+                0,
+                &addr as *const _ as *const libc::sockaddr,
+                size_of::<libc::sockaddr_ll>() as libc::socklen_t,
+            ))
+            .map(|n| n as usize)
+        })
+    }
+
+    /// Sends `buf` as a single packet on the interface the socket is bound to,
+    /// see [`bind_interface`] and [`send_to`].
+    ///
+    /// [`bind_interface`]: PacketSocket::bind_interface
+    /// [`send_to`]: PacketSocket::send_to
+    pub fn send(&self, buf: &[u8]) -> io::Result<usize> {
+        self.inner.do_io(|_| {
+            syscall!(send(
+                self.as_raw_fd(),
+                buf.as_ptr() as *const libc::c_void,
+                buf.len(),
+                0,
+            ))
+            .map(|n| n as usize)
+        })
+    }
+}
+
+impl event::Source for PacketSocket {
+    fn register(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        self.inner.register(registry, token, interests)
+    }
+
+    fn reregister(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        self.inner.reregister(registry, token, interests)
+    }
+
+    fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
+        self.inner.deregister(registry)
+    }
+}
+
+impl AsRawFd for PacketSocket {
+    fn as_raw_fd(&self) -> RawFd {
+        self.inner.as_raw_fd()
+    }
+}
+
+impl IntoRawFd for PacketSocket {
+    fn into_raw_fd(self) -> RawFd {
+        self.inner.into_inner().into_raw_fd()
+    }
+}
+
+/// A classic BPF instruction (`struct sock_filter`), see
+/// [`PacketSocket::attach_filter`].
+///
+/// Programs can be generated using `tcpdump -dd <expression>`, which prints
+/// the instructions in the order of the arguments of [`SockFilter::new`].
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct SockFilter {
+    code: u16,
+    jt: u8,
+    jf: u8,
+    k: u32,
+}
+
+impl SockFilter {
+    /// Creates a new instruction with opcode `code`, jump offsets `jt` (if
+    /// true) and `jf` (if false) and generic field `k`.
+    pub const fn new(code: u16, jt: u8, jf: u8, k: u32) -> SockFilter {
+        SockFilter { code, jt, jf, k }
+    }
+}
+
+/// A link layer address (`struct sockaddr_ll`), see [`packet(7)`].
+///
+/// [`packet(7)`]: https://man7.org/linux/man-pages/man7/packet.7.html
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct LinkAddr {
+    /// Protocol in network byte order.
+    protocol: u16,
+    ifindex: u32,
+    hardware_type: u16,
+    packet_type: u8,
+    len: u8,
+    address: [u8; 8],
+}
+
+impl LinkAddr {
+    /// Creates a destination address for [`PacketSocket::send_to`], for the
+    /// interface with index `ifindex` and physical (e.g. MAC) `address`.
+    /// `protocol` is an `ETH_P_*` constant in host byte order.
+    ///
+    /// Returns `None` if `address` is longer than 8 bytes.
+    pub fn new(ifindex: u32, protocol: u16, address: &[u8]) -> Option<LinkAddr> {
+        if address.len() > 8 {
+            return None;
+        }
+        let mut addr = LinkAddr {
+            protocol: protocol.to_be(),
+            ifindex,
+            hardware_type: 0,
+            packet_type: 0,
+            len: address.len() as u8,
+            address: [0; 8],
+        };
+        addr.address[..address.len()].copy_from_slice(address);
+        Some(addr)
+    }
+
+    /// Returns the `ETH_P_*` protocol, in host byte order.
+    pub fn protocol(&self) -> u16 {
+        u16::from_be(self.protocol)
+    }
+
+    /// Returns the index of the interface.
+    pub fn ifindex(&self) -> u32 {
+        self.ifindex
+    }
+
+    /// Returns the `ARPHRD_*` hardware type, e.g. `ARPHRD_ETHER`.
+    pub fn hardware_type(&self) -> u16 {
+        self.hardware_type
+    }
+
+    /// Returns the `PACKET_*` packet type, e.g. `PACKET_HOST` for packets
+    /// addressed to the local host or `PACKET_OUTGOING` for packets sent by
+    /// the local host.
+    pub fn packet_type(&self) -> u8 {
+        self.packet_type
+    }
+
+    /// Returns the physical (e.g. MAC) address.
+    pub fn address(&self) -> &[u8] {
+        &self.address[..self.len as usize]
+    }
+
+    fn from_sockaddr_ll(addr: &libc::sockaddr_ll) -> LinkAddr {
+        LinkAddr {
+            protocol: addr.sll_protocol,
+            ifindex: addr.sll_ifindex as u32,
+            hardware_type: addr.sll_hatype,
+            packet_type: addr.sll_pkttype,
+            len: addr.sll_halen.min(8),
+            address: addr.sll_addr,
+        }
+    }
+
+    fn to_sockaddr_ll(self) -> libc::sockaddr_ll {
+        let mut addr: libc::sockaddr_ll = unsafe { mem::zeroed() };
+        addr.sll_family = libc::AF_PACKET as libc::c_ushort;
+        addr.sll_protocol = self.protocol;
+        addr.sll_ifindex = self.ifindex as libc::c_int;
+        addr.sll_halen = self.len;
+        addr.sll_addr = self.address;
+        addr
+    }
+}
+
+impl fmt::Debug for LinkAddr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LinkAddr")
+            .field("protocol", &self.protocol())
+            .field("ifindex", &self.ifindex)
+            .field("hardware_type", &self.hardware_type)
+            .field("packet_type", &self.packet_type)
+            .field("address", &self.address())
+            .finish()
+    }
+}
+
+#[test]
+fn sock_filter_layout() {
+    // Must match `struct sock_filter` and `struct sock_fprog`.
+    assert_eq!(size_of::<SockFilter>(), 8);
+    assert_eq!(mem::align_of::<SockFilter>(), 4);
+    assert_eq!(size_of::<SockFprog>(), 2 * size_of::<usize>());
+
+    // `ret #-1`, accept the entire packet.
+    let filter = SockFilter::new(0x06, 1, 2, 0xffff_ffff);
+    let bytes: [u8; 8] = unsafe { mem::transmute(filter) };
+    let mut expected = [0; 8];
+    expected[..2].copy_from_slice(&0x06u16.to_ne_bytes());
+    expected[2] = 1;
+    expected[3] = 2;
+    expected[4..].copy_from_slice(&0xffff_ffffu32.to_ne_bytes());
+    assert_eq!(bytes, expected);
+}
+
+#[test]
+fn link_addr_sockaddr_ll() {
+    let mut raw: libc::sockaddr_ll = unsafe { mem::zeroed() };
+    raw.sll_family = libc::AF_PACKET as libc::c_ushort;
+    raw.sll_protocol = (libc::ETH_P_IP as u16).to_be();
+    raw.sll_ifindex = 2;
+    raw.sll_hatype = 1; // ARPHRD_ETHER.
+    raw.sll_pkttype = libc::PACKET_OUTGOING as u8;
+    raw.sll_halen = 6;
+    raw.sll_addr = [1, 2, 3, 4, 5, 6, 0, 0];
+
+    let addr = LinkAddr::from_sockaddr_ll(&raw);
+    assert_eq!(addr.protocol(), libc::ETH_P_IP as u16);
+    assert_eq!(addr.ifindex(), 2);
+    assert_eq!(addr.hardware_type(), 1);
+    assert_eq!(addr.packet_type(), libc::PACKET_OUTGOING as u8);
+    assert_eq!(addr.address(), &[1, 2, 3, 4, 5, 6]);
+
+    let back = addr.to_sockaddr_ll();
+    assert_eq!(back.sll_family, raw.sll_family);
+    assert_eq!(back.sll_protocol, raw.sll_protocol);
+    assert_eq!(back.sll_ifindex, raw.sll_ifindex);
+    assert_eq!(back.sll_halen, raw.sll_halen);
+    assert_eq!(back.sll_addr, raw.sll_addr);
+
+    let addr = LinkAddr::new(2, libc::ETH_P_IP as u16, &[1, 2, 3, 4, 5, 6]).unwrap();
+    assert_eq!(addr.to_sockaddr_ll().sll_protocol, raw.sll_protocol);
+    assert_eq!(addr.address(), &[1, 2, 3, 4, 5, 6]);
+    assert!(LinkAddr::new(2, 0, &[0; 9]).is_none());
+}
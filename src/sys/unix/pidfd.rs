@@ -0,0 +1,157 @@
+//! Linux process file descriptors.
+//!
+//! See the [`PidFd`] type for documentation.
+
+use std::fs::File;
+use std::io;
+use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
+use std::os::unix::process::ExitStatusExt;
+use std::process::{Child, ExitStatus};
+
+use crate::io_source::IoSource;
+use crate::{event, Interest, Registry, Token};
+
+/// A process file descriptor, created using [`pidfd_open(2)`].
+///
+/// The file descriptor becomes readable once the process exits, so it can be
+/// registered with [`READABLE`] interest to receive an event when that
+/// happens. After the event the exit status can be retrieved using
+/// [`try_wait`].
+///
+/// This requires Linux 5.4 or later, on older kernels [`open`] returns an
+/// error.
+///
+/// [`pidfd_open(2)`]: https://man7.org/linux/man-pages/man2/pidfd_open.2.html
+/// [`READABLE`]: Interest::READABLE
+/// [`try_wait`]: PidFd::try_wait
+/// [`open`]: PidFd::open
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io;
+/// use std::process::Command;
+///
+/// use mio::unix::pidfd::PidFd;
+/// use mio::{Events, Interest, Poll, Token};
+///
+/// # fn main() -> io::Result<()> {
+/// let mut poll = Poll::new()?;
+/// let mut events = Events::with_capacity(8);
+///
+/// let child = Command::new("true").spawn()?;
+/// let mut pidfd = PidFd::from_child(&child)?;
+/// poll.registry().register(&mut pidfd, Token(0), Interest::READABLE)?;
+///
+/// loop {
+///     poll.poll(&mut events, None)?;
+///     if let Some(status) = pidfd.try_wait()? {
+///         println!("child exited: {}", status);
+///         return Ok(());
+///     }
+/// }
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct PidFd {
+    inner: IoSource<File>,
+}
+
+impl PidFd {
+    /// Opens a file descriptor for the process with id `pid`.
+    ///
+    /// The file descriptor has the close-on-exec flag set.
+    pub fn open(pid: u32) -> io::Result<PidFd> {
+        let fd = unsafe { libc::syscall(libc::SYS_pidfd_open, pid as libc::pid_t, 0) };
+        if fd == -1 {
+            return Err(io::Error::last_os_error());
+        }
+        // Safety: `pidfd_open` returned a new file descriptor.
+        Ok(unsafe { PidFd::from_raw_fd(fd as RawFd) })
+    }
+
+    /// Opens a file descriptor for `child`, see [`PidFd::open`].
+    ///
+    /// Note that [`try_wait`] reaps the process, after it returned the exit
+    /// status `child` can't be waited on anymore.
+    ///
+    /// [`try_wait`]: PidFd::try_wait
+    pub fn from_child(child: &Child) -> io::Result<PidFd> {
+        PidFd::open(child.id())
+    }
+
+    /// Returns the exit status of the process if it exited, reaping it,
+    /// without blocking. Returns `Ok(None)` if the process is still running.
+    ///
+    /// Uses `waitid(2)` with `P_PIDFD`, so the process must be a child of the
+    /// calling process.
+    pub fn try_wait(&self) -> io::Result<Option<ExitStatus>> {
+        let mut info: libc::siginfo_t = unsafe { std::mem::zeroed() };
+        syscall!(waitid(
+            libc::P_PIDFD,
+            self.as_raw_fd() as libc::id_t,
+            &mut info,
+            libc::WEXITED | libc::WNOHANG,
+        ))?;
+
+        // If `WNOHANG` is set and the process is still running `si_pid` is
+        // left zeroed.
+        if unsafe { info.si_pid() } == 0 {
+            return Ok(None);
+        }
+
+        let status = unsafe { info.si_status() };
+        // Convert the status into the format used by `waitpid(2)`, which is
+        // what `ExitStatus` expects.
+        let status = match info.si_code {
+            libc::CLD_EXITED => (status & 0xff) << 8,
+            libc::CLD_DUMPED => status | 0x80,
+            _ => status,
+        };
+        Ok(Some(ExitStatus::from_raw(status)))
+    }
+}
+
+impl event::Source for PidFd {
+    fn register(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        self.inner.register(registry, token, interests)
+    }
+
+    fn reregister(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        self.inner.reregister(registry, token, interests)
+    }
+
+    fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
+        self.inner.deregister(registry)
+    }
+}
+
+impl FromRawFd for PidFd {
+    unsafe fn from_raw_fd(fd: RawFd) -> PidFd {
+        PidFd {
+            inner: IoSource::new(File::from_raw_fd(fd)),
+        }
+    }
+}
+
+impl AsRawFd for PidFd {
+    fn as_raw_fd(&self) -> RawFd {
+        self.inner.as_raw_fd()
+    }
+}
+
+impl IntoRawFd for PidFd {
+    fn into_raw_fd(self) -> RawFd {
+        self.inner.into_inner().into_raw_fd()
+    }
+}
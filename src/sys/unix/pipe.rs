@@ -2,10 +2,12 @@
 //!
 //! See the [`new`] function for documentation.
 
-use std::fs::File;
+use std::fs::{File, OpenOptions};
 use std::io::{self, IoSlice, IoSliceMut, Read, Write};
+use std::os::unix::fs::{FileTypeExt, OpenOptionsExt};
 use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
-use std::process::{ChildStderr, ChildStdin, ChildStdout};
+use std::path::Path;
+use std::process::{ChildStderr, ChildStdin, ChildStdout, Stdio};
 
 use crate::io_source::IoSource;
 use crate::{event, Interest, Registry, Token};
@@ -203,6 +205,97 @@ pub fn new() -> io::Result<(Sender, Receiver)> {
     Ok((w, r))
 }
 
+/// Create a new Unix pipe to read the standard output (or error) of a child
+/// process.
+///
+/// Unlike [`new`] only the returned [`Receiver`] is non-blocking, the
+/// [`Stdio`] end, which is passed to the child process, is left in blocking
+/// mode as most programs expect.
+///
+/// # Examples
+///
+/// ```
+/// use std::io;
+/// use std::process::Command;
+///
+/// use mio::unix::pipe;
+///
+/// # fn main() -> io::Result<()> {
+/// let (stdout, receiver) = pipe::stdio_receiver()?;
+/// let mut child = Command::new("echo").arg("Hello").stdout(stdout).spawn()?;
+///
+/// // `receiver` can now be registered with `Poll`.
+/// # drop(receiver);
+/// # child.wait()?;
+/// #     Ok(())
+/// # }
+/// ```
+pub fn stdio_receiver() -> io::Result<(Stdio, Receiver)> {
+    let (sender, receiver) = new()?;
+    sender.set_nonblocking(false)?;
+    // Safety: the file descriptor is owned by `sender`.
+    let stdio = unsafe { Stdio::from_raw_fd(sender.into_raw_fd()) };
+    Ok((stdio, receiver))
+}
+
+/// Create a new Unix pipe to write to the standard input of a child process.
+///
+/// Unlike [`new`] only the returned [`Sender`] is non-blocking, the [`Stdio`]
+/// end, which is passed to the child process, is left in blocking mode as
+/// most programs expect.
+pub fn stdio_sender() -> io::Result<(Sender, Stdio)> {
+    let (sender, receiver) = new()?;
+    receiver.set_nonblocking(false)?;
+    // Safety: the file descriptor is owned by `receiver`.
+    let stdio = unsafe { Stdio::from_raw_fd(receiver.into_raw_fd()) };
+    Ok((sender, stdio))
+}
+
+/// Open the named pipe (FIFO) at `path` for reading, without blocking.
+///
+/// Opening a FIFO read-only blocks until a writer opens it, and once all
+/// writers closed it the receiving end is continuously reported as [read
+/// closed]. To avoid both the FIFO is opened for reading *and* writing, so
+/// writers can come and go and the returned `Receiver` only becomes readable
+/// once data is written. As a consequence reading never returns end of file,
+/// the absence of (new) data is reported as a [`WouldBlock`] error.
+///
+/// Opening a FIFO read-write is not specified by POSIX, but supported on all
+/// platforms supported by this module. Returns an `InvalidInput` error if
+/// `path` isn't a FIFO, e.g. as created by `mkfifo(3)`.
+///
+/// [read closed]: event::Event::is_read_closed
+/// [`WouldBlock`]: io::ErrorKind::WouldBlock
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io;
+///
+/// use mio::unix::pipe;
+/// use mio::{Interest, Poll, Token};
+///
+/// # fn main() -> io::Result<()> {
+/// let poll = Poll::new()?;
+/// let mut receiver = pipe::open_fifo("/tmp/my_fifo")?;
+/// poll.registry().register(&mut receiver, Token(0), Interest::READABLE)?;
+/// #     Ok(())
+/// # }
+/// ```
+pub fn open_fifo<P: AsRef<Path>>(path: P) -> io::Result<Receiver> {
+    let file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .custom_flags(libc::O_NONBLOCK)
+        .open(path)?;
+    if !file.metadata()?.file_type().is_fifo() {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "not a FIFO"));
+    }
+    Ok(Receiver {
+        inner: IoSource::new(file),
+    })
+}
+
 /// Sending end of an Unix pipe.
 ///
 /// See [`new`] for documentation, including examples.
@@ -1,12 +1,14 @@
+use crate::features::{Capabilities, Triggering};
 use crate::{Interest, Token};
 
-use libc::{EPOLLET, EPOLLIN, EPOLLOUT, EPOLLRDHUP};
+use libc::{EPOLLET, EPOLLIN, EPOLLONESHOT, EPOLLOUT, EPOLLPRI, EPOLLRDHUP, EPOLLWRBAND};
 use log::error;
+use std::mem::MaybeUninit;
 use std::os::unix::io::{AsRawFd, RawFd};
 #[cfg(debug_assertions)]
-use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicI32, AtomicUsize, Ordering};
 use std::time::Duration;
-use std::{cmp, i32, io, ptr};
+use std::{cmp, io, ptr, slice};
 
 /// Unique id for use as `SelectorId`.
 #[cfg(debug_assertions)]
@@ -19,9 +21,25 @@ pub struct Selector {
     ep: RawFd,
     #[cfg(debug_assertions)]
     has_waker: AtomicBool,
+    /// Timeout argument of the last call to `epoll_wait`, see `last_timeout`.
+    #[cfg(debug_assertions)]
+    last_timeout: AtomicI32,
 }
 
+/// Value of `Selector::last_timeout` if `select` hasn't been called yet.
+#[cfg(debug_assertions)]
+const NO_TIMEOUT: i32 = i32::MIN;
+
 impl Selector {
+    pub const CAPABILITIES: Capabilities = Capabilities {
+        triggering: Triggering::Edge,
+        oneshot: true,
+        waker: true,
+        priority: true,
+        // `epoll_ctl` returns `EPERM` for regular files.
+        regular_files: false,
+    };
+
     pub fn new() -> io::Result<Selector> {
         // According to libuv, `EPOLL_CLOEXEC` is not defined on Android API <
         // 21. But `EPOLL_CLOEXEC` is an alias for `O_CLOEXEC` on that platform,
@@ -37,6 +55,8 @@ impl Selector {
             ep,
             #[cfg(debug_assertions)]
             has_waker: AtomicBool::new(false),
+            #[cfg(debug_assertions)]
+            last_timeout: AtomicI32::new(NO_TIMEOUT),
         })
     }
 
@@ -48,34 +68,46 @@ impl Selector {
             ep,
             #[cfg(debug_assertions)]
             has_waker: AtomicBool::new(self.has_waker.load(Ordering::Acquire)),
+            #[cfg(debug_assertions)]
+            last_timeout: AtomicI32::new(self.last_timeout.load(Ordering::Relaxed)),
         })
     }
 
     pub fn select(&self, events: &mut Events, timeout: Option<Duration>) -> io::Result<()> {
-        // A bug in kernels < 2.6.37 makes timeouts larger than LONG_MAX / CONFIG_HZ
-        // (approx. 30 minutes with CONFIG_HZ=1200) effectively infinite on 32 bits
-        // architectures. The magic number is the same constant used by libuv.
-        #[cfg(target_pointer_width = "32")]
-        const MAX_SAFE_TIMEOUT: u128 = 1789569;
-        #[cfg(not(target_pointer_width = "32"))]
-        const MAX_SAFE_TIMEOUT: u128 = libc::c_int::max_value() as u128;
-
-        let timeout = timeout
-            .map(|to| cmp::min(to.as_millis(), MAX_SAFE_TIMEOUT) as libc::c_int)
-            .unwrap_or(-1);
-
         events.clear();
+        // Safety: the capacity of `events` is allocated, but uninitialised.
+        let buf = unsafe {
+            slice::from_raw_parts_mut(
+                events.as_mut_ptr() as *mut MaybeUninit<Event>,
+                events.capacity(),
+            )
+        };
+        self.select_into(buf, timeout).map(|n_events| {
+            // This is safe because `epoll_wait` ensures that `n_events` are
+            // assigned.
+            unsafe { events.set_len(n_events) };
+        })
+    }
+
+    /// Same as `select`, but stores the events in `buf`, returning the number
+    /// of events initialised.
+    pub fn select_into(
+        &self,
+        buf: &mut [MaybeUninit<Event>],
+        timeout: Option<Duration>,
+    ) -> io::Result<usize> {
+        let timeout = timeout_millis(timeout);
+        #[cfg(debug_assertions)]
+        self.last_timeout.store(timeout, Ordering::Relaxed);
+
+        let len = cmp::min(buf.len(), libc::c_int::MAX as usize);
         syscall!(epoll_wait(
             self.ep,
-            events.as_mut_ptr(),
-            events.capacity() as i32,
+            buf.as_mut_ptr() as *mut Event,
+            len as libc::c_int,
             timeout,
         ))
-        .map(|n_events| {
-            // This is safe because `epoll_wait` ensures that `n_events` are
-            // assigned.
-            unsafe { events.set_len(n_events as usize) };
-        })
+        .map(|n_events| n_events as usize)
     }
 
     pub fn register(&self, fd: RawFd, token: Token, interests: Interest) -> io::Result<()> {
@@ -104,6 +136,18 @@ impl Selector {
     pub fn register_waker(&self) -> bool {
         self.has_waker.swap(true, Ordering::AcqRel)
     }
+
+    /// Returns the timeout argument, in milliseconds, passed to `epoll_wait`
+    /// by the last call to `select`, `-1` meaning no timeout. Returns `None` if
+    /// `select` wasn't called yet.
+    #[cfg(debug_assertions)]
+    #[allow(dead_code)] // Only used in tests.
+    pub fn last_timeout(&self) -> Option<libc::c_int> {
+        match self.last_timeout.load(Ordering::Relaxed) {
+            NO_TIMEOUT => None,
+            timeout => Some(timeout),
+        }
+    }
 }
 
 cfg_io_source! {
@@ -129,6 +173,28 @@ impl Drop for Selector {
     }
 }
 
+// A bug in kernels < 2.6.37 makes timeouts larger than LONG_MAX / CONFIG_HZ
+// (approx. 30 minutes with CONFIG_HZ=1200) effectively infinite on 32 bits
+// architectures. The magic number is the same constant used by libuv.
+#[cfg(target_pointer_width = "32")]
+const MAX_SAFE_TIMEOUT: u128 = 1789569;
+#[cfg(not(target_pointer_width = "32"))]
+const MAX_SAFE_TIMEOUT: u128 = libc::c_int::max_value() as u128;
+
+/// Converts `timeout` into the timeout argument for `epoll_wait(2)`, in
+/// milliseconds.
+///
+/// Timeouts that don't fit in a `c_int` (e.g. `Duration::MAX`) block forever,
+/// rather than returning after `c_int::MAX` milliseconds only to be polled
+/// again.
+fn timeout_millis(timeout: Option<Duration>) -> libc::c_int {
+    match timeout {
+        Some(to) if to.as_millis() > libc::c_int::MAX as u128 => -1,
+        Some(to) => cmp::min(to.as_millis(), MAX_SAFE_TIMEOUT) as libc::c_int,
+        None => -1,
+    }
+}
+
 fn interests_to_epoll(interests: Interest) -> u32 {
     let mut kind = EPOLLET;
 
@@ -140,6 +206,22 @@ fn interests_to_epoll(interests: Interest) -> u32 {
         kind |= EPOLLOUT;
     }
 
+    if interests.is_priority() {
+        kind |= EPOLLPRI;
+    }
+
+    if interests.is_band_writable() {
+        kind |= EPOLLWRBAND;
+    }
+
+    if interests.is_read_closed() {
+        kind |= EPOLLRDHUP;
+    }
+
+    if interests.is_oneshot() {
+        kind |= EPOLLONESHOT;
+    }
+
     kind as u32
 }
 
@@ -172,9 +254,9 @@ pub mod event {
     pub fn is_read_closed(event: &Event) -> bool {
         // Both halves of the socket have closed
         event.events as libc::c_int & libc::EPOLLHUP != 0
-            // Socket has received FIN or called shutdown(SHUT_RD)
-            || (event.events as libc::c_int & libc::EPOLLIN != 0
-                && event.events as libc::c_int & libc::EPOLLRDHUP != 0)
+            // Socket has received FIN or called shutdown(SHUT_RD). Without
+            // readable interest `EPOLLIN` isn't set, only `EPOLLRDHUP`.
+            || event.events as libc::c_int & libc::EPOLLRDHUP != 0
     }
 
     pub fn is_write_closed(event: &Event) -> bool {
@@ -191,6 +273,10 @@ pub mod event {
         (event.events as libc::c_int & libc::EPOLLPRI) != 0
     }
 
+    pub fn is_band_writable(event: &Event) -> bool {
+        (event.events as libc::c_int & libc::EPOLLWRBAND) != 0
+    }
+
     pub fn is_aio(_: &Event) -> bool {
         // Not supported in the kernel, only in libc.
         false
@@ -244,3 +330,183 @@ fn assert_close_on_exec_flag() {
     // This assertion need to be true for Selector::new.
     assert_eq!(libc::O_CLOEXEC, libc::EPOLL_CLOEXEC);
 }
+
+#[test]
+fn timeout_millis_clamp() {
+    // No timeout blocks forever.
+    assert_eq!(timeout_millis(None), -1);
+    assert_eq!(timeout_millis(Some(Duration::from_millis(0))), 0);
+    assert_eq!(timeout_millis(Some(Duration::from_millis(10))), 10);
+    // Truncated to milliseconds.
+    assert_eq!(timeout_millis(Some(Duration::from_micros(1500))), 1);
+    // Large timeouts are clamped, rather than overflowing into a negative
+    // (infinite) timeout.
+    assert_eq!(
+        timeout_millis(Some(Duration::from_millis(
+            libc::c_int::max_value() as u64
+        ))),
+        MAX_SAFE_TIMEOUT as libc::c_int
+    );
+    // Timeouts that don't fit in a `c_int` are infinite.
+    assert_eq!(
+        timeout_millis(Some(Duration::from_millis(
+            libc::c_int::max_value() as u64 + 1
+        ))),
+        -1
+    );
+    // `Duration::MAX`.
+    assert_eq!(
+        timeout_millis(Some(Duration::new(u64::MAX, 999_999_999))),
+        -1
+    );
+}
+
+#[test]
+fn interests_to_epoll_round_trip() {
+    // Inverse of `interests_to_epoll`.
+    fn epoll_to_interests(kind: u32) -> Option<Interest> {
+        let kind = kind as libc::c_int;
+        assert!(kind & EPOLLET != 0, "missing EPOLLET");
+        let mut interests = None;
+        let mut add = |interest| {
+            interests = Some(interests.map_or(interest, |i: Interest| i | interest));
+        };
+        if kind & EPOLLIN != 0 {
+            assert!(kind & EPOLLRDHUP != 0, "EPOLLIN without EPOLLRDHUP");
+            add(Interest::READABLE);
+        } else if kind & EPOLLRDHUP != 0 {
+            add(Interest::READ_CLOSED);
+        }
+        if kind & EPOLLOUT != 0 {
+            add(Interest::WRITABLE);
+        }
+        if kind & EPOLLPRI != 0 {
+            add(Interest::PRIORITY);
+        }
+        if kind & EPOLLWRBAND != 0 {
+            add(Interest::BAND_WRITABLE);
+        }
+        if kind & EPOLLONESHOT != 0 {
+            add(Interest::ONESHOT);
+        }
+        interests
+    }
+
+    let all = [
+        Interest::READABLE,
+        Interest::WRITABLE,
+        Interest::PRIORITY,
+        Interest::BAND_WRITABLE,
+        Interest::ONESHOT,
+        Interest::READ_CLOSED,
+    ];
+    for n in 1..(1 << all.len()) {
+        let interests = all
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| n & (1 << i) != 0)
+            .fold(None, |acc: Option<Interest>, (_, interest)| {
+                Some(acc.map_or(*interest, |acc| acc | *interest))
+            })
+            .unwrap();
+        let kind = interests_to_epoll(interests);
+        // Readable interest already includes read closed interest.
+        let expected = if interests.is_readable() {
+            interests.remove(Interest::READ_CLOSED)
+        } else {
+            Some(interests)
+        };
+        assert_eq!(epoll_to_interests(kind), expected, "{:?}", interests);
+        assert_eq!(
+            kind as libc::c_int & EPOLLPRI != 0,
+            interests.is_priority(),
+            "{:?}",
+            interests
+        );
+    }
+
+    // Priority interest doesn't imply readable interest.
+    assert_eq!(interests_to_epoll(Interest::PRIORITY), (EPOLLET | EPOLLPRI) as u32);
+    // Band writable interest doesn't imply writable interest.
+    assert_eq!(
+        interests_to_epoll(Interest::BAND_WRITABLE),
+        (EPOLLET | EPOLLWRBAND) as u32
+    );
+    // Read closed interest doesn't imply readable interest.
+    assert_eq!(
+        interests_to_epoll(Interest::READ_CLOSED),
+        (EPOLLET | EPOLLRDHUP) as u32
+    );
+}
+
+#[test]
+fn error_only_event() {
+    // An event with only `EPOLLERR` set, as can be returned for a listener.
+    let event = libc::epoll_event {
+        events: libc::EPOLLERR as u32,
+        u64: 0,
+    };
+    assert!(event::is_error(&event));
+    assert!(!event::is_readable(&event));
+    assert!(!event::is_writable(&event));
+    assert!(!event::is_read_closed(&event));
+    // Also used when the read end of a pipe is closed, see `is_write_closed`.
+    assert!(event::is_write_closed(&event));
+}
+
+#[test]
+fn reset_with_pending_data_event() {
+    // The flags returned for a TCP stream that received a RST while data is
+    // still queued.
+    let event = libc::epoll_event {
+        events: (libc::EPOLLIN | libc::EPOLLERR | libc::EPOLLHUP | libc::EPOLLRDHUP) as u32,
+        u64: 0,
+    };
+    assert!(event::is_readable(&event));
+    assert!(event::is_error(&event));
+    assert!(event::is_read_closed(&event));
+}
+
+#[test]
+#[cfg(debug_assertions)]
+fn last_timeout() {
+    let selector = Selector::new().unwrap();
+    assert_eq!(selector.last_timeout(), None);
+
+    // A pipe that is always readable, so that `select` returns immediately
+    // regardless of the timeout.
+    let mut fds = [-1; 2];
+    assert_eq!(unsafe { libc::pipe(fds.as_mut_ptr()) }, 0);
+    assert_eq!(unsafe { libc::write(fds[1], b"!".as_ptr().cast(), 1) }, 1);
+    selector
+        .register(fds[0], Token(0), Interest::READABLE)
+        .unwrap();
+
+    let tests = [
+        (None, -1),
+        (Some(Duration::from_millis(0)), 0),
+        (Some(Duration::from_millis(10)), 10),
+        (Some(Duration::from_micros(1500)), 1),
+        (
+            Some(Duration::from_millis(libc::c_int::MAX as u64)),
+            MAX_SAFE_TIMEOUT as libc::c_int,
+        ),
+        // `Duration::MAX` blocks forever, it's not clamped.
+        (Some(Duration::new(u64::MAX, 999_999_999)), -1),
+    ];
+    let mut events = Vec::with_capacity(1);
+    for (timeout, expected) in tests.iter() {
+        // Rearm the readiness, the selector is edge-triggered.
+        selector
+            .reregister(fds[0], Token(0), Interest::READABLE)
+            .unwrap();
+        selector.select(&mut events, *timeout).unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(selector.last_timeout(), Some(*expected), "{:?}", timeout);
+    }
+
+    unsafe {
+        libc::close(fds[0]);
+        libc::close(fds[1]);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::features::{Capabilities, Triggering};
 use crate::{Interest, Token};
 use log::error;
 use std::mem::MaybeUninit;
@@ -5,6 +6,8 @@ use std::ops::{Deref, DerefMut};
 use std::os::unix::io::{AsRawFd, RawFd};
 #[cfg(debug_assertions)]
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+#[cfg(any(target_os = "freebsd", target_os = "ios", target_os = "macos"))]
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use std::{cmp, io, ptr, slice};
 
@@ -71,9 +74,21 @@ pub struct Selector {
     kq: RawFd,
     #[cfg(debug_assertions)]
     has_waker: AtomicBool,
+    /// Token of the user space notification set up for a user's `Waker`,
+    /// shared by all clones.
+    #[cfg(any(target_os = "freebsd", target_os = "ios", target_os = "macos"))]
+    waker_token: Arc<Mutex<Option<Token>>>,
 }
 
 impl Selector {
+    pub const CAPABILITIES: Capabilities = Capabilities {
+        triggering: Triggering::Edge,
+        oneshot: false,
+        waker: true,
+        priority: false,
+        regular_files: true,
+    };
+
     pub fn new() -> io::Result<Selector> {
         syscall!(kqueue())
             .and_then(|kq| syscall!(fcntl(kq, libc::F_SETFD, libc::FD_CLOEXEC)).map(|_| kq))
@@ -83,6 +98,8 @@ impl Selector {
                 kq,
                 #[cfg(debug_assertions)]
                 has_waker: AtomicBool::new(false),
+                #[cfg(any(target_os = "freebsd", target_os = "ios", target_os = "macos"))]
+                waker_token: Arc::new(Mutex::new(None)),
             })
     }
 
@@ -94,10 +111,34 @@ impl Selector {
             kq,
             #[cfg(debug_assertions)]
             has_waker: AtomicBool::new(self.has_waker.load(Ordering::Acquire)),
+            #[cfg(any(target_os = "freebsd", target_os = "ios", target_os = "macos"))]
+            waker_token: self.waker_token.clone(),
         })
     }
 
     pub fn select(&self, events: &mut Events, timeout: Option<Duration>) -> io::Result<()> {
+        events.clear();
+        // Safety: the capacity of `events` is allocated, but uninitialised.
+        let buf = unsafe {
+            slice::from_raw_parts_mut(
+                events.as_mut_ptr() as *mut MaybeUninit<Event>,
+                events.capacity(),
+            )
+        };
+        self.select_into(buf, timeout).map(|n_events| {
+            // This is safe because `kevent` ensures that `n_events` are
+            // assigned.
+            unsafe { events.set_len(n_events) };
+        })
+    }
+
+    /// Same as `select`, but stores the events in `buf`, returning the number
+    /// of events initialised.
+    pub fn select_into(
+        &self,
+        buf: &mut [MaybeUninit<Event>],
+        timeout: Option<Duration>,
+    ) -> io::Result<usize> {
         let timeout = timeout.map(|to| libc::timespec {
             tv_sec: cmp::min(to.as_secs(), libc::time_t::max_value() as u64) as libc::time_t,
             // `Duration::subsec_nanos` is guaranteed to be less than one
@@ -111,20 +152,16 @@ impl Selector {
             .map(|s| s as *const _)
             .unwrap_or(ptr::null_mut());
 
-        events.clear();
+        let len = cmp::min(buf.len(), Count::MAX as usize);
         syscall!(kevent(
             self.kq,
             ptr::null(),
             0,
-            events.as_mut_ptr(),
-            events.capacity() as Count,
+            buf.as_mut_ptr() as *mut Event,
+            len as Count,
             timeout,
         ))
-        .map(|n_events| {
-            // This is safe because `kevent` ensures that `n_events` are
-            // assigned.
-            unsafe { events.set_len(n_events as usize) };
-        })
+        .map(|n_events| n_events as usize)
     }
 
     pub fn register(&self, fd: RawFd, token: Token, interests: Interest) -> io::Result<()> {
@@ -219,9 +256,21 @@ impl Selector {
         self.has_waker.swap(true, Ordering::AcqRel)
     }
 
-    // Used by `Waker`.
+    // Used by `Waker`, returns the token to wake with.
+    //
+    // All wakers share a single user space notification. Internal wakers,
+    // using a reserved token (e.g. the one used by `Registry::shutdown`),
+    // reuse the notification of the user's `Waker` if it's set up, rather than
+    // replacing its token.
     #[cfg(any(target_os = "freebsd", target_os = "ios", target_os = "macos"))]
-    pub fn setup_waker(&self, token: Token) -> io::Result<()> {
+    pub fn setup_waker(&self, token: Token) -> io::Result<Token> {
+        let mut waker_token = self.waker_token.lock().unwrap();
+        if token.is_reserved() {
+            if let Some(waker_token) = *waker_token {
+                return Ok(waker_token);
+            }
+        }
+
         // First attempt to accept user space notifications.
         let mut kevent = kevent!(
             0,
@@ -230,13 +279,14 @@ impl Selector {
             token.0
         );
 
-        syscall!(kevent(self.kq, &kevent, 1, &mut kevent, 1, ptr::null())).and_then(|_| {
-            if (kevent.flags & libc::EV_ERROR) != 0 && kevent.data != 0 {
-                Err(io::Error::from_raw_os_error(kevent.data as i32))
-            } else {
-                Ok(())
-            }
-        })
+        syscall!(kevent(self.kq, &kevent, 1, &mut kevent, 1, ptr::null()))?;
+        if (kevent.flags & libc::EV_ERROR) != 0 && kevent.data != 0 {
+            return Err(io::Error::from_raw_os_error(kevent.data as i32));
+        }
+        if !token.is_reserved() {
+            *waker_token = Some(token);
+        }
+        Ok(token)
     }
 
     // Used by `Waker`.
@@ -409,6 +459,11 @@ pub mod event {
         false
     }
 
+    pub fn is_band_writable(_: &Event) -> bool {
+        // kqueue doesn't have band indicators.
+        false
+    }
+
     #[allow(unused_variables)] // `event` is not used on some platforms.
     pub fn is_aio(event: &Event) -> bool {
         #[cfg(any(
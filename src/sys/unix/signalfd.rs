@@ -0,0 +1,181 @@
+//! Linux signal file descriptors.
+//!
+//! See the [`SignalFd`] type for documentation.
+
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Read};
+use std::mem::{self, size_of};
+use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
+
+use crate::io_source::IoSource;
+use crate::{event, Interest, Registry, Token};
+
+/// A non-blocking signal file descriptor, created using [`signalfd(2)`].
+///
+/// The file descriptor becomes readable when one of the signals it was created
+/// for is pending, so it can be registered with [`READABLE`] interest to
+/// handle signals the same way as I/O. [`read_signal`] returns the information
+/// about the signal, removing it from the pending signals.
+///
+/// The signals must be **blocked** (e.g. using `pthread_sigmask(3)`) in all
+/// threads, otherwise they are still handled using their default disposition
+/// (which often terminates the process) instead of being delivered to the file
+/// descriptor.
+///
+/// [`signalfd(2)`]: https://man7.org/linux/man-pages/man2/signalfd.2.html
+/// [`READABLE`]: Interest::READABLE
+/// [`read_signal`]: SignalFd::read_signal
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io;
+///
+/// use mio::unix::signalfd::SignalFd;
+/// use mio::{Events, Interest, Poll, Token};
+///
+/// # fn main() -> io::Result<()> {
+/// // NOTE: `SIGINT` must be blocked before this.
+/// let mut signals = SignalFd::new(&[libc::SIGINT])?;
+///
+/// let mut poll = Poll::new()?;
+/// let mut events = Events::with_capacity(8);
+/// poll.registry().register(&mut signals, Token(0), Interest::READABLE)?;
+///
+/// loop {
+///     poll.poll(&mut events, None)?;
+///     loop {
+///         match signals.read_signal() {
+///             Ok(info) => println!("received signal {}", info.signal()),
+///             Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => break,
+///             Err(err) => return Err(err),
+///         }
+///     }
+/// }
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct SignalFd {
+    inner: IoSource<File>,
+}
+
+impl SignalFd {
+    /// Creates a new signal file descriptor for `signals`.
+    ///
+    /// The file descriptor is in non-blocking mode and has the close-on-exec
+    /// flag set.
+    pub fn new(signals: &[libc::c_int]) -> io::Result<SignalFd> {
+        let mut set: libc::sigset_t = unsafe { mem::zeroed() };
+        syscall!(sigemptyset(&mut set))?;
+        for signal in signals {
+            syscall!(sigaddset(&mut set, *signal))?;
+        }
+        let fd = syscall!(signalfd(-1, &set, libc::SFD_NONBLOCK | libc::SFD_CLOEXEC))?;
+        // Safety: `signalfd` returned a new file descriptor.
+        Ok(unsafe { SignalFd::from_raw_fd(fd) })
+    }
+
+    /// Reads the information about a single pending signal.
+    ///
+    /// Returns a [`WouldBlock`] error if no signal is pending.
+    ///
+    /// [`WouldBlock`]: io::ErrorKind::WouldBlock
+    pub fn read_signal(&self) -> io::Result<SignalInfo> {
+        let mut info: libc::signalfd_siginfo = unsafe { mem::zeroed() };
+        // Safety: `signalfd_siginfo` is valid for any bit pattern.
+        let buf = unsafe {
+            std::slice::from_raw_parts_mut(
+                &mut info as *mut _ as *mut u8,
+                size_of::<libc::signalfd_siginfo>(),
+            )
+        };
+        let n = self.inner.do_io(|file| (&*file).read(buf))?;
+        // The kernel only returns whole `signalfd_siginfo` structures.
+        debug_assert_eq!(n, size_of::<libc::signalfd_siginfo>());
+        Ok(SignalInfo { info })
+    }
+}
+
+impl event::Source for SignalFd {
+    fn register(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        self.inner.register(registry, token, interests)
+    }
+
+    fn reregister(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        self.inner.reregister(registry, token, interests)
+    }
+
+    fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
+        self.inner.deregister(registry)
+    }
+}
+
+impl FromRawFd for SignalFd {
+    unsafe fn from_raw_fd(fd: RawFd) -> SignalFd {
+        SignalFd {
+            inner: IoSource::new(File::from_raw_fd(fd)),
+        }
+    }
+}
+
+impl AsRawFd for SignalFd {
+    fn as_raw_fd(&self) -> RawFd {
+        self.inner.as_raw_fd()
+    }
+}
+
+impl IntoRawFd for SignalFd {
+    fn into_raw_fd(self) -> RawFd {
+        self.inner.into_inner().into_raw_fd()
+    }
+}
+
+/// Information about a signal, returned by [`SignalFd::read_signal`].
+pub struct SignalInfo {
+    info: libc::signalfd_siginfo,
+}
+
+impl SignalInfo {
+    /// Returns the signal number, e.g. `SIGINT`.
+    pub fn signal(&self) -> libc::c_int {
+        self.info.ssi_signo as libc::c_int
+    }
+
+    /// Returns the signal code, e.g. `SI_USER` if the signal was sent using
+    /// `kill(2)`.
+    pub fn code(&self) -> libc::c_int {
+        self.info.ssi_code
+    }
+
+    /// Returns the process id of the sender.
+    pub fn pid(&self) -> u32 {
+        self.info.ssi_pid
+    }
+
+    /// Returns the real user id of the sender.
+    pub fn uid(&self) -> u32 {
+        self.info.ssi_uid
+    }
+}
+
+impl fmt::Debug for SignalInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SignalInfo")
+            .field("signal", &self.signal())
+            .field("code", &self.code())
+            .field("pid", &self.pid())
+            .field("uid", &self.uid())
+            .finish()
+    }
+}
@@ -1,5 +1,8 @@
 use crate::{event, poll, Interest, Registry, Token};
 
+#[cfg(all(debug_assertions, feature = "os-poll"))]
+use log::warn;
+
 use std::io;
 use std::os::unix::io::RawFd;
 
@@ -21,6 +24,25 @@ use std::os::unix::io::RawFd;
 /// [`Poll`]: ../struct.Poll.html
 /// [`Registry::register`]: ../struct.Registry.html#method.register
 ///
+/// # Standard input and output
+///
+/// Standard input, output and error (file descriptors 0, 1 and 2) can be
+/// registered like any other file descriptor, but there are some things to
+/// keep in mind:
+///
+/// * The file descriptor must be put in non-blocking mode before reading or
+///   writing, otherwise the read or write blocks the thread (e.g. waiting for
+///   a complete line on a terminal). Note that the non-blocking flag is shared
+///   with all processes using the same terminal or pipe, e.g. the parent
+///   shell, so it should be reset before the process exits. In debug builds a
+///   warning is logged when registering a terminal in blocking mode.
+/// * The file descriptor can be redirected to something that can't be
+///   registered, e.g. a regular file or `/dev/null` when using epoll, in which
+///   case registering returns an error. Programs should check for this and
+///   fall back to blocking I/O.
+/// * If standard input and output refer to the same terminal they share the
+///   non-blocking flag, so changing it for one affects the other.
+///
 /// # Examples
 ///
 /// Basic usage.
@@ -49,6 +71,40 @@ use std::os::unix::io::RawFd;
 /// # }
 /// ```
 ///
+/// Polling standard input for readable input.
+///
+#[cfg_attr(all(feature = "os-poll", feature = "os-ext"), doc = "```")]
+#[cfg_attr(not(all(feature = "os-poll", feature = "os-ext")), doc = "```ignore")]
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use mio::{Interest, Poll, Token};
+/// use mio::unix::SourceFd;
+///
+/// use std::io;
+///
+/// const STDIN: i32 = 0;
+///
+/// let poll = Poll::new()?;
+///
+/// // Put standard input in non-blocking mode.
+/// let flags = unsafe { libc::fcntl(STDIN, libc::F_GETFL) };
+/// if flags == -1 || unsafe { libc::fcntl(STDIN, libc::F_SETFL, flags | libc::O_NONBLOCK) } == -1 {
+///     return Err(io::Error::last_os_error().into());
+/// }
+///
+/// match poll.registry().register(&mut SourceFd(&STDIN), Token(0), Interest::READABLE) {
+///     Ok(()) => { /* Read from standard input once it's readable. */ }
+///     // Standard input is redirected to something that can't be registered,
+///     // e.g. a file, fall back to blocking I/O.
+///     Err(_) => {}
+/// }
+///
+/// // Restore the original flags.
+/// unsafe { libc::fcntl(STDIN, libc::F_SETFL, flags) };
+/// #     Ok(())
+/// # }
+/// ```
+///
 /// Implementing [`event::Source`] for a custom type backed by a [`RawFd`].
 ///
 #[cfg_attr(all(feature = "os-poll", feature = "os-ext"), doc = "```")]
@@ -92,6 +148,8 @@ impl<'a> event::Source for SourceFd<'a> {
         token: Token,
         interests: Interest,
     ) -> io::Result<()> {
+        #[cfg(all(debug_assertions, feature = "os-poll"))]
+        warn_blocking_tty(*self.0);
         poll::selector(registry).register(*self.0, token, interests)
     }
 
@@ -108,3 +166,18 @@ impl<'a> event::Source for SourceFd<'a> {
         poll::selector(registry).deregister(*self.0)
     }
 }
+
+/// Logs a warning if `fd` is a terminal in blocking mode, reading from it
+/// would block the thread instead of returning a `WouldBlock` error.
+#[cfg(all(debug_assertions, feature = "os-poll"))]
+fn warn_blocking_tty(fd: RawFd) {
+    if unsafe { libc::isatty(fd) } == 1 {
+        let flags = unsafe { libc::fcntl(fd, libc::F_GETFL) };
+        if flags != -1 && flags & libc::O_NONBLOCK == 0 {
+            warn!(
+                "registering terminal (fd {}) in blocking mode, reading from it will block",
+                fd
+            );
+        }
+    }
+}
@@ -459,3 +459,7 @@ pub fn accept(listener: &net::TcpListener) -> io::Result<(net::TcpStream, Socket
     // initialised.
     unsafe { to_socket_addr(addr.as_ptr()) }.map(|addr| (stream, addr))
 }
+
+pub(crate) fn bytes_available(stream: &net::TcpStream) -> io::Result<usize> {
+    crate::sys::unix::net::bytes_available(stream.as_raw_fd())
+}
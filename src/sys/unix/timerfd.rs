@@ -0,0 +1,81 @@
+use crate::sys::{event, Event, Selector};
+use crate::{Interest, Token};
+
+use std::fs::File;
+use std::io::{self, Read};
+use std::os::unix::io::{AsRawFd, FromRawFd};
+use std::time::Duration;
+use std::{cmp, ptr};
+
+/// One shot timer backed by `timerfd`, used by `Poll::wake_after`.
+///
+/// The timer is registered with the selector, so that `epoll_wait` returns
+/// once it expires without the caller having to limit the timeout. The
+/// expired timer returns a single readable event, its readiness is reset by
+/// reading from it (see `reset`).
+#[derive(Debug)]
+pub(crate) struct WakeTimer {
+    fd: File,
+    token: Token,
+}
+
+impl WakeTimer {
+    pub fn new(selector: &Selector, token: Token) -> io::Result<WakeTimer> {
+        syscall!(timerfd_create(
+            libc::CLOCK_MONOTONIC,
+            libc::TFD_CLOEXEC | libc::TFD_NONBLOCK
+        ))
+        .and_then(|fd| {
+            // Turn the file descriptor into a file first so we're ensured
+            // it's closed when dropped, e.g. when register below fails.
+            let file = unsafe { File::from_raw_fd(fd) };
+            selector
+                .register(fd, token, Interest::READABLE)
+                .map(|()| WakeTimer { fd: file, token })
+        })
+    }
+
+    /// Arms the timer to expire after `after`, replacing the previous expiry.
+    pub fn set(&self, after: Duration) -> io::Result<()> {
+        // Expired, but not yet reset, timers are still readable.
+        self.reset()?;
+        // An `it_value` of zero disarms the timer, expire as soon as possible
+        // instead.
+        let after = cmp::max(after, Duration::from_nanos(1));
+        let spec = libc::itimerspec {
+            it_interval: libc::timespec {
+                tv_sec: 0,
+                tv_nsec: 0,
+            },
+            it_value: libc::timespec {
+                tv_sec: cmp::min(after.as_secs(), libc::time_t::MAX as u64) as libc::time_t,
+                tv_nsec: after.subsec_nanos() as libc::c_long,
+            },
+        };
+        syscall!(timerfd_settime(
+            self.fd.as_raw_fd(),
+            0,
+            &spec,
+            ptr::null_mut()
+        ))
+        .map(|_| ())
+    }
+
+    /// Resets the readiness of an expired timer.
+    pub fn reset(&self) -> io::Result<()> {
+        let mut buf = [0; 8];
+        match (&self.fd).read(&mut buf) {
+            Ok(_) => Ok(()),
+            // The timer didn't expire (yet).
+            Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => Ok(()),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Returns `true` if `event` is the timer's event. The token the timer
+    /// is registered with must be reserved, so that it's not used by any
+    /// other event source.
+    pub fn is_event(&self, event: &Event) -> bool {
+        event::token(event) == self.token
+    }
+}
@@ -1,9 +1,11 @@
-use crate::sys::unix::net::{new_ip_socket, socket_addr};
+use crate::sys::unix::net::{
+    new_ip_socket, recv_msg, recv_msg_full, send_msg, socket_addr, to_socket_addr,
+};
 
-use std::io;
-use std::mem;
+use std::io::{self, IoSlice, IoSliceMut};
 use std::net::{self, SocketAddr};
 use std::os::unix::io::{AsRawFd, FromRawFd};
+use std::{mem, ptr};
 
 pub fn bind(addr: SocketAddr) -> io::Result<net::UdpSocket> {
     // Gives a warning for non Apple platforms.
@@ -37,3 +39,63 @@ pub(crate) fn only_v6(socket: &net::UdpSocket) -> io::Result<bool> {
 
     Ok(optval != 0)
 }
+
+pub(crate) fn bytes_available(socket: &net::UdpSocket) -> io::Result<usize> {
+    crate::sys::unix::net::bytes_available(socket.as_raw_fd())
+}
+
+pub(crate) fn send_to_vectored(
+    socket: &net::UdpSocket,
+    bufs: &[IoSlice<'_>],
+    target: SocketAddr,
+) -> io::Result<usize> {
+    let (raw_addr, raw_addr_length) = socket_addr(&target);
+    send_msg(socket.as_raw_fd(), bufs, raw_addr.as_ptr(), raw_addr_length)
+}
+
+pub(crate) fn recv_from_vectored(
+    socket: &net::UdpSocket,
+    bufs: &mut [IoSliceMut<'_>],
+) -> io::Result<(usize, SocketAddr)> {
+    let mut storage: libc::sockaddr_storage = unsafe { mem::zeroed() };
+    let mut length = mem::size_of_val(&storage) as libc::socklen_t;
+    let n = recv_msg(
+        socket.as_raw_fd(),
+        bufs,
+        &mut storage as *mut _ as *mut libc::sockaddr,
+        &mut length,
+    )?;
+    // This is safe because `recvmsg` initialised the address.
+    unsafe { to_socket_addr(&storage) }.map(|addr| (n, addr))
+}
+
+pub(crate) fn send_vectored(socket: &net::UdpSocket, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+    send_msg(socket.as_raw_fd(), bufs, ptr::null(), 0)
+}
+
+pub(crate) fn recv_vectored(
+    socket: &net::UdpSocket,
+    bufs: &mut [IoSliceMut<'_>],
+) -> io::Result<usize> {
+    recv_msg(socket.as_raw_fd(), bufs, ptr::null_mut(), &mut 0)
+}
+
+pub(crate) fn recv_from_full(
+    socket: &net::UdpSocket,
+    buf: &mut [u8],
+) -> io::Result<(usize, bool, SocketAddr)> {
+    let mut storage: libc::sockaddr_storage = unsafe { mem::zeroed() };
+    let mut length = mem::size_of_val(&storage) as libc::socklen_t;
+    let (n, truncated) = recv_msg_full(
+        socket.as_raw_fd(),
+        buf,
+        &mut storage as *mut _ as *mut libc::sockaddr,
+        &mut length,
+    )?;
+    // This is safe because `recvmsg` initialised the address.
+    unsafe { to_socket_addr(&storage) }.map(|addr| (n, truncated, addr))
+}
+
+pub(crate) fn recv_full(socket: &net::UdpSocket, buf: &mut [u8]) -> io::Result<(usize, bool)> {
+    recv_msg_full(socket.as_raw_fd(), buf, ptr::null_mut(), &mut 0)
+}
@@ -1,18 +1,29 @@
 use super::{socket_addr, SocketAddr};
-use crate::sys::unix::net::new_socket;
+use crate::net::BindOptions;
+use crate::sys::unix::net::{new_socket, recv_msg, recv_msg_full, send_msg};
 
-use std::io;
+use std::io::{self, IoSlice, IoSliceMut};
 use std::os::unix::io::{AsRawFd, FromRawFd};
 use std::os::unix::net;
 use std::path::Path;
+use std::ptr;
 
 pub(crate) fn bind(path: &Path) -> io::Result<net::UnixDatagram> {
+    bind_with(path, &BindOptions::default())
+}
+
+pub(crate) fn bind_with(path: &Path, options: &BindOptions) -> io::Result<net::UnixDatagram> {
+    if options.unlink_existing {
+        super::unlink_stale_socket(path)?;
+    }
+
     let fd = new_socket(libc::AF_UNIX, libc::SOCK_DGRAM)?;
     // Ensure the fd is closed.
     let socket = unsafe { net::UnixDatagram::from_raw_fd(fd) };
     let (sockaddr, socklen) = socket_addr(path)?;
     let sockaddr = &sockaddr as *const libc::sockaddr_un as *const _;
     syscall!(bind(fd, sockaddr, socklen))?;
+    super::set_mode(path, options.mode)?;
     Ok(socket)
 }
 
@@ -54,3 +65,63 @@ pub(crate) fn recv_from(
     })?;
     Ok((count as usize, socketaddr))
 }
+
+pub(crate) fn bytes_available(socket: &net::UnixDatagram) -> io::Result<usize> {
+    crate::sys::unix::net::bytes_available(socket.as_raw_fd())
+}
+
+pub(crate) fn send_to_vectored(
+    socket: &net::UnixDatagram,
+    bufs: &[IoSlice<'_>],
+    path: &Path,
+) -> io::Result<usize> {
+    let (sockaddr, socklen) = socket_addr(path)?;
+    let sockaddr = &sockaddr as *const libc::sockaddr_un as *const _;
+    send_msg(socket.as_raw_fd(), bufs, sockaddr, socklen)
+}
+
+pub(crate) fn recv_from_vectored(
+    socket: &net::UnixDatagram,
+    bufs: &mut [IoSliceMut<'_>],
+) -> io::Result<(usize, SocketAddr)> {
+    let mut count = 0;
+    let socketaddr = SocketAddr::new(|sockaddr, socklen| {
+        recv_msg(socket.as_raw_fd(), bufs, sockaddr, socklen).map(|c| {
+            count = c;
+            c as libc::c_int
+        })
+    })?;
+    Ok((count, socketaddr))
+}
+
+pub(crate) fn send_vectored(
+    socket: &net::UnixDatagram,
+    bufs: &[IoSlice<'_>],
+) -> io::Result<usize> {
+    send_msg(socket.as_raw_fd(), bufs, ptr::null(), 0)
+}
+
+pub(crate) fn recv_vectored(
+    socket: &net::UnixDatagram,
+    bufs: &mut [IoSliceMut<'_>],
+) -> io::Result<usize> {
+    recv_msg(socket.as_raw_fd(), bufs, ptr::null_mut(), &mut 0)
+}
+
+pub(crate) fn recv_from_full(
+    socket: &net::UnixDatagram,
+    buf: &mut [u8],
+) -> io::Result<(usize, bool, SocketAddr)> {
+    let mut res = (0, false);
+    let socketaddr = SocketAddr::new(|sockaddr, socklen| {
+        recv_msg_full(socket.as_raw_fd(), buf, sockaddr, socklen).map(|r| {
+            res = r;
+            r.0 as libc::c_int
+        })
+    })?;
+    Ok((res.0, res.1, socketaddr))
+}
+
+pub(crate) fn recv_full(socket: &net::UnixDatagram, buf: &mut [u8]) -> io::Result<(usize, bool)> {
+    recv_msg_full(socket.as_raw_fd(), buf, ptr::null_mut(), &mut 0)
+}
@@ -1,5 +1,5 @@
 use super::socket_addr;
-use crate::net::{SocketAddr, UnixStream};
+use crate::net::{BindOptions, SocketAddr, UnixStream};
 use crate::sys::unix::net::new_socket;
 use std::os::unix::io::{AsRawFd, FromRawFd};
 use std::os::unix::net;
@@ -7,11 +7,22 @@ use std::path::Path;
 use std::{io, mem};
 
 pub(crate) fn bind(path: &Path) -> io::Result<net::UnixListener> {
+    bind_with(path, &BindOptions::default())
+}
+
+pub(crate) fn bind_with(path: &Path, options: &BindOptions) -> io::Result<net::UnixListener> {
+    if options.unlink_existing {
+        super::unlink_stale_socket(path)?;
+    }
+
     let socket = new_socket(libc::AF_UNIX, libc::SOCK_STREAM)?;
     let (sockaddr, socklen) = socket_addr(path)?;
     let sockaddr = &sockaddr as *const libc::sockaddr_un as *const libc::sockaddr;
 
     syscall!(bind(socket, sockaddr, socklen))
+        // Set the mode before listening, so that no connections can be made
+        // with the wrong permissions.
+        .and_then(|_| super::set_mode(path, options.mode))
         .and_then(|_| syscall!(listen(socket, 1024)))
         .map_err(|err| {
             // Close the socket if we hit an error, ignoring the error from
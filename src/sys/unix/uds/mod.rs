@@ -15,7 +15,9 @@ pub(in crate::sys) fn path_offset(sockaddr: &libc::sockaddr_un) -> usize {
 
 cfg_os_poll! {
     use std::cmp::Ordering;
+    use std::fs;
     use std::os::unix::ffi::OsStrExt;
+    use std::os::unix::fs::{FileTypeExt, PermissionsExt};
     use std::os::unix::io::{RawFd, FromRawFd};
     use std::path::Path;
     use std::{io, mem};
@@ -74,6 +76,39 @@ cfg_os_poll! {
         Ok((sockaddr, socklen as libc::socklen_t))
     }
 
+    /// Returns `true` if `path` is a pathname, i.e. not abstract or unnamed.
+    fn is_pathname(path: &Path) -> bool {
+        !matches!(path.as_os_str().as_bytes().first(), Some(&0) | None)
+    }
+
+    /// Removes an existing socket at `path`, returns an error if `path` exists
+    /// but is not a socket.
+    pub(in crate::sys) fn unlink_stale_socket(path: &Path) -> io::Result<()> {
+        if !is_pathname(path) {
+            return Ok(());
+        }
+
+        match fs::symlink_metadata(path) {
+            Ok(metadata) if metadata.file_type().is_socket() => fs::remove_file(path),
+            Ok(_) => Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                "path exists and is not a socket",
+            )),
+            Err(ref err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Sets the file mode of the socket file at `path`, if `mode` is `Some`.
+    pub(in crate::sys) fn set_mode(path: &Path, mode: Option<u32>) -> io::Result<()> {
+        match mode {
+            Some(mode) if is_pathname(path) => {
+                fs::set_permissions(path, fs::Permissions::from_mode(mode))
+            }
+            _ => Ok(()),
+        }
+    }
+
     fn pair<T>(flags: libc::c_int) -> io::Result<(T, T)>
         where T: FromRawFd,
     {
@@ -37,3 +37,7 @@ pub(crate) fn local_addr(socket: &net::UnixStream) -> io::Result<SocketAddr> {
 pub(crate) fn peer_addr(socket: &net::UnixStream) -> io::Result<SocketAddr> {
     super::peer_addr(socket.as_raw_fd())
 }
+
+pub(crate) fn bytes_available(socket: &net::UnixStream) -> io::Result<usize> {
+    crate::sys::unix::net::bytes_available(socket.as_raw_fd())
+}
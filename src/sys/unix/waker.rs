@@ -85,7 +85,7 @@ mod kqueue {
             selector.try_clone().and_then(|selector| {
                 selector
                     .setup_waker(token)
-                    .map(|()| Waker { selector, token })
+                    .map(|token| Waker { selector, token })
             })
         }
 
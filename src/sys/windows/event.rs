@@ -79,6 +79,11 @@ pub fn is_priority(event: &Event) -> bool {
     event.flags & afd::POLL_RECEIVE_EXPEDITED != 0
 }
 
+pub fn is_band_writable(_: &Event) -> bool {
+    // Not supported.
+    false
+}
+
 pub fn is_aio(_: &Event) -> bool {
     // Not supported.
     false
@@ -153,6 +158,10 @@ impl Events {
         self.events.get(idx)
     }
 
+    pub fn push(&mut self, event: Event) {
+        self.events.push(event);
+    }
+
     pub fn clear(&mut self) {
         self.events.clear();
         for status in self.statuses.iter_mut() {
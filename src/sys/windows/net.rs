@@ -8,7 +8,7 @@ use winapi::shared::inaddr::{in_addr_S_un, IN_ADDR};
 use winapi::shared::in6addr::{in6_addr_u, IN6_ADDR};
 use winapi::shared::ws2def::{AF_INET, AF_INET6, ADDRESS_FAMILY, SOCKADDR, SOCKADDR_IN};
 use winapi::shared::ws2ipdef::{SOCKADDR_IN6_LH, SOCKADDR_IN6_LH_u};
-use winapi::um::winsock2::{ioctlsocket, socket, FIONBIO, INVALID_SOCKET, SOCKET};
+use winapi::um::winsock2::{ioctlsocket, socket, FIONBIO, FIONREAD, INVALID_SOCKET, SOCKET};
 
 /// Initialise the network stack for Windows.
 pub(crate) fn init() {
@@ -106,3 +106,15 @@ pub(crate) fn socket_addr(addr: &SocketAddr) -> (SocketAddrCRepr, c_int) {
         }
     }
 }
+
+/// Returns the number of bytes that can be read from `socket` without
+/// blocking, using `ioctlsocket(FIONREAD)`.
+pub(crate) fn bytes_available(socket: SOCKET) -> io::Result<usize> {
+    let mut available = 0;
+    syscall!(
+        ioctlsocket(socket, FIONREAD, &mut available),
+        PartialEq::ne,
+        0
+    )
+    .map(|_| available as usize)
+}
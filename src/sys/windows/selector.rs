@@ -1,6 +1,7 @@
 use super::afd::{self, Afd, AfdPollInfo};
 use super::io_status_block::IoStatusBlock;
 use super::Event;
+use crate::features::{Capabilities, Triggering};
 use crate::sys::Events;
 
 cfg_net! {
@@ -333,6 +334,15 @@ pub struct Selector {
 }
 
 impl Selector {
+    pub const CAPABILITIES: Capabilities = Capabilities {
+        triggering: Triggering::Edge,
+        oneshot: false,
+        waker: true,
+        priority: false,
+        // Only sockets are supported.
+        regular_files: false,
+    };
+
     pub fn new() -> io::Result<Selector> {
         SelectorInner::new().map(|inner| {
             #[cfg(debug_assertions)]
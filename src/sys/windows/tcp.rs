@@ -4,7 +4,7 @@ use std::mem::size_of;
 use std::net::{self, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
 use std::time::Duration;
 use std::ptr;
-use std::os::windows::io::FromRawSocket;
+use std::os::windows::io::{AsRawSocket, FromRawSocket};
 use std::os::windows::raw::SOCKET as StdSocket; // winapi uses usize, stdlib uses u32/u64.
 
 use winapi::ctypes::{c_char, c_int, c_ushort, c_ulong};
@@ -323,3 +323,7 @@ pub(crate) fn accept(listener: &net::TcpListener) -> io::Result<(net::TcpStream,
     // https://docs.microsoft.com/en-us/windows/win32/api/winsock2/nf-winsock2-accept#remarks.
     listener.accept()
 }
+
+pub(crate) fn bytes_available(stream: &net::TcpStream) -> io::Result<usize> {
+    crate::sys::windows::net::bytes_available(stream.as_raw_socket() as SOCKET)
+}
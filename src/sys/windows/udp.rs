@@ -51,3 +51,7 @@ pub(crate) fn only_v6(socket: &net::UdpSocket) -> io::Result<bool> {
     let optval = unsafe { optval.assume_init() };
     Ok(optval != 0)
 }
+
+pub(crate) fn bytes_available(socket: &net::UdpSocket) -> io::Result<usize> {
+    crate::sys::windows::net::bytes_available(socket.as_raw_socket() as usize)
+}
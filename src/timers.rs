@@ -0,0 +1,315 @@
+//! Userspace timers.
+//!
+//! See [`TimerQueue`] for documentation.
+
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+use std::io;
+use std::time::{Duration, Instant};
+
+use crate::{poll, Events, Poll, Token};
+
+/// A queue of timers, without requiring a file descriptor (or other OS
+/// resource) per timer.
+///
+/// Timers are added with a deadline and a [`Token`], once the deadline has
+/// passed the token is returned by [`TimerQueue::expired`]. The queue is
+/// backed by a binary heap, adding and cancelling a timer are `O(log n)`
+/// operations.
+///
+/// To wait for both readiness events and timers see [`poll_with_timers`].
+///
+/// # Examples
+///
+/// ```
+/// use std::time::{Duration, Instant};
+///
+/// use mio::Token;
+/// use mio::timers::TimerQueue;
+///
+/// let mut timers = TimerQueue::new();
+/// let now = Instant::now();
+///
+/// timers.add(now + Duration::from_millis(20), Token(0));
+/// let handle = timers.add(now + Duration::from_millis(10), Token(1));
+/// assert_eq!(timers.next_deadline(), Some(now + Duration::from_millis(10)));
+///
+/// // Cancel the second timer.
+/// assert!(timers.cancel(handle));
+///
+/// let later = now + Duration::from_millis(30);
+/// assert_eq!(timers.expired(later).collect::<Vec<_>>(), vec![Token(0)]);
+/// assert!(timers.is_empty());
+/// ```
+#[derive(Debug, Default)]
+pub struct TimerQueue {
+    heap: BinaryHeap<Reverse<Entry>>,
+    /// Generation for each slot, used by `TimerHandle` and `Entry`.
+    slots: Vec<Slot>,
+    /// Indices into `slots` that can be reused.
+    free: Vec<usize>,
+    /// Number of live timers.
+    len: usize,
+}
+
+/// Handle to a timer in a [`TimerQueue`], used to [cancel] it.
+///
+/// [cancel]: TimerQueue::cancel
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TimerHandle {
+    slot: usize,
+    generation: u64,
+}
+
+#[derive(Debug, Default)]
+struct Slot {
+    /// Incremented every time a timer in this slot expires or is cancelled.
+    generation: u64,
+}
+
+#[derive(Debug)]
+struct Entry {
+    deadline: Instant,
+    slot: usize,
+    generation: u64,
+    token: Token,
+}
+
+// Only ordered by deadline, ties are broken by the slot and generation to get
+// a consistent ordering.
+impl Ord for Entry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.deadline
+            .cmp(&other.deadline)
+            .then(self.slot.cmp(&other.slot))
+            .then(self.generation.cmp(&other.generation))
+    }
+}
+
+impl PartialOrd for Entry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Entry {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Entry {}
+
+impl TimerQueue {
+    /// Create an empty `TimerQueue`.
+    pub fn new() -> TimerQueue {
+        TimerQueue::default()
+    }
+
+    /// Returns the number of timers in the queue.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the queue holds no timers.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Add a timer that expires at `deadline`, returning `token` from
+    /// [`TimerQueue::expired`] once it does.
+    pub fn add(&mut self, deadline: Instant, token: Token) -> TimerHandle {
+        let slot = match self.free.pop() {
+            Some(slot) => slot,
+            None => {
+                self.slots.push(Slot::default());
+                self.slots.len() - 1
+            }
+        };
+        let generation = self.slots[slot].generation;
+        self.heap.push(Reverse(Entry {
+            deadline,
+            slot,
+            generation,
+            token,
+        }));
+        self.len += 1;
+        TimerHandle { slot, generation }
+    }
+
+    /// Cancel the timer referred to by `handle`.
+    ///
+    /// Returns `false` if the timer already expired or was cancelled.
+    pub fn cancel(&mut self, handle: TimerHandle) -> bool {
+        match self.slots.get(handle.slot) {
+            Some(slot) if slot.generation == handle.generation => {}
+            _ => return false,
+        }
+
+        self.release(handle.slot);
+        // The entry in the heap is removed lazily. To not keep cancelled entries
+        // around forever the heap is rebuilt once they make up the majority.
+        if self.heap.len() > 2 * self.len + 16 {
+            let slots = &self.slots;
+            let heap = std::mem::take(&mut self.heap);
+            self.heap = heap
+                .into_iter()
+                .filter(|entry| is_live(slots, &entry.0))
+                .collect();
+        }
+        self.remove_cancelled();
+        true
+    }
+
+    /// Returns the deadline of the first timer to expire, if any.
+    pub fn next_deadline(&self) -> Option<Instant> {
+        // `remove_cancelled` ensures the first entry is always live.
+        self.heap.peek().map(|entry| entry.0.deadline)
+    }
+
+    /// Returns an iterator that removes and returns the tokens of all timers
+    /// that expired at `now`, in order of their deadlines.
+    ///
+    /// Timers not consumed from the iterator remain in the queue.
+    pub fn expired(&mut self, now: Instant) -> Expired<'_> {
+        Expired { timers: self, now }
+    }
+
+    /// Mark `slot` as unused, invalidating the corresponding entry and handle.
+    fn release(&mut self, slot: usize) {
+        self.slots[slot].generation += 1;
+        self.free.push(slot);
+        self.len -= 1;
+    }
+
+    /// Remove cancelled entries from the top of the heap.
+    fn remove_cancelled(&mut self) {
+        while let Some(entry) = self.heap.peek() {
+            if is_live(&self.slots, &entry.0) {
+                break;
+            }
+            let _ = self.heap.pop();
+        }
+    }
+}
+
+fn is_live(slots: &[Slot], entry: &Entry) -> bool {
+    slots[entry.slot].generation == entry.generation
+}
+
+/// Iterator over expired timers.
+///
+/// This struct is created by the [`expired`] method on [`TimerQueue`].
+///
+/// [`expired`]: TimerQueue::expired
+#[derive(Debug)]
+pub struct Expired<'a> {
+    timers: &'a mut TimerQueue,
+    now: Instant,
+}
+
+impl<'a> Iterator for Expired<'a> {
+    type Item = Token;
+
+    fn next(&mut self) -> Option<Token> {
+        match self.timers.heap.peek() {
+            Some(entry) if entry.0.deadline <= self.now => {}
+            _ => return None,
+        }
+        let entry = self.timers.heap.pop().unwrap().0;
+        self.timers.release(entry.slot);
+        self.timers.remove_cancelled();
+        Some(entry.token)
+    }
+}
+
+/// Returns the timeout to pass to [`Poll::poll`] to not block past the first
+/// of `deadlines`.
+///
+/// Returns `None` (block until an event arrives) if `deadlines` is empty and
+/// a zero timeout if a deadline already passed at `now`. Otherwise the
+/// remaining time is rounded up to the next millisecond, most selectors
+/// truncate the timeout to milliseconds and would otherwise return just
+/// before the deadline.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::{Duration, Instant};
+///
+/// use mio::timers::poll_timeout;
+///
+/// let now = Instant::now();
+/// let deadlines = vec![now + Duration::from_millis(20), now + Duration::from_millis(10)];
+/// assert_eq!(poll_timeout(deadlines.into_iter(), now), Some(Duration::from_millis(10)));
+/// assert_eq!(poll_timeout(std::iter::empty(), now), None);
+/// ```
+pub fn poll_timeout(deadlines: impl Iterator<Item = Instant>, now: Instant) -> Option<Duration> {
+    let deadline = deadlines.min()?;
+    let remaining = deadline.checked_duration_since(now).unwrap_or_default();
+    Some(round_up_millis(remaining))
+}
+
+/// Rounds `duration` up to a whole number of milliseconds.
+fn round_up_millis(duration: Duration) -> Duration {
+    match duration.subsec_nanos() % 1_000_000 {
+        0 => duration,
+        nanos => duration
+            .checked_add(Duration::from_nanos(u64::from(1_000_000 - nanos)))
+            .unwrap_or(duration),
+    }
+}
+
+/// Wait for readiness events or expired timers.
+///
+/// This calls [`Poll::poll`], limiting the timeout so that it won't block past
+/// the deadline of the first timer in `timers`. After polling all expired
+/// timers are removed from `timers` and their tokens are stored in `expired`.
+/// Like `events`, `expired` is cleared first.
+///
+/// Note that the timeout used is rounded up to the precision of the system
+/// selector (usually 1 millisecond), so timers may expire slightly later
+/// than their deadline.
+///
+/// # Examples
+///
+#[cfg_attr(feature = "os-poll", doc = "```")]
+#[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use std::time::{Duration, Instant};
+///
+/// use mio::{Events, Poll, Token};
+/// use mio::timers::{poll_with_timers, TimerQueue};
+///
+/// let mut poll = Poll::new()?;
+/// let mut events = Events::with_capacity(128);
+/// let mut timers = TimerQueue::new();
+/// let mut expired = Vec::new();
+///
+/// timers.add(Instant::now() + Duration::from_millis(10), Token(0));
+///
+/// // Returns once the timer expired.
+/// poll_with_timers(&mut poll, &mut events, &mut timers, None, &mut expired)?;
+/// assert_eq!(expired, vec![Token(0)]);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn poll_with_timers(
+    poll: &mut Poll,
+    events: &mut Events,
+    timers: &mut TimerQueue,
+    timeout: Option<Duration>,
+    expired: &mut Vec<Token>,
+) -> io::Result<()> {
+    expired.clear();
+
+    let timeout = match timers.next_deadline() {
+        Some(deadline) => poll::timeout_until(deadline, timeout),
+        None => timeout,
+    };
+    poll.poll(events, timeout)?;
+
+    expired.extend(timers.expired(Instant::now()));
+    Ok(())
+}
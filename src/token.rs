@@ -5,8 +5,20 @@
 ///
 /// See [`Poll`] for more documentation on polling.
 ///
+/// Mio reserves a single value, `Token(usize::MAX)`, which it uses internally
+/// (e.g. for the timer of [`Poll::wake_after`]). Registering an event source
+/// or creating a [`Waker`] with a reserved token returns an `InvalidInput`
+/// error, see [`Token::is_reserved`]. All other values, up to and including
+/// [`Token::MAX_USABLE`], are returned unchanged in events on all platforms,
+/// including 32 bit platforms: all selectors store the token in a field at
+/// least as wide as `usize`.
+///
 /// [`event::Source`]: ./event/trait.Source.html
 /// [`Poll`]: struct.Poll.html
+/// [`Poll::wake_after`]: struct.Poll.html#method.wake_after
+/// [`Waker`]: struct.Waker.html
+/// [`Token::is_reserved`]: #method.is_reserved
+/// [`Token::MAX_USABLE`]: #associatedconstant.MAX_USABLE
 /// [`Registry::register`]: struct.Registry.html#method.register
 /// [`Registry::reregister`]: struct.Registry.html#method.reregister
 ///
@@ -131,6 +143,22 @@
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Token(pub usize);
 
+impl Token {
+    /// The largest token that can be used to register event sources and
+    /// create [`Waker`]s, all larger values are reserved.
+    ///
+    /// [`Waker`]: struct.Waker.html
+    pub const MAX_USABLE: Token = Token(usize::MAX - 1);
+
+    /// Returns `true` if the token is reserved for internal use by Mio, i.e.
+    /// it's larger than [`Token::MAX_USABLE`].
+    ///
+    /// [`Token::MAX_USABLE`]: #associatedconstant.MAX_USABLE
+    pub const fn is_reserved(&self) -> bool {
+        self.0 > Token::MAX_USABLE.0
+    }
+}
+
 impl From<Token> for usize {
     fn from(val: Token) -> usize {
         val.0
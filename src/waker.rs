@@ -81,7 +81,12 @@ pub struct Waker {
 
 impl Waker {
     /// Create a new `Waker`.
+    ///
+    /// Returns an `InvalidInput` error if `token` is [reserved].
+    ///
+    /// [reserved]: struct.Token.html#method.is_reserved
     pub fn new(registry: &Registry, token: Token) -> io::Result<Waker> {
+        poll::check_token(token)?;
         #[cfg(debug_assertions)]
         registry.register_waker();
         sys::Waker::new(poll::selector(&registry), token).map(|inner| Waker { inner })
@@ -89,6 +94,9 @@ impl Waker {
 
     /// Wake up the [`Poll`] associated with this `Waker`.
     ///
+    /// If the `Poll` instance was already dropped this returns `Ok(())`, but
+    /// has no effect.
+    ///
     /// [`Poll`]: struct.Poll.html
     pub fn wake(&self) -> io::Result<()> {
         self.inner.wake()
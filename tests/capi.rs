@@ -0,0 +1,228 @@
+#![cfg(all(unix, feature = "capi"))]
+
+use std::fs;
+use std::mem::size_of;
+use std::os::raw::c_int;
+use std::path::Path;
+use std::process::Command;
+use std::ptr;
+
+use mio::capi::*;
+
+mod util;
+use util::init;
+
+const TOKEN: u64 = 7;
+
+fn pipe() -> (c_int, c_int) {
+    let mut fds = [-1; 2];
+    assert_eq!(unsafe { libc::pipe(fds.as_mut_ptr()) }, 0);
+    for fd in &fds {
+        assert_eq!(
+            unsafe { libc::fcntl(*fd, libc::F_SETFL, libc::O_NONBLOCK) },
+            0
+        );
+    }
+    (fds[0], fds[1])
+}
+
+#[test]
+fn poll_events() {
+    init();
+    let mut poll = ptr::null_mut();
+    assert_eq!(unsafe { mio_poll_new(&mut poll) }, 0);
+    assert!(!poll.is_null());
+
+    let (receiver, sender) = pipe();
+    assert_eq!(
+        unsafe { mio_poll_register(poll, receiver, TOKEN, MIO_READABLE) },
+        0
+    );
+
+    let mut events = [MioEvent {
+        token: 0,
+        readiness: 0,
+    }; 4];
+    let n = unsafe { mio_poll_poll(poll, events.as_mut_ptr(), events.len(), 0) };
+    assert_eq!(n, 0);
+
+    assert_eq!(unsafe { libc::write(sender, b"!".as_ptr().cast(), 1) }, 1);
+    let n = unsafe { mio_poll_poll(poll, events.as_mut_ptr(), events.len(), 500) };
+    assert_eq!(n, 1);
+    assert_eq!(events[0].token, TOKEN);
+    assert_eq!(events[0].readiness & MIO_READABLE, MIO_READABLE);
+
+    assert_eq!(unsafe { mio_poll_deregister(poll, receiver) }, 0);
+    unsafe {
+        mio_poll_free(poll);
+        libc::close(receiver);
+        libc::close(sender);
+    }
+}
+
+#[test]
+fn poll_events_left_over() {
+    init();
+    let mut poll = ptr::null_mut();
+    assert_eq!(unsafe { mio_poll_new(&mut poll) }, 0);
+
+    let pipes: Vec<(c_int, c_int)> = (0..3).map(|_| pipe()).collect();
+    for (i, (receiver, sender)) in pipes.iter().enumerate() {
+        assert_eq!(
+            unsafe { mio_poll_register(poll, *receiver, i as u64, MIO_READABLE) },
+            0
+        );
+        assert_eq!(unsafe { libc::write(*sender, b"!".as_ptr().cast(), 1) }, 1);
+    }
+
+    // Events that don't fit are returned by the following calls.
+    let mut events = [MioEvent {
+        token: 0,
+        readiness: 0,
+    }; 1];
+    let mut tokens = Vec::new();
+    for _ in 0..3 {
+        let n = unsafe { mio_poll_poll(poll, events.as_mut_ptr(), events.len(), 500) };
+        assert_eq!(n, 1);
+        tokens.push(events[0].token);
+    }
+    tokens.sort_unstable();
+    assert_eq!(tokens, vec![0, 1, 2]);
+    // All events are returned, the readiness was consumed.
+    let n = unsafe { mio_poll_poll(poll, events.as_mut_ptr(), events.len(), 0) };
+    assert_eq!(n, 0);
+
+    // Large capacities are fine, at most the buffer's capacity is used.
+    let mut events = vec![
+        MioEvent {
+            token: 0,
+            readiness: 0,
+        };
+        4096
+    ];
+    let n = unsafe { mio_poll_poll(poll, events.as_mut_ptr(), events.len(), 0) };
+    assert_eq!(n, 0);
+
+    unsafe {
+        mio_poll_free(poll);
+        for (receiver, sender) in pipes {
+            libc::close(receiver);
+            libc::close(sender);
+        }
+    }
+}
+
+#[test]
+fn errors() {
+    init();
+    assert_eq!(unsafe { mio_poll_new(ptr::null_mut()) }, -libc::EINVAL);
+    // Must not crash.
+    unsafe { mio_poll_free(ptr::null_mut()) };
+
+    let mut poll = ptr::null_mut();
+    assert_eq!(unsafe { mio_poll_new(&mut poll) }, 0);
+
+    let (receiver, sender) = pipe();
+    // Empty and unknown interests.
+    assert_eq!(
+        unsafe { mio_poll_register(poll, receiver, TOKEN, 0) },
+        -libc::EINVAL
+    );
+    assert_eq!(
+        unsafe { mio_poll_register(poll, receiver, TOKEN, MIO_ERROR) },
+        -libc::EINVAL
+    );
+    assert_eq!(
+        unsafe { mio_poll_register(ptr::null_mut(), receiver, TOKEN, MIO_READABLE) },
+        -libc::EINVAL
+    );
+    // Errors from the OS keep their error code.
+    assert_eq!(
+        unsafe { mio_poll_register(poll, -1, TOKEN, MIO_READABLE) },
+        -libc::EBADF
+    );
+    assert_eq!(
+        unsafe { mio_poll_deregister(poll, receiver) },
+        if cfg!(any(target_os = "android", target_os = "linux")) {
+            -libc::ENOENT
+        } else {
+            // kqueue doesn't report an error if nothing is registered.
+            0
+        }
+    );
+
+    let mut events = [MioEvent {
+        token: 0,
+        readiness: 0,
+    }; 1];
+    assert_eq!(
+        unsafe { mio_poll_poll(poll, events.as_mut_ptr(), 0, 0) },
+        -libc::EINVAL
+    );
+    assert_eq!(
+        unsafe { mio_poll_poll(poll, ptr::null_mut(), 1, 0) },
+        -libc::EINVAL
+    );
+
+    unsafe {
+        mio_poll_free(poll);
+        libc::close(receiver);
+        libc::close(sender);
+    }
+}
+
+/// Checks that `include/mio.h` matches the Rust definitions.
+#[test]
+fn header() {
+    let header_path = Path::new(env!("CARGO_MANIFEST_DIR")).join("include/mio.h");
+    let header = fs::read_to_string(&header_path).unwrap();
+
+    let constants = [
+        ("MIO_READABLE", MIO_READABLE),
+        ("MIO_WRITABLE", MIO_WRITABLE),
+        ("MIO_ERROR", MIO_ERROR),
+        ("MIO_READ_CLOSED", MIO_READ_CLOSED),
+        ("MIO_WRITE_CLOSED", MIO_WRITE_CLOSED),
+        ("MIO_PRIORITY", MIO_PRIORITY),
+    ];
+    for (name, value) in constants.iter() {
+        let define = format!("#define {} {}\n", name, value);
+        assert!(header.contains(&define), "missing `{}`", define.trim());
+    }
+    for function in &[
+        "mio_poll_new",
+        "mio_poll_free",
+        "mio_poll_register",
+        "mio_poll_deregister",
+        "mio_poll_poll",
+    ] {
+        assert!(header.contains(&format!(" {}(", function)));
+    }
+
+    // Check the layout of `MioEvent` using a C compiler, if one is available.
+    let dir = std::env::temp_dir().join(format!("mio_capi_header_{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let source = dir.join("check.c");
+    fs::write(
+        &source,
+        format!(
+            "#include \"{}\"\n\
+             typedef char size_check[sizeof(MioEvent) == {} ? 1 : -1];\n\
+             typedef char token_check[offsetof(MioEvent, readiness) == {} ? 1 : -1];\n",
+            header_path.display(),
+            size_of::<MioEvent>(),
+            size_of::<u64>(),
+        ),
+    )
+    .unwrap();
+    match Command::new("cc")
+        .arg("-fsyntax-only")
+        .arg("-Werror")
+        .arg(&source)
+        .status()
+    {
+        Ok(status) => assert!(status.success(), "compiling `include/mio.h` failed"),
+        Err(err) => eprintln!("skipping C compiler check: {}", err),
+    }
+    let _ = fs::remove_dir_all(&dir);
+}
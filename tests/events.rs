@@ -1,9 +1,9 @@
 #![cfg(all(feature = "os-poll", feature = "net"))]
 
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use mio::net::TcpStream;
-use mio::{event, Token, Waker};
+use mio::{event, Events, Poll, Token, Waker};
 
 mod util;
 use util::init_with_poll;
@@ -40,3 +40,315 @@ fn events_all() {
     events.clear();
     assert!(events.is_empty());
 }
+
+#[test]
+fn events_ready_at() {
+    let (mut poll, mut events) = init_with_poll();
+    assert!(events.ready_at().is_none());
+
+    let waker = Waker::new(poll.registry(), WAKE_TOKEN).unwrap();
+    waker.wake().expect("unable to wake");
+
+    let before = Instant::now();
+    poll.poll(&mut events, Some(Duration::from_millis(100)))
+        .unwrap();
+    let after = Instant::now();
+
+    assert!(!events.is_empty());
+    let ready_at = events.ready_at().expect("missing ready timestamp");
+    assert!(before <= ready_at && ready_at <= after);
+
+    events.clear();
+    assert!(events.ready_at().is_none());
+}
+
+#[test]
+fn events_extend() {
+    let (mut poll1, mut events) = init_with_poll();
+    let mut poll2 = Poll::new().unwrap();
+    let mut all_events = Events::with_capacity(16);
+
+    let waker1 = Waker::new(poll1.registry(), Token(1)).unwrap();
+    let waker2 = Waker::new(poll2.registry(), Token(2)).unwrap();
+    waker1.wake().expect("unable to wake");
+    waker2.wake().expect("unable to wake");
+
+    poll1
+        .poll(&mut events, Some(Duration::from_millis(100)))
+        .unwrap();
+    all_events.extend(&events);
+    poll2
+        .poll(&mut events, Some(Duration::from_millis(100)))
+        .unwrap();
+    all_events.extend(events.iter().cloned());
+
+    let tokens: Vec<Token> = all_events.iter().map(|event| event.token()).collect();
+    assert_eq!(tokens, vec![Token(1), Token(2)]);
+    assert!(all_events.iter().all(|event| event.is_readable()));
+}
+
+#[test]
+fn events_extend_beyond_capacity() {
+    let (mut poll, mut events) = init_with_poll();
+    let mut all_events = Events::with_capacity(1);
+
+    let waker = Waker::new(poll.registry(), Token(1)).unwrap();
+    waker.wake().expect("unable to wake");
+    poll.poll(&mut events, Some(Duration::from_millis(100)))
+        .unwrap();
+    let ready_at = events.ready_at();
+    assert!(ready_at.is_some());
+
+    all_events.extend(&events);
+    all_events.extend(&events);
+    all_events.extend(&events);
+    assert_eq!(all_events.len(), 3);
+    assert_eq!(all_events.capacity(), 1);
+    // Extending doesn't change the metadata of the last poll.
+    assert!(all_events.ready_at().is_none());
+    assert!(!all_events.deadline_expired());
+
+    // Polling replaces all events and returns at most `capacity` events.
+    let mut sockets = Vec::new();
+    for token in 10..13 {
+        let mut socket = mio::net::UdpSocket::bind(util::any_local_address()).unwrap();
+        poll.registry()
+            .register(&mut socket, Token(token), mio::Interest::WRITABLE)
+            .unwrap();
+        sockets.push(socket);
+    }
+    poll.poll(&mut all_events, Some(Duration::from_millis(100)))
+        .unwrap();
+    assert_eq!(all_events.len(), 1);
+    assert_eq!(all_events.capacity(), 1);
+    assert!(all_events.ready_at().is_some());
+}
+
+#[test]
+fn events_ready_tokens() {
+    use mio::net::UdpSocket;
+    use mio::Interest;
+    use std::collections::HashSet;
+
+    let (mut poll, mut events) = init_with_poll();
+    let mut sockets = Vec::new();
+    for token in 0..3 {
+        let mut socket = UdpSocket::bind(util::any_local_address()).unwrap();
+        poll.registry()
+            .register(&mut socket, Token(token), Interest::WRITABLE)
+            .unwrap();
+        sockets.push(socket);
+    }
+
+    poll.poll(&mut events, Some(Duration::from_millis(100)))
+        .unwrap();
+
+    assert_eq!(events.ready_tokens().count(), events.iter().count());
+    let tokens: HashSet<Token> = events.ready_tokens().collect();
+    let expected: HashSet<Token> = (0..3).map(Token).collect();
+    assert_eq!(tokens, expected);
+}
+
+#[test]
+fn events_index_and_reverse_iteration() {
+    use mio::net::UdpSocket;
+    use mio::Interest;
+
+    let (mut poll, mut events) = init_with_poll();
+    assert_eq!(events.len(), 0);
+    assert!(events.get(0).is_none());
+    assert!(events.iter().next_back().is_none());
+
+    let mut sockets = Vec::new();
+    for token in 0..4 {
+        let mut socket = UdpSocket::bind(util::any_local_address()).unwrap();
+        poll.registry()
+            .register(&mut socket, Token(token), Interest::WRITABLE)
+            .unwrap();
+        sockets.push(socket);
+    }
+
+    poll.poll(&mut events, Some(Duration::from_millis(100)))
+        .unwrap();
+
+    let n = events.len();
+    assert!(n >= 4, "{:?}", events);
+    assert_eq!(events.iter().len(), n);
+    assert!(events.get(n).is_none());
+
+    // Iteration order matches index order.
+    let tokens: Vec<Token> = events.iter().map(|event| event.token()).collect();
+    let indexed: Vec<Token> = (0..n).map(|i| events.get(i).unwrap().token()).collect();
+    assert_eq!(tokens, indexed);
+
+    let mut reversed: Vec<Token> = events.iter().rev().map(|event| event.token()).collect();
+    reversed.reverse();
+    assert_eq!(tokens, reversed);
+
+    // Iterating from both ends meets in the middle.
+    let mut iter = events.iter();
+    assert_eq!(iter.next().unwrap().token(), tokens[0]);
+    assert_eq!(iter.next_back().unwrap().token(), tokens[n - 1]);
+    assert_eq!(iter.len(), n - 2);
+    assert_eq!(iter.by_ref().count(), n - 2);
+    assert!(iter.next().is_none());
+    assert!(iter.next_back().is_none());
+}
+
+#[test]
+fn events_reused_no_stale_events() {
+    use mio::net::UdpSocket;
+    use mio::Interest;
+
+    let (mut poll, mut events) = init_with_poll();
+    let mut sockets = Vec::new();
+    for token in 0..8 {
+        let mut socket = UdpSocket::bind(util::any_local_address()).unwrap();
+        poll.registry()
+            .register(&mut socket, Token(token), Interest::WRITABLE)
+            .unwrap();
+        sockets.push(socket);
+    }
+
+    poll.poll(&mut events, Some(Duration::from_millis(100)))
+        .unwrap();
+    assert!(events.len() >= 8, "{:?}", events);
+
+    // Only keep the last socket registered and rearm its writable readiness.
+    for socket in &mut sockets[..7] {
+        poll.registry().deregister(socket).unwrap();
+    }
+    poll.registry()
+        .reregister(&mut sockets[7], Token(7), Interest::WRITABLE)
+        .unwrap();
+
+    poll.poll(&mut events, Some(Duration::from_millis(100)))
+        .unwrap();
+    assert_eq!(events.len(), 1, "{:?}", events);
+    assert_eq!(events.iter().count(), 1);
+    assert!(events.get(1).is_none());
+    assert_eq!(events.get(0).unwrap().token(), Token(7));
+    assert!(events.iter().all(|event| event.token() == Token(7)));
+}
+
+#[test]
+fn events_drain_bounded() {
+    use mio::net::UdpSocket;
+    use mio::Interest;
+    use std::collections::HashSet;
+
+    let (mut poll, _) = init_with_poll();
+    let mut events = Events::with_capacity(32);
+    assert_eq!(events.drain_bounded(5).len(), 0);
+
+    let mut sockets = Vec::new();
+    for token in 0..20 {
+        let mut socket = UdpSocket::bind(util::any_local_address()).unwrap();
+        poll.registry()
+            .register(&mut socket, Token(token), Interest::WRITABLE)
+            .unwrap();
+        sockets.push(socket);
+    }
+
+    poll.poll(&mut events, Some(Duration::from_millis(100)))
+        .unwrap();
+    let n = events.len();
+    assert!(n >= 20, "{:?}", events);
+
+    let mut seen = Vec::new();
+    loop {
+        let chunk = events.drain_bounded(5);
+        if chunk.len() == 0 {
+            break;
+        }
+        assert!(chunk.len() <= 5);
+        seen.extend(chunk.map(|event| event.token()));
+    }
+    // Every event is returned exactly once, in order.
+    let tokens: Vec<Token> = events.iter().map(|event| event.token()).collect();
+    assert_eq!(seen, tokens);
+    let unique: HashSet<Token> = seen.into_iter().collect();
+    assert_eq!(unique, (0..20).map(Token).collect());
+    assert_eq!(events.drain_bounded(5).len(), 0);
+
+    // Clearing the events starts over.
+    events.clear();
+    assert_eq!(events.drain_bounded(5).len(), 0);
+
+    // As does the next poll.
+    for (token, socket) in sockets.iter_mut().enumerate().take(2) {
+        poll.registry()
+            .reregister(socket, Token(token), Interest::WRITABLE)
+            .unwrap();
+    }
+    poll.poll(&mut events, Some(Duration::from_millis(100)))
+        .unwrap();
+    assert_eq!(events.drain_bounded(5).len(), events.len());
+    assert!(!events.is_empty());
+}
+
+#[test]
+fn events_alternating_polls() {
+    let mut poll1 = Poll::new().unwrap();
+    let mut poll2 = Poll::new().unwrap();
+    let mut events = Events::with_capacity(16);
+
+    let waker1 = Waker::new(poll1.registry(), Token(1)).unwrap();
+    let waker2 = Waker::new(poll2.registry(), Token(2)).unwrap();
+
+    for _ in 0..3 {
+        waker1.wake().expect("unable to wake");
+        poll1.wake_after(Duration::from_millis(0));
+        poll1
+            .poll(&mut events, Some(Duration::from_millis(100)))
+            .unwrap();
+        let tokens: Vec<Token> = events.ready_tokens().collect();
+        assert_eq!(tokens, vec![Token(1)]);
+        assert!(events.deadline_expired());
+        let ready_at1 = events.ready_at().unwrap();
+
+        waker2.wake().expect("unable to wake");
+        poll2
+            .poll(&mut events, Some(Duration::from_millis(100)))
+            .unwrap();
+        let tokens: Vec<Token> = events.ready_tokens().collect();
+        assert_eq!(tokens, vec![Token(2)]);
+        assert!(!events.deadline_expired());
+        assert!(events.ready_at().unwrap() >= ready_at1);
+    }
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn events_capacity_no_starvation() {
+    use mio::net::UdpSocket;
+    use mio::Interest;
+    use std::collections::HashSet;
+
+    const SOCKETS: usize = 50;
+    const CAPACITY: usize = 7;
+
+    let (mut poll, _) = init_with_poll();
+    let mut events = Events::with_capacity(CAPACITY);
+    // UDP sockets are always writable.
+    let mut sockets = Vec::new();
+    for token in 0..SOCKETS {
+        let mut socket = UdpSocket::bind(util::any_local_address()).unwrap();
+        poll.registry()
+            .register(&mut socket, Token(token), Interest::WRITABLE)
+            .unwrap();
+        sockets.push(socket);
+    }
+
+    let mut seen = HashSet::new();
+    // `usize::div_ceil` isn't available on our MSRV.
+    #[allow(unknown_lints, clippy::manual_div_ceil)]
+    let polls = (SOCKETS + CAPACITY - 1) / CAPACITY;
+    for _ in 0..polls {
+        poll.poll(&mut events, Some(Duration::from_millis(100)))
+            .unwrap();
+        assert!(events.iter().count() <= CAPACITY);
+        seen.extend(events.iter().map(|event| event.token()));
+    }
+    assert_eq!(seen.len(), SOCKETS, "missing tokens: {:?}", seen);
+}
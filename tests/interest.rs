@@ -8,6 +8,37 @@ fn is_tests() {
     assert!(Interest::WRITABLE.is_writable());
     assert!(!Interest::WRITABLE.is_aio());
     assert!(!Interest::WRITABLE.is_lio());
+    assert!(!Interest::WRITABLE.is_priority());
+    #[cfg(any(
+        target_os = "android",
+        target_os = "illumos",
+        target_os = "linux",
+        target_os = "solaris"
+    ))]
+    {
+        assert!(Interest::PRIORITY.is_priority());
+        assert!(!Interest::PRIORITY.is_readable());
+        assert!(!Interest::READABLE.is_priority());
+        assert!(Interest::BAND_WRITABLE.is_band_writable());
+        assert!(!Interest::BAND_WRITABLE.is_writable());
+        assert!(!Interest::WRITABLE.is_band_writable());
+        assert!(Interest::ONESHOT.is_oneshot());
+        assert!(!Interest::ONESHOT.is_readable());
+        assert!((Interest::READABLE | Interest::ONESHOT).is_oneshot());
+        assert!(!Interest::READABLE.is_oneshot());
+        assert_eq!(
+            (Interest::READABLE | Interest::ONESHOT).remove(Interest::READABLE),
+            Some(Interest::ONESHOT)
+        );
+        assert_eq!(
+            (Interest::READABLE | Interest::WRITABLE | Interest::ONESHOT)
+                .remove(Interest::READABLE),
+            Some(Interest::WRITABLE | Interest::ONESHOT)
+        );
+        assert!(Interest::READ_CLOSED.is_read_closed());
+        assert!(!Interest::READ_CLOSED.is_readable());
+        assert!(!Interest::READABLE.is_read_closed());
+    }
 }
 
 #[test]
@@ -38,6 +69,21 @@ fn fmt_debug() {
     {
         assert_eq!(format!("{:?}", Interest::LIO), "LIO");
     }
+    #[cfg(any(
+        target_os = "android",
+        target_os = "illumos",
+        target_os = "linux",
+        target_os = "solaris"
+    ))]
+    {
+        assert_eq!(format!("{:?}", Interest::PRIORITY), "PRIORITY");
+        assert_eq!(
+            format!("{:?}", Interest::READABLE | Interest::PRIORITY),
+            "READABLE | PRIORITY"
+        );
+        assert_eq!(format!("{:?}", Interest::BAND_WRITABLE), "BAND_WRITABLE");
+        assert_eq!(format!("{:?}", Interest::READ_CLOSED), "READ_CLOSED");
+    }
 }
 
 #[test]
@@ -0,0 +1,73 @@
+#![cfg(all(target_os = "linux", feature = "packet"))]
+
+use std::ffi::CString;
+use std::io;
+use std::net::UdpSocket;
+
+use mio::net::{PacketSocket, SockFilter};
+use mio::{Interest, Token};
+
+mod util;
+use util::{expect_events, init, init_with_poll, ExpectEvent};
+
+const ID1: Token = Token(0);
+
+fn loopback_index() -> u32 {
+    let name = CString::new("lo").unwrap();
+    let index = unsafe { libc::if_nametoindex(name.as_ptr()) };
+    assert_ne!(index, 0, "no loopback interface");
+    index
+}
+
+#[test]
+fn packet_socket_new_permission() {
+    init();
+    // Without `CAP_NET_RAW` creating the socket must fail with a permission
+    // error, rather than an arbitrary one.
+    match PacketSocket::new(libc::ETH_P_ALL as u16) {
+        Ok(_) => {}
+        Err(err) => assert_eq!(err.kind(), io::ErrorKind::PermissionDenied, "{}", err),
+    }
+}
+
+#[test]
+#[ignore = "requires CAP_NET_RAW"]
+fn packet_socket_loopback_capture() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let ifindex = loopback_index();
+    let mut socket = PacketSocket::new_dgram(libc::ETH_P_IP as u16).unwrap();
+    socket.bind_interface(ifindex).unwrap();
+    // Only accept UDP packets: `ldb [9]; jeq #17, accept, drop`.
+    socket
+        .attach_filter(&[
+            SockFilter::new(0x30, 0, 0, 9),
+            SockFilter::new(0x15, 0, 1, libc::IPPROTO_UDP as u32),
+            SockFilter::new(0x06, 0, 0, 0xffff_ffff),
+            SockFilter::new(0x06, 0, 0, 0),
+        ])
+        .unwrap();
+    poll.registry()
+        .register(&mut socket, ID1, Interest::READABLE)
+        .unwrap();
+
+    let receiver = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let sender = UdpSocket::bind("127.0.0.1:0").unwrap();
+    const DATA: &[u8] = b"captured";
+    sender
+        .send_to(DATA, receiver.local_addr().unwrap())
+        .unwrap();
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+
+    let mut buf = [0; 1500];
+    let (n, addr) = socket.recv_from(&mut buf).unwrap();
+    assert_eq!(addr.ifindex(), ifindex);
+    assert_eq!(addr.protocol(), libc::ETH_P_IP as u16);
+    // IPv4 + UDP headers, followed by the payload.
+    assert_eq!(&buf[28..n], DATA);
+}
@@ -3,16 +3,17 @@
 use std::net;
 use std::sync::{Arc, Barrier};
 use std::thread::{self, sleep};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use std::{fmt, io};
 
 use mio::event::Source;
 use mio::net::{TcpListener, TcpStream, UdpSocket};
-use mio::{event, Events, Interest, Poll, Registry, Token};
+use mio::{event, Events, Interest, Poll, Registry, Token, Waker};
 
 mod util;
 use util::{
-    any_local_address, assert_send, assert_sync, expect_events, init, init_with_poll, ExpectEvent,
+    any_local_address, assert_send, assert_sync, expect_events, expect_no_events, init,
+    init_with_poll, ExpectEvent,
 };
 
 const ID1: Token = Token(1);
@@ -41,6 +42,47 @@ fn run_once_with_nothing() {
         .unwrap();
 }
 
+#[test]
+fn wake_after() {
+    let (mut poll, mut events) = init_with_poll();
+
+    const DEADLINE: Duration = Duration::from_millis(20);
+    let start = Instant::now();
+    poll.wake_after(DEADLINE);
+    // Without the deadline this would block forever.
+    poll.poll(&mut events, None).unwrap();
+    let elapsed = start.elapsed();
+
+    assert!(events.is_empty());
+    assert!(events.deadline_expired());
+    // Only check the lower bound, how late the thread is scheduled again
+    // depends on the load of the machine.
+    assert!(elapsed >= DEADLINE, "woken up after {:?}", elapsed);
+
+    // The deadline only fires once.
+    poll.poll(&mut events, Some(Duration::from_millis(10)))
+        .unwrap();
+    assert!(events.is_empty());
+    assert!(!events.deadline_expired());
+}
+
+#[test]
+fn wake_after_with_waker() {
+    let (mut poll, mut events) = init_with_poll();
+    let waker = Waker::new(poll.registry(), Token::MAX_USABLE).unwrap();
+
+    poll.wake_after(Duration::from_millis(10));
+    thread::sleep(Duration::from_millis(20));
+    waker.wake().unwrap();
+
+    // Both the deadline and the waker are reported.
+    poll.poll(&mut events, Some(Duration::from_millis(500)))
+        .unwrap();
+    assert!(events.deadline_expired());
+    let tokens: Vec<_> = events.iter().map(|event| event.token()).collect();
+    assert_eq!(tokens, vec![Token::MAX_USABLE]);
+}
+
 #[test]
 fn add_then_drop() {
     init();
@@ -95,6 +137,169 @@ fn zero_duration_polls_events() {
     drop(listener);
 }
 
+#[test]
+fn token_boundary_values() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let tokens = [Token(0), Token(u32::MAX as usize), Token::MAX_USABLE];
+    let mut sockets = Vec::new();
+    for token in tokens.iter() {
+        let mut socket = UdpSocket::bind(any_local_address()).unwrap();
+        poll.registry()
+            .register(&mut socket, *token, Interest::WRITABLE)
+            .unwrap();
+        sockets.push(socket);
+    }
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        tokens
+            .iter()
+            .map(|token| ExpectEvent::new(*token, Interest::WRITABLE))
+            .collect(),
+    );
+}
+
+#[test]
+fn token_reserved() {
+    assert!(!Token(0).is_reserved());
+    assert!(!Token(u32::MAX as usize).is_reserved());
+    assert!(!Token::MAX_USABLE.is_reserved());
+    assert!(Token(usize::MAX).is_reserved());
+    // Only a single value is reserved, regardless of the width of `usize`.
+    assert_eq!(Token::MAX_USABLE.0 as u64 + 1, usize::MAX as u64);
+    // All usable tokens round-trip through epoll's `u64` and kqueue's
+    // pointer sized user data.
+    assert_eq!(Token::MAX_USABLE.0 as u64 as usize, Token::MAX_USABLE.0);
+    assert_eq!(Token::MAX_USABLE.0 as *mut u8 as usize, Token::MAX_USABLE.0);
+}
+
+#[test]
+fn register_reserved_token() {
+    let (poll, _) = init_with_poll();
+    let reserved = Token(usize::MAX);
+
+    let mut socket = UdpSocket::bind(any_local_address()).unwrap();
+    let err = poll
+        .registry()
+        .register(&mut socket, reserved, Interest::WRITABLE)
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+
+    poll.registry()
+        .register(&mut socket, ID1, Interest::WRITABLE)
+        .unwrap();
+    let err = poll
+        .registry()
+        .reregister(&mut socket, reserved, Interest::WRITABLE)
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+
+    let err = Waker::new(poll.registry(), reserved).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+}
+
+#[test]
+#[cfg(unix)]
+fn nested_poll() {
+    let (mut outer, mut events) = init_with_poll();
+    let mut inner = Poll::new().unwrap();
+    let mut inner_events = Events::with_capacity(16);
+
+    outer
+        .registry()
+        .register(&mut inner, ID1, Interest::READABLE)
+        .unwrap();
+    expect_no_events(&mut outer, &mut events);
+
+    let mut socket = UdpSocket::bind(any_local_address()).unwrap();
+    let sender = net::UdpSocket::bind(any_local_address()).unwrap();
+    inner
+        .registry()
+        .register(&mut socket, ID2, Interest::READABLE)
+        .unwrap();
+
+    sender
+        .send_to(b"hello", socket.local_addr().unwrap())
+        .unwrap();
+    expect_events(
+        &mut outer,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    expect_events(
+        &mut inner,
+        &mut inner_events,
+        vec![ExpectEvent::new(ID2, Interest::READABLE)],
+    );
+
+    outer.registry().deregister(&mut inner).unwrap();
+    sender
+        .send_to(b"hello", socket.local_addr().unwrap())
+        .unwrap();
+    expect_no_events(&mut outer, &mut events);
+}
+
+#[test]
+fn run_event_loop() {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    let (mut poll, mut events) = init_with_poll();
+
+    let waker = Arc::new(mio::Waker::new(poll.registry(), ID1).unwrap());
+    let stop = Arc::new(AtomicBool::new(false));
+
+    let mut socket = UdpSocket::bind(any_local_address()).unwrap();
+    poll.registry()
+        .register(&mut socket, ID2, Interest::WRITABLE)
+        .unwrap();
+
+    let handle = {
+        let waker = waker.clone();
+        let stop = stop.clone();
+        thread::spawn(move || {
+            sleep(Duration::from_millis(50));
+            stop.store(true, Ordering::SeqCst);
+            waker.wake().unwrap();
+        })
+    };
+
+    let mut tokens = Vec::new();
+    let mut ticks = 0;
+    poll.run(
+        &mut events,
+        Some(Duration::from_millis(10)),
+        |_, events| {
+            tokens.extend(events.iter().map(|event| event.token()));
+            Ok(())
+        },
+        |_| {
+            ticks += 1;
+            !stop.load(Ordering::SeqCst)
+        },
+    )
+    .unwrap();
+    handle.join().unwrap();
+
+    // The socket's event and the waker's.
+    assert_eq!(tokens.first(), Some(&ID2), "{:?}", tokens);
+    assert!(tokens.contains(&ID1), "{:?}", tokens);
+    // At least one tick per event, plus the ticks for the timeouts.
+    assert!(ticks > 2, "{}", ticks);
+
+    // Errors of `on_events` stop the loop.
+    let err = poll
+        .run(
+            &mut events,
+            Some(Duration::from_millis(0)),
+            |_, _| Err(io::Error::new(io::ErrorKind::InvalidData, "stop")),
+            |_| panic!("unexpected tick"),
+        )
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
+
 #[test]
 fn poll_closes_fd() {
     init();
@@ -217,6 +422,131 @@ fn registry_behind_arc() {
     handle2.join().unwrap();
 }
 
+#[test]
+fn registry_shutdown() {
+    init();
+
+    let mut poll = Poll::new().unwrap();
+    let registry = poll.registry().try_clone().unwrap();
+    let mut listener = TcpListener::bind(any_local_address()).unwrap();
+    registry
+        .register(&mut listener, ID1, Interest::READABLE)
+        .unwrap();
+    assert!(!poll.is_shutdown());
+
+    let barrier = Arc::new(Barrier::new(2));
+    let barrier2 = Arc::clone(&barrier);
+    let handle = thread::spawn(move || {
+        let mut events = Events::with_capacity(8);
+        barrier2.wait();
+        // Blocks until shut down.
+        let err = poll.poll(&mut events, None).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Other);
+        assert_eq!(err.to_string(), "poll instance shut down");
+        assert!(poll.is_shutdown());
+        assert!(events.is_empty());
+
+        // All following calls fail as well, without blocking.
+        let err = poll
+            .poll(&mut events, Some(Duration::from_secs(10)))
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Other);
+        poll
+    });
+
+    barrier.wait();
+    // Give the thread some time to block in `poll`.
+    sleep(Duration::from_millis(50));
+    let start = Instant::now();
+    registry.shutdown().unwrap();
+    let poll = handle.join().unwrap();
+    assert!(start.elapsed() < Duration::from_millis(100));
+
+    // Registering fails once shut down, on all clones.
+    let err = registry
+        .reregister(&mut listener, ID1, Interest::WRITABLE)
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::Other);
+    let mut listener2 = TcpListener::bind(any_local_address()).unwrap();
+    let err = poll
+        .registry()
+        .register(&mut listener2, ID2, Interest::READABLE)
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::Other);
+
+    // Shutting down again has no effect.
+    registry.shutdown().unwrap();
+}
+
+#[test]
+fn registry_shutdown_with_waker() {
+    init();
+
+    let mut poll = Poll::new().unwrap();
+    let waker = mio::Waker::new(poll.registry(), ID2).unwrap();
+    let mut events = Events::with_capacity(8);
+
+    // The shutdown wake up doesn't replace the `Waker`.
+    poll.registry().shutdown().unwrap();
+    waker.wake().unwrap();
+    let err = poll
+        .poll(&mut events, Some(Duration::from_secs(10)))
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::Other);
+    assert!(poll.is_shutdown());
+    assert!(events.is_empty());
+}
+
+#[test]
+fn registry_outlives_poll() {
+    init();
+
+    let poll = Poll::new().unwrap();
+    let registry = poll.registry().try_clone().unwrap();
+    let waker = mio::Waker::new(poll.registry(), ID2).unwrap();
+    let barrier = Arc::new(Barrier::new(2));
+
+    let barrier2 = Arc::clone(&barrier);
+    let handle = thread::spawn(move || {
+        let mut listener = TcpListener::bind(any_local_address()).unwrap();
+        registry
+            .register(&mut listener, ID1, Interest::READABLE)
+            .unwrap();
+
+        // Wait for the `Poll` instance to be dropped.
+        barrier2.wait();
+        barrier2.wait();
+
+        let err = registry
+            .reregister(&mut listener, ID1, Interest::WRITABLE)
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Other);
+        assert_eq!(err.to_string(), "poll instance closed");
+        let err = registry.deregister(&mut listener).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Other);
+        let mut stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let err = registry
+            .register(&mut stream, ID3, Interest::READABLE)
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Other);
+
+        // Clones share the state.
+        let registry2 = registry.try_clone().unwrap();
+        let err = registry2
+            .register(&mut stream, ID3, Interest::READABLE)
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Other);
+
+        // Waking has no effect, but isn't an error.
+        waker.wake().unwrap();
+    });
+
+    barrier.wait();
+    drop(poll);
+    barrier.wait();
+    handle.join().unwrap();
+}
+
 /// Call all registration operations, ending with `source` being registered with `token` and `final_interests`.
 pub fn registry_ops_flow(
     registry: &Registry,
@@ -345,8 +675,11 @@ fn register_during_poll() {
     // Unlock the thread, allow it to register the `UdpSocket`.
     barrier.wait();
     // Concurrently (at least we attempt to) call `Poll::poll`.
+    let start = Instant::now();
     poll.poll(&mut events, Some(Duration::from_secs(5)))
         .unwrap();
+    // Should return as soon as the socket is registered, not after the timeout.
+    assert!(start.elapsed() < Duration::from_secs(2));
 
     let mut iter = events.iter();
     let event = iter.next().expect("expect an event");
@@ -659,3 +992,160 @@ pub fn assert_error<T, E: fmt::Display>(result: Result<T, E>, expected_msg: &str
         ),
     }
 }
+
+#[test]
+fn capabilities() {
+    use mio::features::Triggering;
+
+    let capabilities = Poll::capabilities();
+    // All selectors are edge-triggered.
+    assert_eq!(capabilities.triggering, Triggering::Edge);
+    assert!(capabilities.waker);
+    let epoll = cfg!(any(
+        target_os = "android",
+        target_os = "illumos",
+        target_os = "linux",
+        target_os = "solaris",
+    ));
+    assert_eq!(capabilities.priority, epoll);
+    assert_eq!(capabilities.oneshot, epoll);
+}
+
+#[test]
+#[cfg(all(unix, feature = "os-ext"))]
+fn capabilities_regular_files() {
+    use mio::unix::SourceFd;
+    use std::fs::File;
+    use std::os::unix::io::AsRawFd;
+
+    init();
+    let poll = Poll::new().unwrap();
+    let file = File::open("Cargo.toml").unwrap();
+    let result =
+        poll.registry()
+            .register(&mut SourceFd(&file.as_raw_fd()), ID1, Interest::READABLE);
+    assert_eq!(result.is_ok(), Poll::capabilities().regular_files);
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn poll_error_keeps_os_error() {
+    let mut poll = Poll::new().unwrap();
+    // `epoll_wait(2)` returns `EINVAL` if `maxevents` is zero.
+    let mut events = Events::with_capacity(0);
+    let err = poll
+        .poll(&mut events, Some(Duration::from_millis(0)))
+        .unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::EINVAL));
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn poll_owned() {
+    init();
+    let mut poll = Poll::new().unwrap();
+    let waker = mio::Waker::new(poll.registry(), ID1).unwrap();
+
+    for _ in 0..3 {
+        waker.wake().unwrap();
+        let events = poll.poll_owned(Some(Duration::from_millis(500))).unwrap();
+        assert_eq!(events.iter().count(), 1, "{:?}", events);
+        let event = events.iter().next().unwrap();
+        assert_eq!(event.token(), ID1);
+        assert!(event.is_readable());
+        assert_eq!(events.capacity(), Poll::OWNED_EVENTS_CAPACITY);
+
+        // The events of the previous call are replaced.
+        let events = poll.poll_owned(Some(Duration::from_millis(10))).unwrap();
+        assert!(events.is_empty(), "{:?}", events);
+    }
+}
+
+#[test]
+fn poll_into() {
+    use std::mem::MaybeUninit;
+
+    use mio::event::Event;
+
+    init();
+    let mut poll = Poll::new().unwrap();
+
+    // UDP sockets are always writable.
+    let mut sockets = Vec::new();
+    for token in 0..5 {
+        let mut socket = UdpSocket::bind(any_local_address()).unwrap();
+        poll.registry()
+            .register(&mut socket, Token(token), Interest::WRITABLE)
+            .unwrap();
+        sockets.push(socket);
+    }
+
+    // Overflow, the remaining events are returned by the next calls.
+    let mut buf: [MaybeUninit<Event>; 2] = unsafe { MaybeUninit::uninit().assume_init() };
+    let mut tokens = Vec::new();
+    for expected in &[2, 2, 1] {
+        let events = poll
+            .poll_into(&mut buf, Some(Duration::from_millis(100)))
+            .unwrap();
+        assert_eq!(events.len(), *expected);
+        assert!(events.iter().all(Event::is_writable));
+        tokens.extend(events.iter().map(Event::token));
+    }
+    tokens.sort();
+    assert_eq!(tokens, (0..5).map(Token).collect::<Vec<_>>());
+
+    // Exact fit.
+    for (token, socket) in sockets.iter_mut().enumerate() {
+        poll.registry()
+            .reregister(socket, Token(token), Interest::WRITABLE)
+            .unwrap();
+    }
+    let mut buf: [MaybeUninit<Event>; 5] = unsafe { MaybeUninit::uninit().assume_init() };
+    let events = poll
+        .poll_into(&mut buf, Some(Duration::from_millis(100)))
+        .unwrap();
+    assert_eq!(events.len(), 5);
+    let events = poll
+        .poll_into(&mut buf, Some(Duration::from_millis(10)))
+        .unwrap();
+    assert!(events.is_empty());
+
+    // Zero-length buffer.
+    let err = poll
+        .poll_into(&mut [], Some(Duration::from_millis(10)))
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn poll_into_wake_after() {
+    use std::mem::MaybeUninit;
+
+    use mio::event::Event;
+
+    let (mut poll, mut events) = init_with_poll();
+    let mut buf: [MaybeUninit<Event>; 2] = unsafe { MaybeUninit::uninit().assume_init() };
+
+    // The deadline wakes up `poll_into`, clearing it.
+    const DEADLINE: Duration = Duration::from_millis(10);
+    let start = Instant::now();
+    poll.wake_after(DEADLINE);
+    let events_into = poll.poll_into(&mut buf, None).unwrap();
+    assert!(events_into.is_empty());
+    assert!(start.elapsed() >= DEADLINE);
+
+    // So it's not reported again by `poll`.
+    poll.poll(&mut events, Some(Duration::from_millis(10)))
+        .unwrap();
+    assert!(events.is_empty());
+    assert!(!events.deadline_expired());
+
+    // And the other way around.
+    poll.wake_after(DEADLINE);
+    poll.poll(&mut events, None).unwrap();
+    assert!(events.deadline_expired());
+    let events_into = poll
+        .poll_into(&mut buf, Some(Duration::from_millis(10)))
+        .unwrap();
+    assert!(events_into.is_empty());
+}
@@ -0,0 +1,288 @@
+#![cfg(all(feature = "reactor", feature = "net"))]
+
+use std::cell::RefCell;
+use std::io;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use mio::net::UdpSocket;
+use mio::reactor::{Control, Handler, Reactor, Readiness};
+use mio::Interest;
+
+mod util;
+use util::{any_local_address, init};
+
+const DATA: &[u8] = b"Hello world!";
+const TIMEOUT: Option<Duration> = Some(Duration::from_millis(500));
+
+/// Closure called by `Test`.
+type TestFn = dyn FnMut(&mut UdpSocket, Readiness, &mut Control<'_, Test>);
+
+/// Handler that calls a closure.
+struct Test(Box<TestFn>);
+
+impl Test {
+    fn new<F>(f: F) -> Test
+    where
+        F: FnMut(&mut UdpSocket, Readiness, &mut Control<'_, Test>) + 'static,
+    {
+        Test(Box::new(f))
+    }
+
+    /// Handler that records all calls in `calls`.
+    fn recording(calls: &Rc<RefCell<Vec<Readiness>>>) -> Test {
+        let calls = calls.clone();
+        Test::new(move |_, readiness, _| calls.borrow_mut().push(readiness))
+    }
+}
+
+impl Handler for Test {
+    type Source = UdpSocket;
+
+    fn ready(
+        &mut self,
+        source: &mut UdpSocket,
+        readiness: Readiness,
+        control: &mut Control<'_, Self>,
+    ) {
+        (self.0)(source, readiness, control)
+    }
+}
+
+fn sockets() -> (UdpSocket, UdpSocket) {
+    let socket1 = UdpSocket::bind(any_local_address()).unwrap();
+    let socket2 = UdpSocket::bind(any_local_address()).unwrap();
+    socket1.connect(socket2.local_addr().unwrap()).unwrap();
+    socket2.connect(socket1.local_addr().unwrap()).unwrap();
+    (socket1, socket2)
+}
+
+#[test]
+fn insert_modify_remove() {
+    init();
+    let mut reactor = Reactor::new().unwrap();
+    let calls = Rc::new(RefCell::new(Vec::new()));
+
+    let (socket1, socket2) = sockets();
+    let key = reactor
+        .insert(socket1, Interest::READABLE, Test::recording(&calls))
+        .unwrap();
+    assert_eq!(reactor.len(), 1);
+
+    // Not readable yet.
+    reactor.run_once(Some(Duration::from_millis(50))).unwrap();
+    assert!(calls.borrow().is_empty());
+
+    reactor.modify(key, Interest::WRITABLE).unwrap();
+    assert_eq!(reactor.run_once(TIMEOUT).unwrap(), 1);
+    assert!(calls.borrow()[0].is_writable());
+
+    let (socket1, _) = reactor.remove(key).unwrap();
+    assert!(reactor.is_empty());
+    assert!(reactor.remove(key).is_none());
+    assert!(reactor.get_mut(key).is_none());
+    let err = reactor.modify(key, Interest::READABLE).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::NotFound);
+
+    // The socket is deregistered, it must not be reported.
+    socket2.send(DATA).unwrap();
+    calls.borrow_mut().clear();
+    reactor.run_once(Some(Duration::from_millis(50))).unwrap();
+    assert!(calls.borrow().is_empty());
+    drop(socket1);
+}
+
+#[test]
+fn remove_during_dispatch() {
+    init();
+    let mut reactor = Reactor::new().unwrap();
+    let calls = Rc::new(RefCell::new(0));
+
+    let (socket1, socket2) = sockets();
+    let handler_calls = calls.clone();
+    let key = reactor
+        .insert(
+            socket1,
+            Interest::READABLE,
+            Test::new(move |_, readiness, control| {
+                assert!(readiness.is_readable());
+                *handler_calls.borrow_mut() += 1;
+                // Wake the handler in the next call, which should be ignored as
+                // the handler is removed.
+                let key = control.key();
+                control.wake(key);
+                control.remove();
+            }),
+        )
+        .unwrap();
+
+    socket2.send(DATA).unwrap();
+    assert_eq!(reactor.run_once(TIMEOUT).unwrap(), 1);
+    assert_eq!(*calls.borrow(), 1);
+    assert!(reactor.is_empty());
+    assert!(reactor.get_mut(key).is_none());
+
+    // Reuses the slot of the removed socket, but must not receive the wake up
+    // meant for it.
+    let new_calls = Rc::new(RefCell::new(Vec::new()));
+    let (socket3, _socket4) = sockets();
+    let new_key = reactor
+        .insert(socket3, Interest::READABLE, Test::recording(&new_calls))
+        .unwrap();
+    assert_ne!(key, new_key);
+    assert_eq!(
+        reactor.run_once(Some(Duration::from_millis(50))).unwrap(),
+        0
+    );
+    assert!(new_calls.borrow().is_empty());
+    assert_eq!(*calls.borrow(), 1);
+}
+
+#[test]
+fn insert_during_dispatch() {
+    init();
+    let mut reactor = Reactor::new().unwrap();
+    let calls = Rc::new(RefCell::new(Vec::new()));
+
+    let (socket1, socket2) = sockets();
+    let (socket3, socket4) = sockets();
+    let mut socket3 = Some(socket3);
+    let new_calls = calls.clone();
+    reactor
+        .insert(
+            socket1,
+            Interest::READABLE,
+            Test::new(move |_, _, control| {
+                if let Some(socket3) = socket3.take() {
+                    control
+                        .insert(socket3, Interest::READABLE, Test::recording(&new_calls))
+                        .unwrap();
+                }
+            }),
+        )
+        .unwrap();
+
+    socket2.send(DATA).unwrap();
+    assert_eq!(reactor.run_once(TIMEOUT).unwrap(), 1);
+    assert_eq!(reactor.len(), 2);
+    assert!(calls.borrow().is_empty());
+
+    socket4.send(DATA).unwrap();
+    assert_eq!(reactor.run_once(TIMEOUT).unwrap(), 1);
+    assert!(calls.borrow()[0].is_readable());
+}
+
+#[test]
+fn wake() {
+    init();
+    let mut reactor = Reactor::new().unwrap();
+    let calls = Rc::new(RefCell::new(Vec::new()));
+
+    let (socket1, socket2) = sockets();
+    let (socket3, _socket4) = sockets();
+    let woken = reactor
+        .insert(socket3, Interest::READABLE, Test::recording(&calls))
+        .unwrap();
+    reactor
+        .insert(
+            socket1,
+            Interest::READABLE,
+            Test::new(move |socket, _, control| {
+                let mut buf = [0; 20];
+                while socket.recv(&mut buf).is_ok() {}
+                control.wake(woken);
+            }),
+        )
+        .unwrap();
+
+    socket2.send(DATA).unwrap();
+    assert_eq!(reactor.run_once(TIMEOUT).unwrap(), 1);
+    assert!(calls.borrow().is_empty());
+
+    // Must not block, even without a timeout.
+    assert_eq!(reactor.run_once(None).unwrap(), 1);
+    let readiness = calls.borrow()[0];
+    assert!(readiness.is_woken());
+    assert!(!readiness.is_readable());
+}
+
+#[test]
+fn waker() {
+    init();
+    let mut reactor = Reactor::new().unwrap();
+    let calls = Rc::new(RefCell::new(Vec::new()));
+
+    let (socket, _peer) = sockets();
+    reactor
+        .insert(socket, Interest::READABLE, Test::recording(&calls))
+        .unwrap();
+
+    let waker = reactor.waker().unwrap();
+    assert!(Arc::ptr_eq(&waker, &reactor.waker().unwrap()));
+    thread::spawn(move || waker.wake().unwrap()).join().unwrap();
+    // The waker's event doesn't call any handler.
+    assert_eq!(reactor.run_once(TIMEOUT).unwrap(), 0);
+    assert!(calls.borrow().is_empty());
+}
+
+#[test]
+fn idle_timeout() {
+    init();
+    let mut reactor = Reactor::new().unwrap();
+    let calls = Rc::new(RefCell::new(Vec::new()));
+
+    let (socket1, _socket2) = sockets();
+    let (socket3, _socket4) = sockets();
+    let key = reactor
+        .insert(socket1, Interest::READABLE, Test::recording(&calls))
+        .unwrap();
+    let other_key = reactor
+        .insert(socket3, Interest::READABLE, Test::recording(&calls))
+        .unwrap();
+    reactor
+        .set_idle_timeout(key, Some(Duration::from_millis(50)))
+        .unwrap();
+
+    // Returns once the timeout expired, even without a timeout.
+    let start = Instant::now();
+    assert_eq!(reactor.run_once(None).unwrap(), 1);
+    assert!(start.elapsed() >= Duration::from_millis(50));
+    assert!(calls.borrow()[0].is_timed_out());
+
+    // The timed out source is removed, the other is unaffected.
+    assert!(reactor.get_mut(key).is_none());
+    assert!(reactor.get_mut(other_key).is_some());
+    assert_eq!(reactor.len(), 1);
+    let err = reactor
+        .set_idle_timeout(key, Some(Duration::from_millis(50)))
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::NotFound);
+}
+
+#[test]
+fn idle_timeout_restarted_by_events() {
+    init();
+    let mut reactor = Reactor::new().unwrap();
+    let calls = Rc::new(RefCell::new(Vec::new()));
+
+    let (socket1, socket2) = sockets();
+    let key = reactor
+        .insert(socket1, Interest::READABLE, Test::recording(&calls))
+        .unwrap();
+    reactor
+        .set_idle_timeout(key, Some(Duration::from_millis(100)))
+        .unwrap();
+
+    socket2.send(DATA).unwrap();
+    assert_eq!(reactor.run_once(TIMEOUT).unwrap(), 1);
+    assert!(calls.borrow()[0].is_readable());
+
+    // Handled an event, so the timeout was restarted.
+    let start = Instant::now();
+    assert_eq!(reactor.run_once(None).unwrap(), 1);
+    assert!(start.elapsed() >= Duration::from_millis(90));
+    assert!(calls.borrow()[1].is_timed_out());
+    assert!(reactor.is_empty());
+}
@@ -243,3 +243,301 @@ fn registering_after_deregistering() {
         .unwrap();
     assert!(events.is_empty());
 }
+
+#[test]
+#[cfg(unix)]
+fn register_guarded() {
+    use util::{expect_events, expect_no_events, init_with_poll, ExpectEvent};
+
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut listener = TcpListener::bind(any_local_address()).unwrap();
+    let address = listener.local_addr().unwrap();
+
+    // The guard borrows the registry, so we can't use `poll.registry()`.
+    let registry = poll.registry().try_clone().unwrap();
+    let mut guard = registry
+        .register_guarded(&mut listener, SERVER, Interest::READABLE)
+        .unwrap();
+
+    let _stream1 = std::net::TcpStream::connect(address).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(SERVER, Interest::READABLE)],
+    );
+    let _ = guard.accept().unwrap();
+
+    // Reregistering passes through.
+    guard.reregister(CLIENT, Interest::READABLE).unwrap();
+    let _stream2 = std::net::TcpStream::connect(address).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(CLIENT, Interest::READABLE)],
+    );
+    let _ = guard.accept().unwrap();
+
+    // Dropping the guard deregisters the listener.
+    drop(guard);
+    let _stream3 = std::net::TcpStream::connect(address).unwrap();
+    expect_no_events(&mut poll, &mut events);
+
+    // Which means it can be registered again.
+    poll.registry()
+        .register(&mut listener, SERVER, Interest::READABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(SERVER, Interest::READABLE)],
+    );
+    poll.registry().deregister(&mut listener).unwrap();
+}
+
+#[test]
+#[cfg(unix)]
+fn register_guarded_forget() {
+    use util::{expect_events, init_with_poll, ExpectEvent};
+
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut listener = TcpListener::bind(any_local_address()).unwrap();
+    let address = listener.local_addr().unwrap();
+
+    poll.registry()
+        .register_guarded(&mut listener, SERVER, Interest::READABLE)
+        .unwrap()
+        .forget();
+
+    // Still registered.
+    let _stream = std::net::TcpStream::connect(address).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(SERVER, Interest::READABLE)],
+    );
+
+    poll.registry().deregister(&mut listener).unwrap();
+}
+
+#[test]
+fn transfer() {
+    use std::io::Read;
+    use util::{expect_events, expect_no_events, init_with_poll, ExpectEvent};
+
+    let (mut poll1, mut events) = init_with_poll();
+    let mut poll2 = Poll::new().unwrap();
+
+    let listener = std::net::TcpListener::bind(any_local_address()).unwrap();
+    let mut stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (mut peer, _) = listener.accept().unwrap();
+
+    poll1
+        .registry()
+        .register(&mut stream, SERVER, Interest::READABLE)
+        .unwrap();
+
+    peer.write_all(b"hello").unwrap();
+    // Wait for the data to arrive, but don't poll `poll1`.
+    sleep(Duration::from_millis(50));
+
+    poll1
+        .registry()
+        .transfer(&mut stream, poll2.registry(), CLIENT, Interest::READABLE)
+        .unwrap();
+
+    // Readiness is reported by the second poll, without the peer writing
+    // again.
+    expect_events(
+        &mut poll2,
+        &mut events,
+        vec![ExpectEvent::new(CLIENT, Interest::READABLE)],
+    );
+    expect_no_events(&mut poll1, &mut events);
+
+    let mut buf = [0; 16];
+    assert_eq!(stream.read(&mut buf).unwrap(), 5);
+    assert_eq!(&buf[..5], b"hello");
+
+    // The stream is now registered with the second poll.
+    poll2.registry().deregister(&mut stream).unwrap();
+}
+
+#[test]
+#[cfg(all(unix, feature = "os-ext"))]
+fn transfer_register_fails() {
+    use mio::unix::SourceFd;
+    use std::os::unix::io::AsRawFd;
+    use util::{expect_events, expect_no_events, init_with_poll, ExpectEvent};
+
+    let (mut poll1, mut events) = init_with_poll();
+    let poll2 = Poll::new().unwrap();
+
+    let listener = std::net::TcpListener::bind(any_local_address()).unwrap();
+    let mut stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (mut peer, _) = listener.accept().unwrap();
+
+    poll1
+        .registry()
+        .register(&mut stream, SERVER, Interest::READABLE)
+        .unwrap();
+    // Registering the same file descriptor again with `poll2` fails.
+    let fd = stream.as_raw_fd();
+    poll2
+        .registry()
+        .register(&mut SourceFd(&fd), SERVER, Interest::READABLE)
+        .unwrap();
+
+    let err = poll1
+        .registry()
+        .transfer(&mut stream, poll2.registry(), CLIENT, Interest::READABLE)
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::AlreadyExists);
+
+    // The stream isn't registered with `poll1` anymore.
+    peer.write_all(b"hello").unwrap();
+    expect_no_events(&mut poll1, &mut events);
+
+    // But it can be registered again, without missing the readiness.
+    poll1
+        .registry()
+        .register(&mut stream, CLIENT, Interest::READABLE)
+        .unwrap();
+    expect_events(
+        &mut poll1,
+        &mut events,
+        vec![ExpectEvent::new(CLIENT, Interest::READABLE)],
+    );
+}
+
+#[test]
+#[cfg(all(unix, feature = "os-ext"))]
+fn reregister_after_close() {
+    use mio::net::UdpSocket;
+    use mio::unix::SourceFd;
+    use std::os::unix::io::AsRawFd;
+    use util::{expect_events, ExpectEvent};
+
+    init();
+    let mut poll = Poll::new().unwrap();
+    let mut events = Events::with_capacity(16);
+
+    let mut socket1 = UdpSocket::bind(any_local_address()).unwrap();
+    poll.registry()
+        .register(&mut socket1, SERVER, Interest::READABLE)
+        .unwrap();
+    // Close the socket without deregistering it first.
+    let fd = socket1.as_raw_fd();
+    drop(socket1);
+
+    // The kernel removes closed file descriptors itself, there is no state
+    // left behind that could get out of sync.
+    assert!(poll
+        .registry()
+        .reregister(&mut SourceFd(&fd), SERVER, Interest::WRITABLE)
+        .is_err());
+    assert!(poll.registry().deregister(&mut SourceFd(&fd)).is_err());
+
+    // A new socket, likely using the same file descriptor number, can be
+    // registered again.
+    let mut socket2 = UdpSocket::bind(any_local_address()).unwrap();
+    poll.registry()
+        .register(&mut socket2, CLIENT, Interest::WRITABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(CLIENT, Interest::WRITABLE)],
+    );
+}
+
+#[test]
+fn deregister_keeps_buffered_data() {
+    use std::io::Read;
+    use std::net;
+
+    init();
+    let mut poll = Poll::new().unwrap();
+    let mut events = Events::with_capacity(16);
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let mut client = net::TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (server, _) = listener.accept().unwrap();
+    server.set_nonblocking(true).unwrap();
+    let mut server = TcpStream::from_std(server);
+    poll.registry()
+        .register(&mut server, SERVER, Interest::READABLE)
+        .unwrap();
+
+    const DATA: &[u8] = b"final words";
+    client.write_all(DATA).unwrap();
+    drop(client);
+
+    // Deregister while the readable event is still pending.
+    poll.registry().deregister(&mut server).unwrap();
+    poll.poll(&mut events, Some(Duration::from_millis(100)))
+        .unwrap();
+    assert!(events.is_empty());
+
+    // Event is gone, but the data isn't.
+    let mut buf = Vec::new();
+    server.read_to_end(&mut buf).unwrap();
+    assert_eq!(buf, DATA);
+}
+
+#[test]
+#[cfg(all(unix, feature = "os-ext"))]
+fn register_pty() {
+    use mio::unix::SourceFd;
+    use std::ffi::CStr;
+
+    init();
+    let mut poll = Poll::new().unwrap();
+    let mut events = Events::with_capacity(16);
+
+    // Use the secondary side of a pseudo terminal like standard input.
+    let primary = unsafe { libc::posix_openpt(libc::O_RDWR | libc::O_NOCTTY) };
+    assert!(primary >= 0);
+    assert_eq!(unsafe { libc::grantpt(primary) }, 0);
+    assert_eq!(unsafe { libc::unlockpt(primary) }, 0);
+    let name = unsafe { CStr::from_ptr(libc::ptsname(primary)) };
+    let stdin = unsafe {
+        libc::open(
+            name.as_ptr(),
+            libc::O_RDWR | libc::O_NOCTTY | libc::O_NONBLOCK,
+        )
+    };
+    assert!(stdin >= 0);
+
+    poll.registry()
+        .register(&mut SourceFd(&stdin), CLIENT, Interest::READABLE)
+        .unwrap();
+    poll.poll(&mut events, Some(Duration::from_millis(50)))
+        .unwrap();
+    assert!(events.is_empty());
+
+    // A terminal in canonical mode is readable once a line is complete.
+    const LINE: &[u8] = b"input\n";
+    let n = unsafe { libc::write(primary, LINE.as_ptr().cast(), LINE.len()) };
+    assert_eq!(n, LINE.len() as isize);
+    poll.poll(&mut events, Some(Duration::from_millis(500)))
+        .unwrap();
+    let event = events.iter().next().expect("no event");
+    assert_eq!(event.token(), CLIENT);
+    assert!(event.is_readable());
+
+    let mut buf = [0; 16];
+    let n = unsafe { libc::read(stdin, buf.as_mut_ptr().cast(), buf.len()) };
+    assert_eq!(&buf[..n as usize], LINE);
+    // Non-blocking, so no more input returns `WouldBlock`.
+    let n = unsafe { libc::read(stdin, buf.as_mut_ptr().cast(), buf.len()) };
+    assert_eq!(n, -1);
+    assert_eq!(io::Error::last_os_error().kind(), io::ErrorKind::WouldBlock);
+
+    poll.registry().deregister(&mut SourceFd(&stdin)).unwrap();
+    unsafe {
+        libc::close(stdin);
+        libc::close(primary);
+    }
+}
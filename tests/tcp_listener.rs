@@ -282,3 +282,83 @@ fn start_connections(
         }
     })
 }
+
+#[test]
+#[cfg(unix)]
+fn socket_introspection() {
+    use mio::net::{Family, Kind};
+
+    let listener = TcpListener::bind(any_local_address()).unwrap();
+    assert_eq!(listener.socket_family().unwrap(), Family::Ipv4);
+    assert_eq!(listener.socket_type().unwrap(), Kind::Stream);
+    assert!(listener.is_listening().unwrap());
+
+    let listener = TcpListener::bind(any_local_ipv6_address()).unwrap();
+    assert_eq!(listener.socket_family().unwrap(), Family::Ipv6);
+    assert_eq!(listener.socket_type().unwrap(), Kind::Stream);
+    assert!(listener.is_listening().unwrap());
+}
+
+#[test]
+#[cfg(unix)]
+fn any_socket_from_raw_fd() {
+    use mio::net::{AnySocket, Family, Kind, UdpSocket};
+
+    let listener = TcpListener::bind(any_local_address()).unwrap();
+    let address = listener.local_addr().unwrap();
+    let socket = unsafe { AnySocket::from_raw_fd(listener.into_raw_fd()).unwrap() };
+    assert!(matches!(socket, AnySocket::TcpListener(..)));
+    assert!(socket.is_listening().unwrap());
+
+    let stream = net::TcpStream::connect(address).unwrap();
+    let socket = unsafe { AnySocket::from_raw_fd(stream.into_raw_fd()).unwrap() };
+    assert!(matches!(socket, AnySocket::TcpStream(..)));
+    assert_eq!(socket.socket_type().unwrap(), Kind::Stream);
+    assert!(!socket.is_listening().unwrap());
+
+    let udp = UdpSocket::bind(any_local_ipv6_address()).unwrap();
+    let socket = unsafe { AnySocket::from_raw_fd(udp.into_raw_fd()).unwrap() };
+    assert!(matches!(socket, AnySocket::UdpSocket(..)));
+    assert_eq!(socket.socket_family().unwrap(), Family::Ipv6);
+}
+
+#[test]
+#[cfg(unix)]
+fn accept_with_options() {
+    use mio::net::{AcceptedOptions, TcpKeepalive, TcpSocket};
+    use std::time::Duration;
+
+    let (mut poll, mut events) = init_with_poll();
+    let mut listener = TcpListener::bind(any_local_address()).unwrap();
+    poll.registry()
+        .register(&mut listener, ID1, Interest::READABLE)
+        .unwrap();
+
+    let mut options = AcceptedOptions::default();
+    options.nodelay = Some(true);
+    options.keepalive = Some(TcpKeepalive::new().with_time(Duration::from_secs(30)));
+    options.recv_buf = Some(64 * 1024);
+    options.send_buf = Some(64 * 1024);
+
+    let _client = net::TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+
+    let (stream, _) = listener.accept_with(&options).unwrap();
+    assert!(stream.nodelay().unwrap());
+
+    let socket = unsafe { TcpSocket::from_raw_fd(stream.into_raw_fd()) };
+    assert!(socket.get_keepalive().unwrap());
+    assert_eq!(
+        socket.get_keepalive_time().unwrap(),
+        Some(Duration::from_secs(30))
+    );
+    // Linux doubles the value.
+    assert!(socket.get_recv_buffer_size().unwrap() >= 64 * 1024);
+    assert!(socket.get_send_buffer_size().unwrap() >= 64 * 1024);
+
+    assert_would_block(listener.accept_with(&options));
+}
@@ -9,7 +9,7 @@ use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd};
 use std::os::windows::io::{AsRawSocket, FromRawSocket};
 use std::sync::{mpsc::channel, Arc, Barrier};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use mio::net::{TcpSocket, TcpStream};
 use mio::{Interest, Token};
@@ -472,6 +472,34 @@ fn reregistering() {
     thread_handle.join().expect("unable to join thread");
 }
 
+#[test]
+fn writable_once_after_registering() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let (thread_handle, address) = echo_listener(any_local_address(), 1);
+
+    let mut stream = TcpStream::connect(address).unwrap();
+
+    poll.registry()
+        .register(&mut stream, ID1, Interest::WRITABLE)
+        .expect("unable to register TCP stream");
+
+    // The stream is writable (once connected), which should be reported once.
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::WRITABLE)],
+    );
+
+    // Even though the stream stays writable no further events should be
+    // returned, as readiness didn't change.
+    expect_no_events(&mut poll, &mut events);
+    expect_no_events(&mut poll, &mut events);
+
+    drop(stream);
+    thread_handle.join().expect("unable to join thread");
+}
+
 #[test]
 fn no_events_after_deregister() {
     let (mut poll, mut events) = init_with_poll();
@@ -810,3 +838,508 @@ fn set_linger_zero(socket: &TcpStream) {
     s.set_linger(Some(Duration::from_millis(0))).unwrap();
     forget(s);
 }
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn readable_excludes_priority_data() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let client = net::TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (server, _) = listener.accept().unwrap();
+    server.set_nonblocking(true).unwrap();
+    let mut server = TcpStream::from_std(server);
+
+    poll.registry()
+        .register(&mut server, ID1, Interest::READABLE)
+        .unwrap();
+    expect_no_events(&mut poll, &mut events);
+
+    // Send a single byte of out-of-band (priority) data.
+    let n = unsafe { libc::send(client.as_raw_fd(), b"!".as_ptr().cast(), 1, libc::MSG_OOB) };
+    assert_eq!(n, 1);
+
+    // Readable interest doesn't include priority data.
+    expect_no_events(&mut poll, &mut events);
+
+    poll.registry()
+        .reregister(&mut server, ID1, Interest::READABLE | Interest::PRIORITY)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Readiness::PRIORITY)],
+    );
+}
+
+#[test]
+#[cfg(unix)]
+fn socket_introspection() {
+    use mio::net::{Family, Kind, TcpListener};
+
+    let listener = TcpListener::bind(any_local_address()).unwrap();
+    let stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    assert_eq!(stream.socket_family().unwrap(), Family::Ipv4);
+    assert_eq!(stream.socket_type().unwrap(), Kind::Stream);
+    assert!(!stream.is_listening().unwrap());
+}
+
+#[test]
+fn bytes_available() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let mut client = net::TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (server, _) = listener.accept().unwrap();
+    server.set_nonblocking(true).unwrap();
+    let mut server = TcpStream::from_std(server);
+
+    poll.registry()
+        .register(&mut server, ID1, Interest::READABLE)
+        .unwrap();
+    expect_no_events(&mut poll, &mut events);
+    // No data available isn't an error.
+    assert_eq!(server.bytes_available().unwrap(), 0);
+
+    client.write_all(DATA1).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    assert_eq!(server.bytes_available().unwrap(), DATA1.len());
+
+    let mut buf = [0; 20];
+    assert_eq!(server.read(&mut buf).unwrap(), DATA1.len());
+    assert_eq!(server.bytes_available().unwrap(), 0);
+}
+
+#[test]
+fn read_closed_delivered_once() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let client = net::TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (server, _) = listener.accept().unwrap();
+    server.set_nonblocking(true).unwrap();
+    let mut server = TcpStream::from_std(server);
+
+    poll.registry()
+        .register(&mut server, ID1, Interest::READABLE)
+        .unwrap();
+    expect_no_events(&mut poll, &mut events);
+
+    drop(client);
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Readiness::READ_CLOSED)],
+    );
+
+    // The selector is edge-triggered, so the closed readiness isn't returned
+    // again even though the connection stays closed.
+    for _ in 0..5 {
+        expect_no_events(&mut poll, &mut events);
+    }
+}
+
+#[test]
+fn reregister_rearms_readiness() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let mut client = net::TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (server, _) = listener.accept().unwrap();
+    server.set_nonblocking(true).unwrap();
+    let mut server = TcpStream::from_std(server);
+
+    poll.registry()
+        .register(&mut server, ID1, Interest::READABLE)
+        .unwrap();
+    client.write_all(DATA1).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+
+    // Only read part of the data, so we won't get another (edge-triggered)
+    // event: the missed edge.
+    let mut buf = [0; 4];
+    server.read_exact(&mut buf).unwrap();
+    expect_no_events(&mut poll, &mut events);
+
+    // Reregistering rearms the readiness.
+    poll.registry()
+        .reregister(&mut server, ID1, Interest::READABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+}
+
+#[test]
+fn data_readable_after_reset() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let mut stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    poll.registry()
+        .register(&mut stream, ID1, Interest::READABLE)
+        .unwrap();
+
+    let (peer, _) = listener.accept().unwrap();
+    let mut peer = TcpStream::from_std(peer);
+    checked_write!(peer.write(DATA1));
+    // Closing with a zero linger timeout sends a RST.
+    set_linger_zero(&peer);
+    drop(peer);
+
+    // The error is reported together with readable readiness, so reading
+    // returns the last bytes before the error.
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+
+    let mut buf = [0; 20];
+    let n = stream.read(&mut buf).unwrap();
+    assert_eq!(&buf[..n], DATA1);
+    let err = stream.read(&mut buf).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::ConnectionReset);
+}
+
+#[test]
+#[cfg(any(
+    target_os = "android",
+    target_os = "illumos",
+    target_os = "linux",
+    target_os = "solaris"
+))]
+fn read_closed_interest() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let mut client = net::TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (server, _) = listener.accept().unwrap();
+    server.set_nonblocking(true).unwrap();
+    let mut server = TcpStream::from_std(server);
+
+    poll.registry()
+        .register(&mut server, ID1, Interest::READ_CLOSED)
+        .unwrap();
+    expect_no_events(&mut poll, &mut events);
+
+    // Receiving data doesn't trigger an event.
+    client.write_all(DATA1).unwrap();
+    expect_no_events(&mut poll, &mut events);
+
+    client.shutdown(Shutdown::Write).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Readiness::READ_CLOSED)],
+    );
+    assert!(!events.iter().next().unwrap().is_readable());
+
+    // The data can still be read.
+    let mut buf = [0; 16];
+    expect_read!(server.read(&mut buf), DATA1);
+}
+
+/// Runs `race` to completion.
+fn finish_race(
+    poll: &mut mio::Poll,
+    events: &mut mio::Events,
+    race: &mut mio::net::ConnectRace,
+) -> io::Result<TcpStream> {
+    loop {
+        poll.poll(events, race.timeout()).unwrap();
+        for event in events.iter() {
+            if let Some(result) = race.on_event(poll.registry(), event) {
+                return result;
+            }
+        }
+        if let Some(result) = race.on_timeout(poll.registry()) {
+            return result;
+        }
+    }
+}
+
+#[test]
+fn connect_first() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let addr = listener.local_addr().unwrap();
+    // Listener with a full backlog, that doesn't complete new connections (or
+    // refuses them on some platforms).
+    let full = TcpSocket::new_v4().unwrap();
+    full.bind(any_local_address()).unwrap();
+    let full = full.listen(0).unwrap();
+    let blackhole = full.local_addr().unwrap();
+    let fillers: Vec<TcpStream> = (0..4)
+        .map(|_| TcpStream::connect(blackhole).unwrap())
+        .collect();
+    thread::sleep(Duration::from_millis(10));
+
+    let mut race = TcpStream::connect_first(
+        &[blackhole, addr],
+        poll.registry(),
+        ID1,
+        Duration::from_millis(10),
+    )
+    .unwrap();
+    let mut stream = finish_race(&mut poll, &mut events, &mut race).unwrap();
+    assert_eq!(stream.peer_addr().unwrap(), addr);
+    // The loser is closed.
+    assert_eq!(race.in_progress(), 0);
+    assert!(race.timeout().is_none());
+
+    // The winner is deregistered, so it can be registered again.
+    poll.registry()
+        .register(&mut stream, ID2, Interest::WRITABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID2, Interest::WRITABLE)],
+    );
+
+    drop(fillers);
+}
+
+#[test]
+fn connect_first_failure_starts_next() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let addr = listener.local_addr().unwrap();
+    let full = TcpSocket::new_v4().unwrap();
+    full.bind(any_local_address()).unwrap();
+    let full = full.listen(0).unwrap();
+    let blackhole = full.local_addr().unwrap();
+    let fillers: Vec<TcpStream> = (0..4)
+        .map(|_| TcpStream::connect(blackhole).unwrap())
+        .collect();
+    thread::sleep(Duration::from_millis(10));
+    let refused = net::TcpListener::bind(any_local_address())
+        .unwrap()
+        .local_addr()
+        .unwrap();
+
+    // The refused attempt starts after one stagger delay, once it fails the
+    // last attempt must start without waiting for another delay.
+    let stagger = Duration::from_millis(500);
+    let start = Instant::now();
+    let mut race =
+        TcpStream::connect_first(&[blackhole, refused, addr], poll.registry(), ID1, stagger)
+            .unwrap();
+    let stream = finish_race(&mut poll, &mut events, &mut race).unwrap();
+    assert_eq!(stream.peer_addr().unwrap(), addr);
+    assert!(
+        start.elapsed() < stagger * 2,
+        "waited for the stagger delay"
+    );
+
+    drop(fillers);
+}
+
+#[test]
+fn connect_first_all_fail() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let addrs: Vec<SocketAddr> = (0..2)
+        .map(|_| {
+            let listener = net::TcpListener::bind(any_local_address()).unwrap();
+            listener.local_addr().unwrap()
+        })
+        .collect();
+
+    let mut race =
+        TcpStream::connect_first(&addrs, poll.registry(), ID1, Duration::from_millis(10)).unwrap();
+    let err = finish_race(&mut poll, &mut events, &mut race).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::ConnectionRefused);
+    assert_eq!(race.in_progress(), 0);
+
+    let err =
+        TcpStream::connect_first(&[], poll.registry(), ID1, Duration::from_millis(10)).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+}
+
+#[test]
+#[cfg(unix)]
+fn recv_low_water_mark() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let mut stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (mut peer, _) = listener.accept().unwrap();
+
+    stream.set_recv_low_water_mark(DATA1_LEN).unwrap();
+    assert_eq!(stream.recv_low_water_mark().unwrap(), DATA1_LEN);
+
+    poll.registry()
+        .register(&mut stream, ID1, Interest::READABLE)
+        .unwrap();
+    expect_no_events(&mut poll, &mut events);
+
+    // Not enough data to reach the low water mark.
+    peer.write_all(&DATA1[..4]).unwrap();
+    expect_no_events(&mut poll, &mut events);
+
+    peer.write_all(&DATA1[4..]).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    let mut buf = [0; DATA1_LEN + 1];
+    assert_eq!(stream.read(&mut buf).unwrap(), DATA1_LEN);
+    assert_eq!(&buf[..DATA1_LEN], DATA1);
+}
+
+#[test]
+#[cfg(unix)]
+fn send_low_water_mark() {
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+
+    let res = stream.set_send_low_water_mark(4096);
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    assert_eq!(res.unwrap_err().raw_os_error(), Some(libc::ENOPROTOOPT));
+    #[cfg(not(any(target_os = "android", target_os = "linux")))]
+    {
+        res.unwrap();
+        assert_eq!(stream.send_low_water_mark().unwrap(), 4096);
+    }
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn tcp_fast_open_connect() {
+    // Not defined by all supported versions of libc.
+    const TCP_FASTOPEN: libc::c_int = 23;
+    const TCP_FASTOPEN_CONNECT: libc::c_int = 30;
+
+    fn set_option(fd: libc::c_int, opt: libc::c_int, value: libc::c_int) {
+        let res = unsafe {
+            libc::setsockopt(
+                fd,
+                libc::IPPROTO_TCP,
+                opt,
+                &value as *const _ as *const libc::c_void,
+                std::mem::size_of::<libc::c_int>() as libc::socklen_t,
+            )
+        };
+        assert_eq!(res, 0, "{}", io::Error::last_os_error());
+    }
+
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    set_option(listener.as_raw_fd(), TCP_FASTOPEN, 8);
+    let addr = listener.local_addr().unwrap();
+
+    // Connect twice, the first connect gets the TFO cookie, the second one can
+    // send data in the SYN (if enabled in `net.ipv4.tcp_fastopen`).
+    for _ in 0..2 {
+        let socket = TcpSocket::new_v4().unwrap();
+        set_option(socket.as_raw_fd(), TCP_FASTOPEN_CONNECT, 1);
+        // With `TCP_FASTOPEN_CONNECT` the connect is delayed until the first
+        // write, so the socket is writable immediately.
+        let mut stream = socket.connect(addr).unwrap();
+        poll.registry()
+            .register(&mut stream, ID1, Interest::WRITABLE | Interest::READABLE)
+            .unwrap();
+        expect_events(
+            &mut poll,
+            &mut events,
+            vec![ExpectEvent::new(ID1, Interest::WRITABLE)],
+        );
+        // Which must not be mistaken for a failed connect.
+        assert!(events.iter().all(|event| !event.is_error()));
+        assert!(stream.take_error().unwrap().is_none());
+        assert_eq!(stream.peer_addr().unwrap(), addr);
+
+        assert_eq!(stream.write(DATA1).unwrap(), DATA1_LEN);
+        let (mut peer, _) = listener.accept().unwrap();
+        let mut buf = [0; DATA1_LEN];
+        peer.read_exact(&mut buf).unwrap();
+        assert_eq!(buf, DATA1);
+
+        peer.write_all(DATA2).unwrap();
+        expect_events(
+            &mut poll,
+            &mut events,
+            vec![ExpectEvent::new(ID1, Interest::READABLE)],
+        );
+        let mut buf = [0; DATA2_LEN];
+        assert_eq!(stream.read(&mut buf).unwrap(), DATA2_LEN);
+        assert_eq!(buf, DATA2);
+    }
+}
+
+#[test]
+fn connect_result_writable_only() {
+    let (mut poll, mut events) = init_with_poll();
+
+    // Successful connect.
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let addr = listener.local_addr().unwrap();
+    let mut stream = TcpStream::connect(addr).unwrap();
+    poll.registry()
+        .register(&mut stream, ID1, Interest::WRITABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::WRITABLE)],
+    );
+    assert!(events.iter().all(|event| !event.is_error()));
+    assert!(stream.take_error().unwrap().is_none());
+    assert_eq!(stream.peer_addr().unwrap(), addr);
+
+    // Refused connect.
+    drop(listener);
+    let mut stream = match TcpStream::connect(addr) {
+        Ok(stream) => stream,
+        // Connect failed synchronously, nothing to poll for.
+        Err(ref err) if err.kind() == io::ErrorKind::ConnectionRefused => return,
+        Err(err) => panic!("unexpected error: {}", err),
+    };
+    poll.registry()
+        .register(&mut stream, ID2, Interest::WRITABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID2, Interest::WRITABLE)],
+    );
+    assert!(events.iter().any(|event| event.is_error()), "{:?}", events);
+    let err = stream.take_error().unwrap().unwrap();
+    assert_eq!(err.kind(), io::ErrorKind::ConnectionRefused);
+}
+
+#[test]
+fn buffer_sizes() {
+    init();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let _peer = listener.accept().unwrap();
+
+    const SIZE: u32 = 64 * 1024;
+    stream.set_recv_buffer_size(SIZE).unwrap();
+    stream.set_send_buffer_size(SIZE).unwrap();
+    // Linux doubles the value.
+    #[cfg(target_os = "linux")]
+    let expected = 2 * SIZE;
+    #[cfg(not(target_os = "linux"))]
+    let expected = SIZE;
+    assert_eq!(stream.recv_buffer_size().unwrap(), expected);
+    assert_eq!(stream.send_buffer_size().unwrap(), expected);
+}
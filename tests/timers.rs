@@ -0,0 +1,207 @@
+#![cfg(feature = "os-poll")]
+
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use mio::timers::{poll_timeout, poll_with_timers, TimerQueue};
+use mio::{Token, Waker};
+
+mod util;
+use util::{assert_send, assert_sync, init_with_poll};
+
+const WAKE_TOKEN: Token = Token(10);
+
+#[test]
+fn is_send_and_sync() {
+    assert_send::<TimerQueue>();
+    assert_sync::<TimerQueue>();
+}
+
+#[test]
+fn timer_queue_ordering() {
+    let mut timers = TimerQueue::new();
+    assert!(timers.is_empty());
+    assert_eq!(timers.next_deadline(), None);
+
+    let now = Instant::now();
+    let ms = Duration::from_millis;
+    timers.add(now + ms(30), Token(3));
+    timers.add(now + ms(10), Token(1));
+    timers.add(now + ms(40), Token(4));
+    timers.add(now + ms(20), Token(2));
+    assert_eq!(timers.len(), 4);
+    assert_eq!(timers.next_deadline(), Some(now + ms(10)));
+
+    assert_eq!(timers.expired(now).next(), None);
+    assert_eq!(
+        timers.expired(now + ms(25)).collect::<Vec<_>>(),
+        vec![Token(1), Token(2)]
+    );
+    assert_eq!(timers.len(), 2);
+    assert_eq!(timers.next_deadline(), Some(now + ms(30)));
+
+    // Only take a single expired timer, the other remains in the queue.
+    assert_eq!(timers.expired(now + ms(50)).next(), Some(Token(3)));
+    assert_eq!(
+        timers.expired(now + ms(50)).collect::<Vec<_>>(),
+        vec![Token(4)]
+    );
+    assert!(timers.is_empty());
+    assert_eq!(timers.next_deadline(), None);
+}
+
+#[test]
+fn timer_queue_cancel() {
+    let mut timers = TimerQueue::new();
+
+    let now = Instant::now();
+    let ms = Duration::from_millis;
+    let handle1 = timers.add(now + ms(10), Token(1));
+    let handle2 = timers.add(now + ms(20), Token(2));
+    let handle3 = timers.add(now + ms(30), Token(3));
+
+    assert!(timers.cancel(handle1));
+    // Can't cancel twice.
+    assert!(!timers.cancel(handle1));
+    assert_eq!(timers.len(), 2);
+    assert_eq!(timers.next_deadline(), Some(now + ms(20)));
+
+    assert!(timers.cancel(handle3));
+    assert_eq!(
+        timers.expired(now + ms(50)).collect::<Vec<_>>(),
+        vec![Token(2)]
+    );
+    // Can't cancel an expired timer.
+    assert!(!timers.cancel(handle2));
+
+    // Reusing an internal slot must not allow the old handle to cancel the
+    // new timer.
+    let handle4 = timers.add(now + ms(40), Token(4));
+    assert!(!timers.cancel(handle1));
+    assert!(!timers.cancel(handle3));
+    assert_eq!(timers.len(), 1);
+    assert!(timers.cancel(handle4));
+    assert!(timers.is_empty());
+    assert_eq!(timers.next_deadline(), None);
+}
+
+#[test]
+fn timer_queue_cancel_many() {
+    let mut timers = TimerQueue::new();
+
+    let now = Instant::now();
+    let last = timers.add(now + Duration::from_secs(1), Token(0));
+    // Repeatedly adding and cancelling timers, the remaining timer must not be
+    // affected.
+    for n in 1..1000 {
+        let handle = timers.add(now + Duration::from_millis(n), Token(n as usize));
+        assert!(timers.cancel(handle));
+    }
+    assert_eq!(timers.len(), 1);
+    assert_eq!(timers.next_deadline(), Some(now + Duration::from_secs(1)));
+    assert!(timers.cancel(last));
+    assert!(timers.is_empty());
+}
+
+#[test]
+fn poll_with_timers_timeout() {
+    let (mut poll, mut events) = init_with_poll();
+    let mut timers = TimerQueue::new();
+    let mut expired = Vec::new();
+
+    const TIMEOUT: Duration = Duration::from_millis(20);
+    let start = Instant::now();
+    timers.add(start + TIMEOUT, Token(0));
+    // Far enough apart that a late wake up still only expires the first
+    // timer.
+    timers.add(start + TIMEOUT * 10, Token(1));
+
+    poll_with_timers(&mut poll, &mut events, &mut timers, None, &mut expired).unwrap();
+    let elapsed = start.elapsed();
+    assert!(events.is_empty());
+    assert_eq!(expired, vec![Token(0)]);
+    // Only check the lower bound, how late the thread is scheduled again
+    // depends on the load of the machine.
+    assert!(elapsed >= TIMEOUT, "expired after {:?}", elapsed);
+
+    poll_with_timers(&mut poll, &mut events, &mut timers, None, &mut expired).unwrap();
+    assert!(start.elapsed() >= TIMEOUT * 10);
+    assert_eq!(expired, vec![Token(1)]);
+
+    // The timeout is still respected.
+    timers.add(Instant::now() + Duration::from_secs(10), Token(2));
+    let start = Instant::now();
+    poll_with_timers(
+        &mut poll,
+        &mut events,
+        &mut timers,
+        Some(Duration::from_millis(10)),
+        &mut expired,
+    )
+    .unwrap();
+    assert!(start.elapsed() < Duration::from_secs(1));
+    assert!(expired.is_empty());
+}
+
+#[test]
+fn poll_with_timers_events() {
+    let (mut poll, mut events) = init_with_poll();
+    let mut timers = TimerQueue::new();
+    let mut expired = Vec::new();
+
+    let waker = Arc::new(Waker::new(poll.registry(), WAKE_TOKEN).unwrap());
+    timers.add(Instant::now() + Duration::from_secs(10), Token(0));
+
+    let waker1 = waker.clone();
+    let handle = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(10));
+        waker1.wake().unwrap();
+    });
+
+    // Wakes up for the event, not the timer.
+    poll_with_timers(&mut poll, &mut events, &mut timers, None, &mut expired).unwrap();
+    assert!(expired.is_empty());
+    assert_eq!(events.iter().next().unwrap().token(), WAKE_TOKEN);
+    assert_eq!(timers.len(), 1);
+
+    handle.join().unwrap();
+}
+
+#[test]
+fn poll_timeout_deadlines() {
+    let now = Instant::now();
+
+    // No deadlines, block until an event arrives.
+    assert_eq!(poll_timeout(std::iter::empty(), now), None);
+
+    // Minimum of the deadlines in the future.
+    let deadlines = vec![
+        now + Duration::from_millis(30),
+        now + Duration::from_millis(10),
+        now + Duration::from_millis(20),
+    ];
+    assert_eq!(
+        poll_timeout(deadlines.into_iter(), now),
+        Some(Duration::from_millis(10))
+    );
+
+    // Rounded up to the next millisecond.
+    let deadline = now + Duration::from_micros(10_001);
+    assert_eq!(
+        poll_timeout(std::iter::once(deadline), now),
+        Some(Duration::from_millis(11))
+    );
+
+    // Deadline is now or already passed.
+    assert_eq!(
+        poll_timeout(std::iter::once(now), now),
+        Some(Duration::from_millis(0))
+    );
+    let later = now + Duration::from_millis(50);
+    let deadlines = vec![now + Duration::from_millis(100), now];
+    assert_eq!(
+        poll_timeout(deadlines.into_iter(), later),
+        Some(Duration::from_millis(0))
+    );
+}
@@ -1130,3 +1130,346 @@ fn et_behavior_recv_from() {
     assert!(socket1.take_error().unwrap().is_none());
     assert!(socket2.take_error().unwrap().is_none());
 }
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn connected_udp_socket_error_event() {
+    use std::io;
+    use util::Readiness;
+
+    let (mut poll, mut events) = init_with_poll();
+
+    // Get a local address that nobody is listening on.
+    let closed = net::UdpSocket::bind(any_local_address()).unwrap();
+    let closed_address = closed.local_addr().unwrap();
+    drop(closed);
+
+    let mut socket = UdpSocket::bind(any_local_address()).unwrap();
+    socket.connect(closed_address).unwrap();
+    poll.registry()
+        .register(&mut socket, ID1, Interest::READABLE)
+        .unwrap();
+    expect_no_events(&mut poll, &mut events);
+
+    // The ICMP port unreachable reply is reported as error.
+    checked_write!(socket.send(DATA1));
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Readiness::ERROR)],
+    );
+
+    let err = socket.take_error().unwrap().expect("no pending error");
+    assert_eq!(err.kind(), io::ErrorKind::ConnectionRefused);
+    assert!(socket.take_error().unwrap().is_none());
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn band_writable() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut socket1 = UdpSocket::bind(any_local_address()).unwrap();
+    let mut socket2 = UdpSocket::bind(any_local_address()).unwrap();
+    poll.registry()
+        .register(&mut socket1, ID1, Interest::BAND_WRITABLE)
+        .unwrap();
+    poll.registry()
+        .register(&mut socket2, ID2, Interest::WRITABLE)
+        .unwrap();
+
+    let mut band_writable = false;
+    let mut writable = false;
+    for _ in 0..3 {
+        poll.poll(&mut events, Some(Duration::from_millis(100)))
+            .unwrap();
+        for event in events.iter() {
+            match event.token() {
+                ID1 => {
+                    assert!(event.is_band_writable());
+                    assert!(!event.is_writable());
+                    band_writable = true;
+                }
+                ID2 => {
+                    assert!(event.is_writable());
+                    assert!(!event.is_band_writable());
+                    writable = true;
+                }
+                token => panic!("unexpected event: {:?}", token),
+            }
+        }
+        if band_writable && writable {
+            return;
+        }
+    }
+    panic!(
+        "missing events, band writable: {}, writable: {}",
+        band_writable, writable
+    );
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn oneshot() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut socket1 = UdpSocket::bind(any_local_address()).unwrap();
+    let socket2 = UdpSocket::bind(any_local_address()).unwrap();
+    socket2.connect(socket1.local_addr().unwrap()).unwrap();
+    poll.registry()
+        .register(&mut socket1, ID1, Interest::READABLE | Interest::ONESHOT)
+        .unwrap();
+
+    checked_write!(socket2.send(DATA1));
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+
+    // The registration is disabled after the first event, so another
+    // readiness change must not be reported.
+    checked_write!(socket2.send(DATA2));
+    expect_no_events(&mut poll, &mut events);
+
+    // Reregistering enables it again, the socket is still readable.
+    poll.registry()
+        .reregister(&mut socket1, ID1, Interest::READABLE | Interest::ONESHOT)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    expect_no_events(&mut poll, &mut events);
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn oneshot_without_readiness() {
+    use std::io;
+
+    let (poll, _) = init_with_poll();
+
+    let mut socket = UdpSocket::bind(any_local_address()).unwrap();
+    let err = poll
+        .registry()
+        .register(&mut socket, ID1, Interest::ONESHOT)
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+
+    poll.registry()
+        .register(&mut socket, ID1, Interest::READABLE | Interest::ONESHOT)
+        .unwrap();
+    let err = poll
+        .registry()
+        .reregister(&mut socket, ID1, Interest::ONESHOT)
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+}
+
+#[test]
+#[cfg(unix)]
+fn socket_introspection() {
+    use mio::net::{Family, Kind};
+
+    let socket = UdpSocket::bind(any_local_address()).unwrap();
+    assert_eq!(socket.socket_family().unwrap(), Family::Ipv4);
+    assert_eq!(socket.socket_type().unwrap(), Kind::Datagram);
+    assert!(!socket.is_listening().unwrap());
+
+    let socket = UdpSocket::bind(any_local_ipv6_address()).unwrap();
+    assert_eq!(socket.socket_family().unwrap(), Family::Ipv6);
+}
+
+#[test]
+fn next_datagram_size() {
+    let (mut poll, mut events) = init_with_poll();
+    let s1 = UdpSocket::bind(any_local_address()).unwrap();
+    let mut s2 = UdpSocket::bind(any_local_address()).unwrap();
+
+    poll.registry()
+        .register(&mut s2, ID1, Interest::READABLE)
+        .unwrap();
+    // No datagram queued isn't an error.
+    assert_eq!(s2.next_datagram_size().unwrap(), 0);
+
+    let address = s2.local_addr().unwrap();
+    checked_write!(s1.send_to(DATA1, address));
+    checked_write!(s1.send_to(DATA2, address));
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+
+    let size = s2.next_datagram_size().unwrap();
+    if cfg!(any(target_os = "android", target_os = "linux", windows)) {
+        assert_eq!(size, DATA1.len());
+    } else {
+        // Other platforms return the size of all queued datagrams.
+        assert!(size >= DATA1.len());
+    }
+
+    let mut buf = [0; 20];
+    expect_read!(s2.recv_from(&mut buf), DATA1, s1.local_addr().unwrap());
+    if cfg!(any(target_os = "android", target_os = "linux", windows)) {
+        assert_eq!(s2.next_datagram_size().unwrap(), DATA2.len());
+    }
+}
+
+#[test]
+#[cfg(unix)]
+fn vectored_datagrams() {
+    use std::io::{IoSlice, IoSliceMut};
+
+    let (mut poll, mut events) = init_with_poll();
+    let s1 = UdpSocket::bind(any_local_address()).unwrap();
+    let mut s2 = UdpSocket::bind(any_local_address()).unwrap();
+    let address1 = s1.local_addr().unwrap();
+    let address2 = s2.local_addr().unwrap();
+
+    poll.registry()
+        .register(&mut s2, ID1, Interest::READABLE)
+        .unwrap();
+
+    let mut buf1 = [0; 4];
+    let mut buf2 = [0; 4];
+    let mut buf3 = [0; 8];
+    assert_would_block(s2.recv_from_vectored(&mut [IoSliceMut::new(&mut buf1)]));
+
+    // Each call sends a single datagram.
+    let bufs = [
+        IoSlice::new(&DATA1[..4]),
+        IoSlice::new(&DATA1[4..8]),
+        IoSlice::new(&DATA1[8..]),
+    ];
+    assert_eq!(s1.send_to_vectored(&bufs, address2).unwrap(), DATA1.len());
+    checked_write!(s1.send_to(DATA2, address2));
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+
+    let mut bufs = [
+        IoSliceMut::new(&mut buf1),
+        IoSliceMut::new(&mut buf2),
+        IoSliceMut::new(&mut buf3),
+    ];
+    let (n, address) = s2.recv_from_vectored(&mut bufs).unwrap();
+    assert_eq!(n, DATA1.len());
+    assert_eq!(address, address1);
+    assert_eq!(&buf1, &DATA1[..4]);
+    assert_eq!(&buf2, &DATA1[4..8]);
+    assert_eq!(&buf3[..4], &DATA1[8..]);
+
+    // The second datagram isn't merged with the first.
+    let mut buf = [0; 20];
+    expect_read!(s2.recv_from(&mut buf), DATA2, address1);
+    assert_would_block(s2.recv_from(&mut buf));
+
+    // Connected variants.
+    s1.connect(address2).unwrap();
+    s2.connect(address1).unwrap();
+    let bufs = [
+        IoSlice::new(&DATA2[..2]),
+        IoSlice::new(&DATA2[2..5]),
+        IoSlice::new(&DATA2[5..]),
+    ];
+    assert_eq!(s1.send_vectored(&bufs).unwrap(), DATA2.len());
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    let (mut buf1, mut buf2, mut buf3) = ([0; 2], [0; 3], [0; 8]);
+    let mut bufs = [
+        IoSliceMut::new(&mut buf1),
+        IoSliceMut::new(&mut buf2),
+        IoSliceMut::new(&mut buf3),
+    ];
+    assert_eq!(s2.recv_vectored(&mut bufs).unwrap(), DATA2.len());
+    assert_eq!(&buf1, &DATA2[..2]);
+    assert_eq!(&buf2, &DATA2[2..5]);
+    assert_eq!(&buf3[..6], &DATA2[5..]);
+    assert_would_block(s2.recv_vectored(&mut [IoSliceMut::new(&mut buf)]));
+
+    // Rather than sending a truncated datagram too many buffers is an error.
+    let bufs = vec![IoSlice::new(b"a"); 1025];
+    assert_error(s1.send_vectored(&bufs), "too many buffers");
+}
+
+#[test]
+#[cfg(unix)]
+fn recv_truncated_datagram() {
+    let (mut poll, mut events) = init_with_poll();
+    let s1 = UdpSocket::bind(any_local_address()).unwrap();
+    let mut s2 = UdpSocket::bind(any_local_address()).unwrap();
+    let address1 = s1.local_addr().unwrap();
+    let address2 = s2.local_addr().unwrap();
+
+    poll.registry()
+        .register(&mut s2, ID1, Interest::READABLE)
+        .unwrap();
+
+    let mut buf = [0; 4];
+    assert_would_block(s2.recv_from_full(&mut buf));
+
+    checked_write!(s1.send_to(DATA1, address2));
+    checked_write!(s1.send_to(DATA2, address2));
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+
+    let (n, truncated, address) = s2.recv_from_full(&mut buf).unwrap();
+    assert!(truncated);
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    assert_eq!(n, DATA1.len());
+    #[cfg(not(any(target_os = "android", target_os = "linux")))]
+    assert_eq!(n, buf.len());
+    assert_eq!(address, address1);
+    assert_eq!(&buf, &DATA1[..4]);
+
+    // A datagram that fits isn't truncated.
+    let mut buf = [0; 20];
+    let (n, truncated, address) = s2.recv_from_full(&mut buf).unwrap();
+    assert!(!truncated);
+    assert_eq!(n, DATA2.len());
+    assert_eq!(address, address1);
+    assert_eq!(&buf[..n], DATA2);
+
+    // Connected variant.
+    s1.connect(address2).unwrap();
+    s2.connect(address1).unwrap();
+    checked_write!(s1.send(DATA1));
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    let mut buf = [0; 4];
+    let (_, truncated) = s2.recv_full(&mut buf).unwrap();
+    assert!(truncated);
+    assert_would_block(s2.recv_full(&mut buf));
+}
+
+#[test]
+fn buffer_sizes() {
+    init();
+
+    let socket = UdpSocket::bind(any_local_address()).unwrap();
+
+    const SIZE: u32 = 64 * 1024;
+    socket.set_recv_buffer_size(SIZE).unwrap();
+    socket.set_send_buffer_size(SIZE).unwrap();
+    // Linux doubles the value.
+    #[cfg(target_os = "linux")]
+    let expected = 2 * SIZE;
+    #[cfg(not(target_os = "linux"))]
+    let expected = SIZE;
+    assert_eq!(socket.recv_buffer_size().unwrap(), expected);
+    assert_eq!(socket.send_buffer_size().unwrap(), expected);
+}
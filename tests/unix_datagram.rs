@@ -1,7 +1,8 @@
 #![cfg(all(unix, feature = "os-poll", feature = "net"))]
 
-use mio::net::UnixDatagram;
+use mio::net::{BindOptions, UnixDatagram};
 use mio::{Interest, Token};
+use std::fs;
 use std::io;
 use std::net::Shutdown;
 use std::os::unix::net;
@@ -26,6 +27,28 @@ fn is_send_and_sync() {
     assert_sync::<UnixDatagram>();
 }
 
+#[test]
+fn unix_datagram_bind_with() {
+    init();
+
+    let path = temp_file("unix_datagram_bind_with");
+    drop(UnixDatagram::bind(&path).unwrap());
+
+    let mut options = BindOptions::default();
+    options.unlink_existing = true;
+    options.unlink_on_drop = true;
+    let socket = UnixDatagram::bind_with(&path, &options).unwrap();
+    assert!(path.exists());
+    drop(socket);
+    assert!(!path.exists());
+
+    // Refuse to remove a file that is not a socket.
+    fs::write(&path, b"not a socket").unwrap();
+    let err = UnixDatagram::bind_with(&path, &options).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::AlreadyExists);
+    assert!(path.exists());
+}
+
 #[test]
 fn unix_datagram_smoke_unconnected() {
     init();
@@ -398,3 +421,157 @@ fn smoke_test_connected(mut datagram1: UnixDatagram, mut datagram2: UnixDatagram
     assert!(datagram1.take_error().unwrap().is_none());
     assert!(datagram2.take_error().unwrap().is_none());
 }
+
+#[test]
+fn unix_datagram_socket_introspection() {
+    use mio::net::{Family, Kind};
+
+    let datagram = UnixDatagram::unbound().unwrap();
+    assert_eq!(datagram.socket_family().unwrap(), Family::Unix);
+    assert_eq!(datagram.socket_type().unwrap(), Kind::Datagram);
+    assert!(!datagram.is_listening().unwrap());
+}
+
+#[test]
+fn unix_datagram_next_datagram_size() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let (s1, mut s2) = UnixDatagram::pair().unwrap();
+    poll.registry()
+        .register(&mut s2, TOKEN_1, Interest::READABLE)
+        .unwrap();
+    assert_eq!(s2.next_datagram_size().unwrap(), 0);
+
+    checked_write!(s1.send(DATA1));
+    checked_write!(s1.send(DATA2));
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(TOKEN_1, Interest::READABLE)],
+    );
+    let size = s2.next_datagram_size().unwrap();
+    if cfg!(any(target_os = "android", target_os = "linux")) {
+        assert_eq!(size, DATA1.len());
+    } else {
+        assert!(size >= DATA1.len());
+    }
+}
+
+#[test]
+fn unix_datagram_vectored() {
+    use std::io::{IoSlice, IoSliceMut};
+
+    let (mut poll, mut events) = init_with_poll();
+    let path1 = temp_file("unix_datagram_vectored1");
+    let path2 = temp_file("unix_datagram_vectored2");
+    let s1 = UnixDatagram::bind(&path1).unwrap();
+    let mut s2 = UnixDatagram::bind(&path2).unwrap();
+
+    poll.registry()
+        .register(&mut s2, TOKEN_1, Interest::READABLE)
+        .unwrap();
+
+    let mut buf = [0; DEFAULT_BUF_SIZE];
+    assert_would_block(s2.recv_from_vectored(&mut [IoSliceMut::new(&mut buf)]));
+
+    // Each call sends a single datagram.
+    let bufs = [
+        IoSlice::new(&DATA1[..4]),
+        IoSlice::new(&DATA1[4..8]),
+        IoSlice::new(&DATA1[8..]),
+    ];
+    assert_eq!(s1.send_to_vectored(&bufs, &path2).unwrap(), DATA1.len());
+    checked_write!(s1.send_to(DATA2, &path2));
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(TOKEN_1, Interest::READABLE)],
+    );
+
+    let (mut buf1, mut buf2, mut buf3) = ([0; 4], [0; 4], [0; DEFAULT_BUF_SIZE]);
+    let mut bufs = [
+        IoSliceMut::new(&mut buf1),
+        IoSliceMut::new(&mut buf2),
+        IoSliceMut::new(&mut buf3),
+    ];
+    let (n, address) = s2.recv_from_vectored(&mut bufs).unwrap();
+    assert_eq!(n, DATA1.len());
+    assert_eq!(address.as_pathname(), Some(&*path1));
+    assert_eq!(&buf1, &DATA1[..4]);
+    assert_eq!(&buf2, &DATA1[4..8]);
+    assert_eq!(&buf3[..n - 8], &DATA1[8..]);
+
+    // The second datagram isn't merged with the first.
+    expect_read!(s2.recv(&mut buf), DATA2);
+    assert_would_block(s2.recv(&mut buf));
+
+    // Connected variants.
+    let (s1, mut s2) = UnixDatagram::pair().unwrap();
+    poll.registry()
+        .register(&mut s2, TOKEN_2, Interest::READABLE)
+        .unwrap();
+    let bufs = [
+        IoSlice::new(&DATA2[..2]),
+        IoSlice::new(&DATA2[2..5]),
+        IoSlice::new(&DATA2[5..]),
+    ];
+    assert_eq!(s1.send_vectored(&bufs).unwrap(), DATA2.len());
+    checked_write!(s1.send(DATA1));
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(TOKEN_2, Interest::READABLE)],
+    );
+    let (mut buf1, mut buf2, mut buf3) = ([0; 2], [0; 3], [0; DEFAULT_BUF_SIZE]);
+    let mut bufs = [
+        IoSliceMut::new(&mut buf1),
+        IoSliceMut::new(&mut buf2),
+        IoSliceMut::new(&mut buf3),
+    ];
+    let n = s2.recv_vectored(&mut bufs).unwrap();
+    assert_eq!(n, DATA2.len());
+    assert_eq!(&buf1, &DATA2[..2]);
+    assert_eq!(&buf2, &DATA2[2..5]);
+    assert_eq!(&buf3[..n - 5], &DATA2[5..]);
+    expect_read!(s2.recv(&mut buf), DATA1);
+    assert_would_block(s2.recv_vectored(&mut [IoSliceMut::new(&mut buf)]));
+}
+
+#[test]
+fn unix_datagram_recv_truncated() {
+    let (mut poll, mut events) = init_with_poll();
+    let path1 = temp_file("unix_datagram_recv_truncated1");
+    let path2 = temp_file("unix_datagram_recv_truncated2");
+    let s1 = UnixDatagram::bind(&path1).unwrap();
+    let mut s2 = UnixDatagram::bind(&path2).unwrap();
+
+    poll.registry()
+        .register(&mut s2, TOKEN_1, Interest::READABLE)
+        .unwrap();
+
+    let mut buf = [0; 4];
+    assert_would_block(s2.recv_from_full(&mut buf));
+
+    checked_write!(s1.send_to(DATA1, &path2));
+    checked_write!(s1.send_to(DATA2, &path2));
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(TOKEN_1, Interest::READABLE)],
+    );
+
+    let (n, truncated, address) = s2.recv_from_full(&mut buf).unwrap();
+    assert!(truncated);
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    assert_eq!(n, DATA1.len());
+    #[cfg(not(any(target_os = "android", target_os = "linux")))]
+    assert_eq!(n, buf.len());
+    assert_eq!(address.as_pathname(), Some(&*path1));
+    assert_eq!(&buf, &DATA1[..4]);
+
+    let mut buf = [0; DEFAULT_BUF_SIZE];
+    let (n, truncated) = s2.recv_full(&mut buf).unwrap();
+    assert!(!truncated);
+    assert_eq!(&buf[..n], DATA2);
+    assert_would_block(s2.recv_full(&mut buf));
+}
@@ -0,0 +1,63 @@
+#![cfg(all(target_os = "linux", feature = "os-poll", feature = "os-ext"))]
+
+use std::ffi::OsStr;
+use std::fs::{self, File};
+
+use mio::unix::inotify::Inotify;
+use mio::{Interest, Token};
+
+mod util;
+use util::{
+    assert_would_block, expect_events, expect_no_events, init_with_poll, temp_file, ExpectEvent,
+};
+
+const INOTIFY: Token = Token(0);
+
+#[test]
+fn inotify_file_created() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let dir = temp_file("unix_inotify::inotify_file_created");
+    fs::create_dir(&dir).unwrap();
+
+    let mut inotify = Inotify::new().unwrap();
+    let wd = inotify.add_watch(&dir, libc::IN_CREATE).unwrap();
+    poll.registry()
+        .register(&mut inotify, INOTIFY, Interest::READABLE)
+        .unwrap();
+    expect_no_events(&mut poll, &mut events);
+    let mut buf = [0; 4096];
+    assert_would_block(inotify.read_events(&mut buf));
+
+    File::create(dir.join("created")).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(INOTIFY, Interest::READABLE)],
+    );
+
+    let received: Vec<_> = inotify
+        .read_events(&mut buf)
+        .unwrap()
+        .map(|event| (event.wd(), event.mask(), event.name().map(OsStr::to_owned)))
+        .collect();
+    assert_eq!(
+        received,
+        vec![(wd, libc::IN_CREATE, Some("created".into()))]
+    );
+    assert_would_block(inotify.read_events(&mut buf));
+
+    // Removing the watch queues an `IN_IGNORED` event.
+    inotify.remove_watch(wd).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(INOTIFY, Interest::READABLE)],
+    );
+    let mut received = inotify.read_events(&mut buf).unwrap();
+    let event = received.next().unwrap();
+    assert_eq!(event.wd(), wd);
+    assert_eq!(event.mask(), libc::IN_IGNORED);
+    assert_eq!(event.name(), None);
+    assert!(received.next().is_none());
+}
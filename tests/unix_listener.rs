@@ -1,8 +1,10 @@
 #![cfg(all(unix, feature = "os-poll", feature = "net"))]
 
-use mio::net::UnixListener;
+use mio::net::{BindOptions, UnixListener};
 use mio::{Interest, Token};
+use std::fs;
 use std::io::{self, Read};
+use std::os::unix::fs::{FileTypeExt, PermissionsExt};
 use std::os::unix::net;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Barrier};
@@ -12,7 +14,8 @@ use std::thread;
 mod util;
 use util::{
     assert_send, assert_socket_close_on_exec, assert_socket_non_blocking, assert_sync,
-    assert_would_block, expect_events, expect_no_events, init_with_poll, temp_file, ExpectEvent,
+    assert_would_block, expect_events, expect_no_events, init, init_with_poll, temp_file,
+    ExpectEvent,
 };
 
 const DEFAULT_BUF_SIZE: usize = 64;
@@ -133,6 +136,78 @@ fn unix_listener_deregister() {
     handle.join().unwrap();
 }
 
+#[test]
+fn unix_listener_bind_with_default_options() {
+    #[allow(clippy::redundant_closure)]
+    smoke_test(
+        |path| UnixListener::bind_with(path, &BindOptions::default()),
+        "unix_listener_bind_with_default_options",
+    );
+}
+
+#[test]
+fn unix_listener_bind_with_unlink_existing() {
+    init();
+
+    let path = temp_file("unix_listener_bind_with_unlink_existing");
+    // Leaves a stale socket file behind.
+    drop(UnixListener::bind(&path).unwrap());
+    assert!(fs::symlink_metadata(&path).unwrap().file_type().is_socket());
+
+    // Default behaviour is unchanged.
+    let err = UnixListener::bind(&path).unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::EADDRINUSE));
+
+    let mut options = BindOptions::default();
+    options.unlink_existing = true;
+    let listener = UnixListener::bind_with(&path, &options).unwrap();
+    net::UnixStream::connect(&path).unwrap();
+    drop(listener);
+
+    // `unlink_on_drop` wasn't set.
+    assert!(path.exists());
+}
+
+#[test]
+fn unix_listener_bind_with_unlink_existing_regular_file() {
+    init();
+
+    let path = temp_file("unix_listener_bind_with_unlink_existing_regular_file");
+    fs::write(&path, b"not a socket").unwrap();
+
+    let mut options = BindOptions::default();
+    options.unlink_existing = true;
+    let err = UnixListener::bind_with(&path, &options).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::AlreadyExists);
+    // The file should be untouched.
+    assert_eq!(fs::read(&path).unwrap(), b"not a socket");
+}
+
+#[test]
+fn unix_listener_bind_with_unlink_on_drop() {
+    init();
+
+    let path = temp_file("unix_listener_bind_with_unlink_on_drop");
+    let mut options = BindOptions::default();
+    options.unlink_on_drop = true;
+    let listener = UnixListener::bind_with(&path, &options).unwrap();
+    assert!(path.exists());
+    drop(listener);
+    assert!(!path.exists());
+}
+
+#[test]
+fn unix_listener_bind_with_mode() {
+    init();
+
+    let path = temp_file("unix_listener_bind_with_mode");
+    let mut options = BindOptions::default();
+    options.mode = Some(0o600);
+    let _listener = UnixListener::bind_with(&path, &options).unwrap();
+    let mode = fs::metadata(&path).unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, 0o600);
+}
+
 fn smoke_test<F>(new_listener: F, test_name: &'static str)
 where
     F: FnOnce(&Path) -> io::Result<UnixListener>,
@@ -187,3 +262,72 @@ fn open_connections(
         }
     })
 }
+
+#[test]
+fn unix_listener_socket_introspection() {
+    use mio::net::{Family, Kind};
+
+    let path = temp_file("unix_listener_socket_introspection");
+    let listener = UnixListener::bind(&path).unwrap();
+    assert_eq!(listener.socket_family().unwrap(), Family::Unix);
+    assert_eq!(listener.socket_type().unwrap(), Kind::Stream);
+    assert!(listener.is_listening().unwrap());
+}
+
+#[test]
+fn unix_any_socket_from_raw_fd() {
+    use mio::net::{AnySocket, UnixDatagram, UnixStream};
+    use std::os::unix::io::IntoRawFd;
+
+    let path = temp_file("unix_any_socket_from_raw_fd");
+    let listener = UnixListener::bind(&path).unwrap();
+    let socket = unsafe { AnySocket::from_raw_fd(listener.into_raw_fd()).unwrap() };
+    assert!(matches!(socket, AnySocket::UnixListener(..)));
+
+    let (stream, _) = UnixStream::pair().unwrap();
+    let socket = unsafe { AnySocket::from_raw_fd(stream.into_raw_fd()).unwrap() };
+    assert!(matches!(socket, AnySocket::UnixStream(..)));
+
+    let datagram = UnixDatagram::unbound().unwrap();
+    let socket = unsafe { AnySocket::from_raw_fd(datagram.into_raw_fd()).unwrap() };
+    assert!(matches!(socket, AnySocket::UnixDatagram(..)));
+}
+
+#[test]
+fn unix_listener_accept_all_pending() {
+    let (mut poll, mut events) = init_with_poll();
+    let path = temp_file("unix_listener_accept_all_pending");
+
+    let mut listener = UnixListener::bind(&path).unwrap();
+    poll.registry()
+        .register(&mut listener, TOKEN_1, Interest::READABLE)
+        .unwrap();
+
+    let clients: Vec<_> = (0..3)
+        .map(|_| net::UnixStream::connect(&path).unwrap())
+        .collect();
+
+    // Readiness is edge-triggered, so a single event is returned for all
+    // pending connections and we need to accept until `WouldBlock`.
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(TOKEN_1, Interest::READABLE)],
+    );
+
+    let mut accepted = 0;
+    loop {
+        match listener.accept() {
+            Ok((stream, addr)) => {
+                // The clients aren't bound to a path.
+                assert!(addr.is_unnamed());
+                assert_eq!(stream.local_addr().unwrap().as_pathname(), Some(&*path));
+                accepted += 1;
+            }
+            Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => break,
+            Err(err) => panic!("unexpected error: {}", err),
+        }
+    }
+    assert_eq!(accepted, clients.len());
+    expect_no_events(&mut poll, &mut events);
+}
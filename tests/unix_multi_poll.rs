@@ -0,0 +1,107 @@
+#![cfg(all(unix, feature = "os-poll", feature = "os-ext"))]
+
+use std::io::{Read, Write};
+use std::time::Duration;
+
+use mio::unix::multi_poll::MultiPoll;
+use mio::unix::pipe;
+use mio::{Interest, Poll, Token};
+
+mod util;
+use util::{assert_send, assert_sync, init};
+
+const RECEIVER1: Token = Token(0);
+const RECEIVER2: Token = Token(1);
+
+const DATA1: &[u8; 11] = b"Hello world";
+
+#[test]
+fn is_send_and_sync() {
+    assert_send::<MultiPoll>();
+    assert_sync::<MultiPoll>();
+}
+
+#[test]
+fn multi_poll_smoke() {
+    init();
+
+    let mut multi_poll = MultiPoll::new(8).unwrap();
+    let child1 = multi_poll.add(Poll::new().unwrap(), 8).unwrap();
+    let child2 = multi_poll.add(Poll::new().unwrap(), 8).unwrap();
+    assert!(multi_poll.registry(2).is_none());
+
+    let (mut sender1, mut receiver1) = pipe::new().unwrap();
+    let (mut sender2, mut receiver2) = pipe::new().unwrap();
+    multi_poll
+        .registry(child1)
+        .unwrap()
+        .register(&mut receiver1, RECEIVER1, Interest::READABLE)
+        .unwrap();
+    multi_poll
+        .registry(child2)
+        .unwrap()
+        .register(&mut receiver2, RECEIVER2, Interest::READABLE)
+        .unwrap();
+
+    multi_poll.poll(Some(Duration::from_millis(10))).unwrap();
+    assert_eq!(multi_poll.iter().count(), 0);
+
+    sender1.write_all(DATA1).unwrap();
+    sender2.write_all(DATA1).unwrap();
+
+    multi_poll.poll(Some(Duration::from_millis(500))).unwrap();
+    let mut got: Vec<_> = multi_poll
+        .iter()
+        .map(|(child, event)| {
+            assert!(event.is_readable());
+            (child, event.token())
+        })
+        .collect();
+    got.sort();
+    assert_eq!(got, vec![(child1, RECEIVER1), (child2, RECEIVER2)]);
+
+    let mut buf = [0; 20];
+    assert_eq!(receiver1.read(&mut buf).unwrap(), DATA1.len());
+    assert_eq!(receiver2.read(&mut buf).unwrap(), DATA1.len());
+
+    // Only the second child should be polled.
+    sender2.write_all(DATA1).unwrap();
+    multi_poll.poll(Some(Duration::from_millis(500))).unwrap();
+    let got: Vec<_> = multi_poll
+        .iter()
+        .map(|(child, event)| (child, event.token()))
+        .collect();
+    assert_eq!(got, vec![(child2, RECEIVER2)]);
+}
+
+#[test]
+fn multi_poll_full_child_events() {
+    init();
+
+    let mut multi_poll = MultiPoll::new(8).unwrap();
+    // Child can only return a single event at a time.
+    let child = multi_poll.add(Poll::new().unwrap(), 1).unwrap();
+
+    let (mut sender1, mut receiver1) = pipe::new().unwrap();
+    let (mut sender2, mut receiver2) = pipe::new().unwrap();
+    let registry = multi_poll.registry(child).unwrap();
+    registry
+        .register(&mut receiver1, RECEIVER1, Interest::READABLE)
+        .unwrap();
+    registry
+        .register(&mut receiver2, RECEIVER2, Interest::READABLE)
+        .unwrap();
+
+    sender1.write_all(DATA1).unwrap();
+    sender2.write_all(DATA1).unwrap();
+
+    // Even though the parent will only report the child ready once, we should
+    // still receive both events.
+    let mut tokens = Vec::new();
+    for _ in 0..2 {
+        multi_poll.poll(Some(Duration::from_millis(500))).unwrap();
+        tokens.extend(multi_poll.iter().map(|(_, event)| event.token()));
+    }
+    tokens.sort();
+    assert_eq!(tokens, vec![RECEIVER1, RECEIVER2]);
+}
@@ -0,0 +1,86 @@
+#![cfg(all(target_os = "linux", feature = "os-poll", feature = "os-ext"))]
+// The children are reaped using `PidFd::try_wait`, which Clippy doesn't know.
+#![allow(unknown_lints, clippy::zombie_processes)]
+
+use std::process::{Command, Stdio};
+
+use mio::unix::pidfd::PidFd;
+use mio::{Events, Interest, Poll, Token};
+
+mod util;
+use util::{expect_events, expect_no_events, init, ExpectEvent};
+
+const PIDFD: Token = Token(0);
+
+#[test]
+fn pidfd_exit_event() {
+    init();
+    let mut poll = Poll::new().unwrap();
+    let mut events = Events::with_capacity(8);
+
+    let mut child = Command::new("cat")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .spawn()
+        .expect("failed to spawn child");
+    let mut pidfd = PidFd::from_child(&child).unwrap();
+    poll.registry()
+        .register(&mut pidfd, PIDFD, Interest::READABLE)
+        .unwrap();
+
+    // The child is still running.
+    expect_no_events(&mut poll, &mut events);
+    assert!(pidfd.try_wait().unwrap().is_none());
+
+    // Closing stdin makes `cat` exit.
+    drop(child.stdin.take());
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(PIDFD, Interest::READABLE)],
+    );
+
+    let status = pidfd.try_wait().unwrap().expect("child didn't exit");
+    assert!(status.success());
+}
+
+#[test]
+fn pidfd_exit_status() {
+    use std::os::unix::process::ExitStatusExt;
+
+    init();
+    let mut poll = Poll::new().unwrap();
+    let mut events = Events::with_capacity(8);
+
+    let child = Command::new("sh")
+        .arg("-c")
+        .arg("exit 3")
+        .spawn()
+        .expect("failed to spawn child");
+    let mut pidfd = PidFd::from_child(&child).unwrap();
+    poll.registry()
+        .register(&mut pidfd, PIDFD, Interest::READABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(PIDFD, Interest::READABLE)],
+    );
+    let status = pidfd.try_wait().unwrap().expect("child didn't exit");
+    assert_eq!(status.code(), Some(3));
+    drop(pidfd);
+
+    let mut child = Command::new("sleep").arg("10").spawn().unwrap();
+    let mut pidfd = PidFd::from_child(&child).unwrap();
+    poll.registry()
+        .register(&mut pidfd, PIDFD, Interest::READABLE)
+        .unwrap();
+    child.kill().unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(PIDFD, Interest::READABLE)],
+    );
+    let status = pidfd.try_wait().unwrap().expect("child didn't exit");
+    assert_eq!(status.signal(), Some(libc::SIGKILL));
+}
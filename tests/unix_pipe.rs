@@ -1,6 +1,9 @@
 #![cfg(all(unix, feature = "os-poll", feature = "os-ext"))]
 
+use std::ffi::CString;
+use std::fs::OpenOptions;
 use std::io::{Read, Write};
+use std::os::unix::ffi::OsStrExt;
 use std::process::{Command, Stdio};
 use std::sync::{Arc, Barrier};
 use std::thread;
@@ -10,7 +13,9 @@ use mio::unix::pipe::{self, Receiver, Sender};
 use mio::{Events, Interest, Poll, Token};
 
 mod util;
-use util::{assert_would_block, expect_events, ExpectEvent};
+use util::{
+    assert_would_block, expect_events, expect_no_events, init_with_poll, temp_file, ExpectEvent,
+};
 
 const RECEIVER: Token = Token(0);
 const SENDER: Token = Token(1);
@@ -215,3 +220,105 @@ pub fn expect_one_closed_event(poll: &mut Poll, events: &mut Events, token: Toke
     }
     assert!(iter.next().is_none());
 }
+
+#[test]
+fn stdio_child_process_io() {
+    let (stdin, child_stdin) = pipe::stdio_sender().unwrap();
+    let (child_stdout, mut receiver) = pipe::stdio_receiver().unwrap();
+    // `cat` simply echo everything that we write via standard in.
+    let mut child = Command::new("cat")
+        .env_clear()
+        .stdin(child_stdin)
+        .stdout(child_stdout)
+        .spawn()
+        .expect("failed to start `cat` command");
+
+    // Our end of the pipes is non-blocking.
+    let mut buf = [0; 20];
+    assert_would_block(receiver.read(&mut buf));
+
+    let mut poll = Poll::new().unwrap();
+    let mut events = Events::with_capacity(8);
+    poll.registry()
+        .register(&mut receiver, RECEIVER, Interest::READABLE)
+        .unwrap();
+
+    (&stdin).write_all(DATA1).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(RECEIVER, Interest::READABLE)],
+    );
+    let n = receiver.read(&mut buf).unwrap();
+    assert_eq!(&buf[..n], DATA1);
+
+    // Closing standard in makes `cat` exit.
+    drop(stdin);
+    expect_one_closed_event(&mut poll, &mut events, RECEIVER, true);
+    assert_eq!(receiver.read(&mut buf).unwrap(), 0);
+
+    assert!(child.wait().unwrap().success());
+}
+
+#[test]
+fn fifo_readable() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let path = temp_file("unix_pipe::fifo_readable");
+    let c_path = CString::new(path.as_os_str().as_bytes()).unwrap();
+    assert_eq!(unsafe { libc::mkfifo(c_path.as_ptr(), 0o600) }, 0);
+
+    // Doesn't block, even though there is no writer.
+    let mut receiver = pipe::open_fifo(&path).unwrap();
+    poll.registry()
+        .register(&mut receiver, RECEIVER, Interest::READABLE)
+        .unwrap();
+    expect_no_events(&mut poll, &mut events);
+    let mut buf = [0; 20];
+    assert_would_block(receiver.read(&mut buf));
+
+    let mut writer = OpenOptions::new().write(true).open(&path).unwrap();
+    writer.write_all(DATA1).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(RECEIVER, Interest::READABLE)],
+    );
+    let n = receiver.read(&mut buf).unwrap();
+    assert_eq!(&buf[..n], DATA1);
+
+    // Closing the only writer doesn't close the receiving end.
+    drop(writer);
+    expect_no_events(&mut poll, &mut events);
+    assert_would_block(receiver.read(&mut buf));
+
+    // Not a FIFO.
+    let path = temp_file("unix_pipe::fifo_readable_file");
+    std::fs::File::create(&path).unwrap();
+    let err = pipe::open_fifo(&path).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn events_write_closed() {
+    let mut poll = Poll::new().unwrap();
+    let mut events = Events::with_capacity(8);
+
+    let (mut sender, receiver) = pipe::new().unwrap();
+    poll.registry()
+        .register(&mut sender, SENDER, Interest::WRITABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(SENDER, Interest::WRITABLE)],
+    );
+    assert_eq!(events.write_closed().count(), 0);
+
+    drop(receiver);
+    poll.poll(&mut events, Some(Duration::from_secs(1)))
+        .unwrap();
+    let write_closed: Vec<_> = events.write_closed().map(|event| event.token()).collect();
+    assert_eq!(write_closed, vec![SENDER]);
+    assert_eq!(events.readable().count(), 0);
+}
@@ -0,0 +1,49 @@
+#![cfg(all(target_os = "linux", feature = "os-poll", feature = "os-ext"))]
+
+use std::mem;
+
+use mio::unix::signalfd::SignalFd;
+use mio::{Events, Interest, Poll, Token};
+
+mod util;
+use util::{assert_would_block, expect_events, expect_no_events, init, ExpectEvent};
+
+const SIGNALS: Token = Token(0);
+
+#[test]
+fn signalfd_delivers_signal() {
+    init();
+    let mut poll = Poll::new().unwrap();
+    let mut events = Events::with_capacity(8);
+
+    // Block `SIGUSR1` in this thread, so that the signal raised below is
+    // delivered to the signalfd.
+    unsafe {
+        let mut set: libc::sigset_t = mem::zeroed();
+        libc::sigemptyset(&mut set);
+        libc::sigaddset(&mut set, libc::SIGUSR1);
+        assert_eq!(
+            libc::pthread_sigmask(libc::SIG_BLOCK, &set, std::ptr::null_mut()),
+            0
+        );
+    }
+
+    let mut signals = SignalFd::new(&[libc::SIGUSR1]).unwrap();
+    poll.registry()
+        .register(&mut signals, SIGNALS, Interest::READABLE)
+        .unwrap();
+    expect_no_events(&mut poll, &mut events);
+    assert_would_block(signals.read_signal());
+
+    assert_eq!(unsafe { libc::raise(libc::SIGUSR1) }, 0);
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(SIGNALS, Interest::READABLE)],
+    );
+
+    let info = signals.read_signal().unwrap();
+    assert_eq!(info.signal(), libc::SIGUSR1);
+    assert_eq!(info.pid(), std::process::id());
+    assert_would_block(signals.read_signal());
+}
@@ -537,3 +537,33 @@ fn new_noop_listener(
     });
     (handle, receiver.recv().unwrap())
 }
+
+#[test]
+fn unix_stream_socket_introspection() {
+    use mio::net::{Family, Kind};
+
+    let (stream, _) = UnixStream::pair().unwrap();
+    assert_eq!(stream.socket_family().unwrap(), Family::Unix);
+    assert_eq!(stream.socket_type().unwrap(), Kind::Stream);
+    assert!(!stream.is_listening().unwrap());
+}
+
+#[test]
+fn unix_stream_bytes_available() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let (mut s1, mut s2) = UnixStream::pair().unwrap();
+    poll.registry()
+        .register(&mut s2, TOKEN_1, Interest::READABLE)
+        .unwrap();
+    assert_eq!(s2.bytes_available().unwrap(), 0);
+
+    checked_write!(s1.write(DATA1));
+    checked_write!(s1.write(DATA2));
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(TOKEN_1, Interest::READABLE)],
+    );
+    assert_eq!(s2.bytes_available().unwrap(), DATA1_LEN + DATA2_LEN);
+}